@@ -817,6 +817,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn truncated_header() {
+        // Header ends before the data section size.
+        let err = validate_raw_eof(hex!("ef000101000402000100010400").into());
+        assert_eq!(err, Err(EofError::Decode(EofDecodeError::MissingInput)));
+    }
+
+    #[test]
+    fn section_stack_underflow() {
+        // Section 0 calls section 1, section 1 pops from an empty stack before returning.
+        let eof = hex!("ef000101000802000200040002040000000080000100010001e300010050e4");
+        let err = validate_raw_eof_inner(eof.into(), None);
+        assert_eq!(
+            err,
+            Err(EofError::Validation(EofValidationError::StackUnderflow))
+        );
+
+        // Same container with section 1 pushing its output is valid.
+        let eof = hex!("ef000101000802000200040002040000000080000100010001e30001005fe4");
+        let eof = validate_raw_eof_inner(eof.into(), None).unwrap();
+        assert_eq!(eof.body.code_section.len(), 2);
+        assert_eq!(eof.body.types_section[1].outputs, 1);
+    }
+
     #[test]
     fn size_limit() {
         let eof = validate_raw_eof_inner(
@@ -1,7 +1,9 @@
 mod analyzed;
 mod jump_map;
 mod raw;
+mod stats;
 
 pub use analyzed::LegacyAnalyzedBytecode;
 pub use jump_map::JumpTable;
 pub use raw::{analyze_legacy, LegacyRawBytecode};
+pub use stats::{eof_static_gas, static_gas, BytecodeStats};
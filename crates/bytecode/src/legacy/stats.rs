@@ -0,0 +1,200 @@
+use super::LegacyAnalyzedBytecode;
+use crate::opcode::*;
+
+/// Static statistics of a legacy bytecode, see [`LegacyAnalyzedBytecode::stats`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BytecodeStats {
+    /// Number of occurrences of each opcode, indexed by opcode.
+    ///
+    /// Bytes that are PUSH immediates are not counted.
+    pub opcode_counts: [u64; 256],
+    /// Number of JUMPDEST opcodes.
+    pub jumpdests: u64,
+    /// Number of bytes that are PUSH immediates.
+    pub push_data_bytes: u64,
+    /// Sum of the static gas of every opcode.
+    ///
+    /// Dynamic gas (memory expansion, copies, cold accesses, etc.) is not included and state
+    /// accessing opcodes are priced as warm accesses, see [`static_gas`].
+    pub static_gas: u64,
+}
+
+impl Default for BytecodeStats {
+    fn default() -> Self {
+        Self {
+            opcode_counts: [0; 256],
+            jumpdests: 0,
+            push_data_bytes: 0,
+            static_gas: 0,
+        }
+    }
+}
+
+impl BytecodeStats {
+    /// Returns the number of occurrences of the opcode.
+    pub fn count(&self, opcode: u8) -> u64 {
+        self.opcode_counts[opcode as usize]
+    }
+
+    /// Returns the total number of opcodes.
+    pub fn opcodes(&self) -> u64 {
+        self.opcode_counts.iter().sum()
+    }
+}
+
+impl LegacyAnalyzedBytecode {
+    /// Computes [`BytecodeStats`] of the original bytecode.
+    ///
+    /// PUSH immediates are skipped, so data bytes are never counted as opcodes. A truncated
+    /// PUSH at the end of the code only counts the bytes that are present.
+    pub fn stats(&self) -> BytecodeStats {
+        let code = self.original_byte_slice();
+        let mut stats = BytecodeStats::default();
+        let mut i = 0;
+        while i < code.len() {
+            let opcode = code[i];
+            stats.opcode_counts[opcode as usize] += 1;
+            stats.static_gas += static_gas(opcode);
+            if opcode == JUMPDEST {
+                stats.jumpdests += 1;
+            }
+
+            let push_len = if (PUSH1..=PUSH32).contains(&opcode) {
+                (opcode - PUSH0) as usize
+            } else {
+                0
+            };
+            let data_len = push_len.min(code.len() - i - 1);
+            stats.push_data_bytes += data_len as u64;
+            i += 1 + data_len;
+        }
+        stats
+    }
+}
+
+/// Returns the static gas of the opcode in legacy bytecode for the latest spec.
+///
+/// Opcodes that access the state are priced as warm accesses and SSTORE as a no-op store.
+/// Unknown opcodes, including the EOF-only ones, cost zero gas.
+pub const fn static_gas(opcode: u8) -> u64 {
+    match opcode {
+        DATALOAD | DATALOADN | DATASIZE | DATACOPY | RJUMP | RJUMPI | RJUMPV | CALLF | RETF
+        | JUMPF | DUPN | SWAPN | EXCHANGE | EOFCREATE | RETURNCONTRACT | RETURNDATALOAD
+        | EXTCALL | EXTDELEGATECALL | EXTSTATICCALL => 0,
+        _ => eof_static_gas(opcode),
+    }
+}
+
+/// Returns the static gas of the opcode in EOF bytecode for the latest spec.
+///
+/// Priced as [`static_gas`], with the EOF-only opcodes included. Opcodes that EOF rejects keep
+/// their legacy price.
+pub const fn eof_static_gas(opcode: u8) -> u64 {
+    match opcode {
+        STOP | RETURN | REVERT | INVALID | RETURNCONTRACT => 0,
+        JUMPDEST => 1,
+        ADDRESS | ORIGIN | CALLER | CALLVALUE | CALLDATASIZE | CODESIZE | GASPRICE
+        | RETURNDATASIZE | COINBASE | TIMESTAMP | NUMBER | DIFFICULTY | GASLIMIT | CHAINID
+        | BASEFEE | BLOBBASEFEE | POP | PC | MSIZE | GAS | PUSH0 | DATASIZE | RJUMP => 2,
+        ADD
+        | SUB
+        | NOT
+        | LT
+        | GT
+        | SLT
+        | SGT
+        | EQ
+        | ISZERO
+        | AND
+        | OR
+        | XOR
+        | BYTE
+        | SHL
+        | SHR
+        | SAR
+        | CALLDATALOAD
+        | CALLDATACOPY
+        | CODECOPY
+        | RETURNDATACOPY
+        | BLOBHASH
+        | MLOAD
+        | MSTORE
+        | MSTORE8
+        | MCOPY
+        | PUSH1..=PUSH32
+        | DUP1..=DUP16
+        | SWAP1..=SWAP16
+        | DATALOADN
+        | DATACOPY
+        | RETF
+        | DUPN
+        | SWAPN
+        | EXCHANGE
+        | RETURNDATALOAD => 3,
+        RJUMPI | RJUMPV | DATALOAD => 4,
+        MUL | DIV | SDIV | MOD | SMOD | SIGNEXTEND | SELFBALANCE | CALLF | JUMPF => 5,
+        ADDMOD | MULMOD | JUMP => 8,
+        EXP | JUMPI => 10,
+        BLOCKHASH => 20,
+        KECCAK256 => 30,
+        BALANCE | EXTCODESIZE | EXTCODECOPY | EXTCODEHASH | SLOAD | SSTORE | TLOAD | TSTORE
+        | CALL | CALLCODE | DELEGATECALL | STATICCALL | EXTCALL | EXTDELEGATECALL
+        | EXTSTATICCALL => 100,
+        LOG0..=LOG4 => 375 * (1 + (opcode - LOG0) as u64),
+        SELFDESTRUCT => 5000,
+        CREATE | CREATE2 | EOFCREATE => 32000,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{legacy::LegacyRawBytecode, Bytecode};
+    use primitives::Bytes;
+
+    #[test]
+    fn stats_skip_push_data() {
+        // PUSH2 0x5b5b JUMPDEST PUSH1 0x01 ADD JUMPDEST SSTORE PUSH32 <truncated, 2 bytes>
+        let code = [
+            PUSH2, JUMPDEST, JUMPDEST, JUMPDEST, PUSH1, 0x01, ADD, JUMPDEST, SSTORE, PUSH32, 0x5b,
+            0x5b,
+        ];
+        let stats = LegacyRawBytecode(Bytes::from(code.to_vec()))
+            .into_analyzed()
+            .stats();
+
+        assert_eq!(stats.count(PUSH2), 1);
+        assert_eq!(stats.count(PUSH1), 1);
+        assert_eq!(stats.count(PUSH32), 1);
+        assert_eq!(stats.count(ADD), 1);
+        assert_eq!(stats.count(SSTORE), 1);
+        assert_eq!(stats.count(JUMPDEST), 2);
+        assert_eq!(stats.jumpdests, 2);
+        assert_eq!(stats.opcodes(), 7);
+        assert_eq!(stats.push_data_bytes, 2 + 1 + 2);
+        assert_eq!(stats.static_gas, 3 + 1 + 3 + 3 + 1 + 100 + 3);
+    }
+
+    #[test]
+    fn eof_only_opcodes_are_free_in_legacy_stats() {
+        let code = [RJUMP, 0x00, 0x00, DATACOPY, EXTCALL, EOFCREATE, 0x00, STOP];
+        let stats = LegacyRawBytecode(Bytes::from(code.to_vec()))
+            .into_analyzed()
+            .stats();
+
+        // Immediates are not skipped in legacy bytecode, they are counted as opcodes.
+        assert_eq!(stats.opcodes(), code.len() as u64);
+        assert_eq!(stats.static_gas, 0);
+        assert_eq!(eof_static_gas(RJUMP), 2);
+        assert_eq!(eof_static_gas(EOFCREATE), 32000);
+    }
+
+    #[test]
+    fn stats_of_empty_bytecode() {
+        let Bytecode::LegacyAnalyzed(analyzed) = Bytecode::new() else {
+            unreachable!()
+        };
+        assert_eq!(analyzed.stats(), BytecodeStats::default());
+    }
+}
@@ -23,4 +23,4 @@ pub use eof::{
     },
     Eof, EOF_MAGIC, EOF_MAGIC_BYTES, EOF_MAGIC_HASH,
 };
-pub use legacy::{JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
+pub use legacy::{BytecodeStats, JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
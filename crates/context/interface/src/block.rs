@@ -1,6 +1,9 @@
 pub mod blob;
 
-pub use blob::{calc_blob_gasprice, calc_excess_blob_gas, BlobExcessGasAndPrice};
+pub use blob::{
+    calc_blob_gasprice, calc_blob_gasprice_with_spec, calc_excess_blob_gas,
+    calc_excess_blob_gas_with_spec, BlobExcessGasAndPrice,
+};
 
 use auto_impl::auto_impl;
 use primitives::{Address, B256, U256};
@@ -68,6 +71,13 @@ pub trait Block {
     fn blob_excess_gas(&self) -> Option<u64> {
         self.blob_excess_gas_and_price().map(|a| a.excess_blob_gas)
     }
+
+    /// Returns the hash of a previous block that overrides the one in the database.
+    ///
+    /// Used by the `BLOCKHASH` opcode for numbers inside the block hash window.
+    fn block_hash_override(&self, _number: u64) -> Option<B256> {
+        None
+    }
 }
 
 #[auto_impl(&, &mut, Box, Arc)]
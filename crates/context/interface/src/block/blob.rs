@@ -1,6 +1,7 @@
-use specification::eip4844::{
-    BLOB_GASPRICE_UPDATE_FRACTION, MIN_BLOB_GASPRICE, TARGET_BLOB_GAS_PER_BLOCK,
-};
+use primitives::{eip4844, eip7918};
+use specification::hardfork::SpecId;
+
+pub use primitives::eip4844::fake_exponential;
 
 /// Structure holding block blob excess gas and it calculates blob fee
 ///
@@ -27,64 +28,76 @@ impl BlobExcessGasAndPrice {
             blob_gasprice,
         }
     }
+
+    /// Creates a new instance with the blob gas price of the spec, see
+    /// [`calc_blob_gasprice_with_spec`].
+    pub fn new_with_spec(excess_blob_gas: u64, spec: SpecId) -> Self {
+        Self {
+            excess_blob_gas,
+            blob_gasprice: calc_blob_gasprice_with_spec(excess_blob_gas, spec),
+        }
+    }
 }
 
 /// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and `excess_blob_gas`.
 ///
-/// See also [the EIP-4844 helpers]<https://eips.ethereum.org/EIPS/eip-4844#helpers>
-/// (`calc_excess_blob_gas`).
+/// Uses the Cancun blob target, see [`primitives::eip4844::calc_excess_blob_gas`] for Prague.
 #[inline]
 pub fn calc_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_used: u64) -> u64 {
-    (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(TARGET_BLOB_GAS_PER_BLOCK)
+    eip4844::calc_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used, false)
 }
 
 /// Calculates the blob gas price from the header's excess blob gas field.
 ///
-/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
-/// (`get_blob_gasprice`).
+/// Uses the Cancun update fraction, see [`primitives::eip4844::get_base_fee_per_blob_gas`] for
+/// Prague.
 #[inline]
 pub fn calc_blob_gasprice(excess_blob_gas: u64) -> u128 {
-    fake_exponential(
-        MIN_BLOB_GASPRICE,
-        excess_blob_gas,
-        BLOB_GASPRICE_UPDATE_FRACTION,
-    )
+    eip4844::get_base_fee_per_blob_gas(excess_blob_gas, false)
 }
 
-/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
-///
-/// This is used to calculate the blob price.
-///
-/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
-/// (`fake_exponential`).
+/// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used`,
+/// `excess_blob_gas` and `base_fee_per_gas`.
 ///
-/// # Panics
-///
-/// This function panics if `denominator` is zero.
+/// Uses the Prague blob target since Prague. Since Osaka the excess follows the
+/// [EIP-7918](https://eips.ethereum.org/EIPS/eip-7918) reserve price, see
+/// [`eip7918::calc_excess_blob_gas`].
 #[inline]
-pub fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u128 {
-    assert_ne!(denominator, 0, "attempt to divide by zero");
-    let factor = factor as u128;
-    let numerator = numerator as u128;
-    let denominator = denominator as u128;
-
-    let mut i = 1;
-    let mut output = 0;
-    let mut numerator_accum = factor * denominator;
-    while numerator_accum > 0 {
-        output += numerator_accum;
-
-        // Denominator is asserted as not zero at the start of the function.
-        numerator_accum = (numerator_accum * numerator) / (denominator * i);
-        i += 1;
+pub fn calc_excess_blob_gas_with_spec(
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+    parent_base_fee_per_gas: u64,
+    spec: SpecId,
+) -> u64 {
+    if spec.is_enabled_in(SpecId::OSAKA) {
+        eip7918::calc_excess_blob_gas(
+            parent_excess_blob_gas,
+            parent_blob_gas_used,
+            parent_base_fee_per_gas,
+        )
+    } else {
+        eip4844::calc_excess_blob_gas(
+            parent_excess_blob_gas,
+            parent_blob_gas_used,
+            spec.is_enabled_in(SpecId::PRAGUE),
+        )
     }
-    output / denominator
+}
+
+/// Calculates the blob gas price from the header's excess blob gas field.
+///
+/// Uses the Prague update fraction since Prague.
+#[inline]
+pub fn calc_blob_gasprice_with_spec(excess_blob_gas: u64, spec: SpecId) -> u128 {
+    eip4844::get_base_fee_per_blob_gas(excess_blob_gas, spec.is_enabled_in(SpecId::PRAGUE))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use specification::eip4844::GAS_PER_BLOB;
+    use specification::eip4844::{
+        BLOB_GASPRICE_UPDATE_FRACTION, GAS_PER_BLOB, TARGET_BLOB_GAS_PER_BLOCK,
+    };
 
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L27
     #[test]
@@ -140,6 +153,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn excess_blob_gas_reserve_price() {
+        // 100 gwei execution base fee keeps blobs at the minimum blob base fee under the
+        // reserve price.
+        let base_fee = 100_000_000_000;
+        let used = 6 * GAS_PER_BLOB;
+        assert_eq!(
+            calc_excess_blob_gas_with_spec(0, used, base_fee, SpecId::PRAGUE),
+            0
+        );
+        assert_eq!(
+            calc_excess_blob_gas_with_spec(0, used, base_fee, SpecId::OSAKA),
+            2 * GAS_PER_BLOB
+        );
+        assert_eq!(calc_excess_blob_gas_with_spec(0, used, 0, SpecId::OSAKA), 0);
+
+        // The blob gas price itself is not raised by the reserve price.
+        assert_eq!(
+            BlobExcessGasAndPrice::new_with_spec(0, SpecId::OSAKA).blob_gasprice,
+            1
+        );
+        assert_eq!(
+            BlobExcessGasAndPrice::new_with_spec(10 * 1024 * 1024, SpecId::OSAKA).blob_gasprice,
+            8
+        );
+    }
+
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L60
     #[test]
     fn test_calc_blob_fee() {
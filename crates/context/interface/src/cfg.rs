@@ -1,8 +1,8 @@
 use auto_impl::auto_impl;
 use core::fmt::Debug;
 use core::hash::Hash;
-use primitives::{TxKind, U256};
-use specification::hardfork::SpecId;
+use primitives::{keccak256, Address, Bytes, TxKind, B256, U256};
+use specification::{chain_spec::ChainSpec, eip7825, hardfork::SpecId};
 
 #[auto_impl(&, &mut, Box, Arc)]
 pub trait Cfg {
@@ -15,6 +15,11 @@ pub trait Cfg {
 
     fn max_code_size(&self) -> usize;
 
+    /// Returns the EIP-3860 initcode size limit.
+    fn max_initcode_size(&self) -> usize {
+        self.max_code_size().saturating_mul(2)
+    }
+
     fn is_eip3607_disabled(&self) -> bool;
 
     fn is_balance_check_disabled(&self) -> bool;
@@ -25,9 +30,181 @@ pub trait Cfg {
 
     fn is_nonce_check_disabled(&self) -> bool;
 
+    /// Returns `true` if the caller is not charged for gas.
+    fn is_fee_charge_disabled(&self) -> bool {
+        false
+    }
+
     fn is_base_fee_check_disabled(&self) -> bool;
+
+    /// Returns `true` if the EIP-7623 calldata floor is not applied.
+    fn is_calldata_floor_disabled(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if running out of gas does not halt execution.
+    fn is_gas_metering_disabled(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the transaction should be executed as a static call.
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    /// Returns the address that receives the base fee, if it is not burned.
+    fn base_fee_recipient(&self) -> Option<Address> {
+        None
+    }
+
+    /// Returns `true` if the coinbase is warm at the start of the transaction (EIP-3651).
+    fn is_coinbase_warm(&self) -> bool {
+        self.spec().into().is_enabled_in(SpecId::SHANGHAI)
+    }
+
+    /// Returns the EIP-161 state clearing override, `None` if it follows the spec.
+    fn force_empty_account_removal(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns the maximum input length accepted by precompiles, `None` if unlimited.
+    fn precompile_max_input_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the maximum output size of `RETURN` and `REVERT`, `None` if unlimited.
+    fn max_return_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the function used to compute the code hash of newly created contracts.
+    fn code_hasher(&self) -> fn(&[u8]) -> B256 {
+        |code| keccak256(code)
+    }
+
+    /// Returns the activation rules of the chain, `None` if they follow the spec.
+    fn chain_spec(&self) -> Option<&dyn ChainSpec> {
+        None
+    }
+
+    /// Returns the `SSTORE` gas model override, `None` if it follows the spec.
+    fn sstore_model(&self) -> Option<SstoreModel> {
+        None
+    }
+
+    /// Returns the maximum dynamic gas spent by a single instruction, `None` if unlimited.
+    fn per_opcode_gas_cap(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the function mapping the transaction caller to the `msg.sender` of the top level
+    /// call or create, identity if addresses are not aliased.
+    fn address_alias(&self) -> fn(Address) -> Address {
+        |address| address
+    }
+
+    /// Returns the quotient limiting the gas refund, `None` if it follows the spec.
+    fn refund_quotient(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the nonce of newly created contracts, `None` if it follows the spec.
+    fn contract_initial_nonce(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the maximum size of the transaction data, `None` if unlimited.
+    fn max_tx_input_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the maximum gas limit of a transaction, `None` if it is not limited.
+    fn max_tx_gas_limit(&self) -> Option<u64> {
+        self.spec()
+            .into()
+            .is_enabled_in(SpecId::OSAKA)
+            .then_some(eip7825::TX_GAS_LIMIT_CAP)
+    }
+
+    /// Returns the gas charged per word of hashed data, `None` if it follows the spec.
+    fn keccak_word_cost(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns `true` if calls to inactive addresses in `0x01..=0xff` return without execution.
+    fn is_precompile_range_reserved(&self) -> bool {
+        false
+    }
+
+    /// Returns the stipend of value transferring calls, `None` if it follows the spec.
+    fn call_stipend(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns `true` if the state of the top level frame is captured when it halts.
+    fn is_halt_state_captured(&self) -> bool {
+        false
+    }
+
+    /// Returns the native implementation of the address, `None` if calls to it are not
+    /// intercepted.
+    fn intercept(&self, _address: &Address) -> Option<InterceptFn> {
+        None
+    }
+
+    /// Returns the KZG proof verifier of the point evaluation precompile, `None` for the mainnet
+    /// trusted setup.
+    fn kzg_settings(&self) -> Option<&dyn KzgVerifier> {
+        None
+    }
+
+    /// Returns the wall-clock deadline after which precompiles are interrupted.
+    #[cfg(feature = "std")]
+    fn precompile_deadline(&self) -> Option<std::time::Instant> {
+        None
+    }
+
+    /// Returns the wall-clock deadline after which execution halts with a timeout.
+    #[cfg(feature = "std")]
+    fn execution_deadline(&self) -> Option<std::time::Instant> {
+        None
+    }
 }
 
+/// Native implementation of an intercepted address, see [`Cfg::intercept`].
+///
+/// Receives the call input and gas limit, and returns the consumed gas and the output. The call
+/// runs out of gas if the consumed gas exceeds the limit.
+pub type InterceptFn = fn(input: &[u8], gas_limit: u64) -> (u64, Bytes);
+
+/// Verifier of the KZG proofs checked by the point evaluation precompile, see
+/// [`Cfg::kzg_settings`].
+///
+/// Lets chains with a different trusted setup, e.g. testnets, verify proofs against it.
+pub trait KzgVerifier: Debug + Send + Sync {
+    /// Returns `true` if `proof` shows that the polynomial of `commitment` evaluates to `y` at
+    /// `z`.
+    fn verify_kzg_proof(
+        &self,
+        commitment: &[u8; 48],
+        z: &[u8; 32],
+        y: &[u8; 32],
+        proof: &[u8; 48],
+    ) -> bool;
+}
+
+/// Verifiers are compared by identity.
+impl PartialEq for dyn KzgVerifier {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(
+            self as *const Self as *const u8,
+            other as *const Self as *const u8,
+        )
+    }
+}
+
+impl Eq for dyn KzgVerifier {}
+
 /// What bytecode analysis to perform
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -39,6 +216,32 @@ pub enum AnalysisKind {
     Analyse,
 }
 
+/// `SSTORE` gas cost and refund rules.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SstoreModel {
+    /// Set and reset costs without net metering, before Istanbul.
+    Frontier,
+    /// EIP-2200: Structured Definitions for Net Gas Metering.
+    Istanbul,
+    /// EIP-2929: Gas cost increases for state access opcodes.
+    Berlin,
+    /// EIP-3529: Reduction in refunds.
+    London,
+}
+
+impl SstoreModel {
+    /// Returns the first spec that uses this model.
+    pub const fn spec_id(self) -> SpecId {
+        match self {
+            Self::Frontier => SpecId::FRONTIER,
+            Self::Istanbul => SpecId::ISTANBUL,
+            Self::Berlin => SpecId::BERLIN,
+            Self::London => SpecId::LONDON,
+        }
+    }
+}
+
 /// Transaction destination
 pub type TransactTo = TxKind;
 
@@ -61,3 +264,68 @@ pub trait CfgGetter {
 
     fn cfg(&self) -> &Self::Cfg;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Implements only the methods without a default body.
+    struct MinimalCfg(SpecId);
+
+    impl Cfg for MinimalCfg {
+        type Spec = SpecId;
+
+        fn chain_id(&self) -> u64 {
+            1
+        }
+
+        fn spec(&self) -> Self::Spec {
+            self.0
+        }
+
+        fn max_code_size(&self) -> usize {
+            0x6000
+        }
+
+        fn is_eip3607_disabled(&self) -> bool {
+            false
+        }
+
+        fn is_balance_check_disabled(&self) -> bool {
+            false
+        }
+
+        fn is_gas_refund_disabled(&self) -> bool {
+            false
+        }
+
+        fn is_block_gas_limit_disabled(&self) -> bool {
+            false
+        }
+
+        fn is_nonce_check_disabled(&self) -> bool {
+            false
+        }
+
+        fn is_base_fee_check_disabled(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn defaults_follow_the_spec() {
+        let cfg = MinimalCfg(SpecId::PRAGUE);
+        assert_eq!(cfg.max_initcode_size(), 0xC000);
+        assert!(cfg.is_coinbase_warm());
+        assert!(!MinimalCfg(SpecId::LONDON).is_coinbase_warm());
+        assert_eq!(cfg.max_tx_gas_limit(), None);
+        assert_eq!(
+            MinimalCfg(SpecId::OSAKA).max_tx_gas_limit(),
+            Some(eip7825::TX_GAS_LIMIT_CAP)
+        );
+        assert_eq!((cfg.code_hasher())(&[]), primitives::KECCAK_EMPTY);
+        assert_eq!((cfg.address_alias())(Address::ZERO), Address::ZERO);
+        assert!(cfg.intercept(&Address::ZERO).is_none());
+        assert!(cfg.chain_spec().is_none() && cfg.kzg_settings().is_none());
+    }
+}
@@ -20,6 +20,11 @@ pub trait Host: TransactionGetter + BlockGetter + CfgGetter {
     /// Gets the block hash of the given block `number`.
     fn block_hash(&mut self, number: u64) -> Option<B256>;
 
+    /// Gets the blob versioned hash of the transaction at `index` and records the read.
+    ///
+    /// Returns `None` if the transaction is not a blob transaction or `index` is out of bounds.
+    fn blob_hash(&mut self, index: usize) -> Option<B256>;
+
     /// Gets balance of `address` and if the account is cold.
     fn balance(&mut self, address: Address) -> Option<StateLoad<U256>>;
 
@@ -1,5 +1,5 @@
 use super::{Host, SStoreResult, SelfDestructResult};
-use crate::{Block, BlockGetter, Cfg, CfgGetter, Transaction, TransactionGetter};
+use crate::{Block, BlockGetter, Cfg, CfgGetter, Transaction, TransactionGetter, TransactionType};
 use primitives::{hash_map::Entry, Address, Bytes, HashMap, Log, B256, KECCAK_EMPTY, U256};
 use std::vec::Vec;
 
@@ -83,6 +83,15 @@ impl<TX: Transaction, BLOCK: Block, CFG: Cfg> Host for DummyHost<BLOCK, TX, CFG>
         Some(B256::ZERO)
     }
 
+    #[inline]
+    fn blob_hash(&mut self, index: usize) -> Option<B256> {
+        let tx = self.tx();
+        if tx.tx_type() != TransactionType::Eip4844 {
+            return None;
+        }
+        tx.blob_versioned_hashes().get(index).copied()
+    }
+
     #[inline]
     fn balance(&mut self, _address: Address) -> Option<StateLoad<U256>> {
         Some(Default::default())
@@ -63,12 +63,28 @@ pub trait Journal {
 
     fn warm_account(&mut self, address: Address);
 
+    /// Marks the account as warm loaded for this and every following transaction.
+    fn warm_account_persistent(&mut self, address: Address);
+
     fn warm_precompiles(&mut self, addresses: HashSet<Address>);
 
     fn precompile_addresses(&self) -> &HashSet<Address>;
 
+    /// Returns `true` if the account is warm, without warming it.
+    ///
+    /// Accounts that were never loaded are cold unless they are warm preloaded.
+    fn is_account_warm(&self, address: Address) -> bool;
+
+    /// Returns `true` if the storage slot is warm, without warming it.
+    ///
+    /// Slots that were never loaded are cold.
+    fn is_storage_warm(&self, address: Address, key: U256) -> bool;
+
     fn set_spec_id(&mut self, spec_id: SpecId);
 
+    /// Overrides EIP-161 state clearing, `None` follows the spec.
+    fn set_empty_account_removal(&mut self, removal: Option<bool>);
+
     fn touch_account(&mut self, address: Address);
 
     // TODO : Instruction result is not known
@@ -111,6 +127,21 @@ pub trait Journal {
         self.set_code_with_hash(address, code, hash);
     }
 
+    /// Overrides the bytecode of the account when it is loaded from the database.
+    ///
+    /// Override takes precedence over the database bytecode but does not change the account code
+    /// hash, and is not part of the final state.
+    fn set_code_override(&mut self, address: Address, code: Bytecode);
+
+    /// Records that the blob versioned hash at `index` was read by the `BLOBHASH` opcode.
+    ///
+    /// Reads are not reverted together with the call that made them.
+    fn record_blob_hash_read(&mut self, index: usize);
+
+    /// Returns the number of distinct blob versioned hashes of the transaction read by the
+    /// `BLOBHASH` opcode.
+    fn blob_hashes_read(&self) -> usize;
+
     /// Called at the end of the transaction to clean all residue data from journal.
     fn clear(&mut self);
 
@@ -153,6 +184,8 @@ pub enum TransferError {
 pub struct JournalCheckpoint {
     pub log_i: usize,
     pub journal_i: usize,
+    /// Number of times the journal was cleared or finalized when the checkpoint was taken.
+    pub generation: u64,
 }
 
 /// State load information that contains the data and if the account or storage is cold loaded
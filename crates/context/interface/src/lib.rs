@@ -15,7 +15,7 @@ pub mod result;
 pub mod transaction;
 
 pub use block::{Block, BlockGetter};
-pub use cfg::{Cfg, CfgGetter, CreateScheme, TransactTo};
+pub use cfg::{Cfg, CfgGetter, CreateScheme, InterceptFn, KzgVerifier, SstoreModel, TransactTo};
 pub use context::PerformantContextAccess;
 pub use database_interface::{DBErrorMarker, Database, DatabaseGetter};
 pub use errors::ErrorGetter;
@@ -19,6 +19,45 @@ pub struct ResultAndState<HaltReasonT: HaltReasonTrait> {
     pub result: ExecutionResult<HaltReasonT>,
     /// State that got updated
     pub state: EvmState,
+    /// Fee credited to the block beneficiary by the transaction.
+    ///
+    /// Only the fee is reported, so it is exact even if the beneficiary is also the caller or
+    /// the recipient of the transaction.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub coinbase_reward: U256,
+}
+
+impl<HaltReasonT: HaltReasonTrait> ResultAndState<HaltReasonT> {
+    /// Returns an iterator over all changed storage slots as `(address, slot, original, present)`.
+    ///
+    /// Accounts without storage changes are skipped.
+    pub fn storage_changes(&self) -> impl Iterator<Item = (Address, U256, U256, U256)> + '_ {
+        self.state.iter().flat_map(|(address, account)| {
+            account.changed_storage_slots().map(|(slot, value)| {
+                (
+                    *address,
+                    *slot,
+                    value.original_value(),
+                    value.present_value(),
+                )
+            })
+        })
+    }
+
+    /// Returns the state with the balances of all accounts restored to their original values.
+    ///
+    /// Storage, nonces and code are kept, e.g. to model a transaction whose fees and transfers
+    /// are sponsored by someone else.
+    pub fn without_balance_changes(&self) -> EvmState {
+        self.state
+            .iter()
+            .map(|(address, account)| {
+                let mut account = account.clone();
+                account.info.balance = account.original_balance;
+                (*address, account)
+            })
+            .collect()
+    }
 }
 
 /// Result of a transaction execution
@@ -32,17 +71,41 @@ pub enum ExecutionResult<HaltReasonT: HaltReasonTrait> {
         gas_refunded: u64,
         logs: Vec<Log>,
         output: Output,
+        /// Output was truncated to the configured maximum return size.
+        #[cfg_attr(feature = "serde", serde(default))]
+        output_truncated: bool,
     },
     /// Reverted by `REVERT` opcode that doesn't spend all gas
-    Revert { gas_used: u64, output: Bytes },
+    Revert {
+        gas_used: u64,
+        output: Bytes,
+        /// Output was truncated to the configured maximum return size.
+        #[cfg_attr(feature = "serde", serde(default))]
+        output_truncated: bool,
+    },
     /// Reverted for various reasons and spend all gas
     Halt {
         reason: HaltReasonT,
         /// Halting will spend all the gas, and will be equal to gas_limit.
         gas_used: u64,
+        /// State of the interpreter at the halt, captured if enabled in the configuration.
+        #[cfg_attr(feature = "serde", serde(default))]
+        context: Option<Box<HaltContext>>,
     },
 }
 
+/// State of the interpreter when the transaction halted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HaltContext {
+    /// Program counter of the instruction that halted.
+    pub pc: usize,
+    /// Stack at the halt, the top of the stack is the last element.
+    pub stack: Vec<U256>,
+    /// Memory of the halted frame.
+    pub memory: Bytes,
+}
+
 impl<HaltReasonT: HaltReasonTrait> ExecutionResult<HaltReasonT> {
     /// Returns if transaction execution is successful.
     ///
@@ -259,12 +322,19 @@ pub enum InvalidTransaction {
     GasPriceLessThanBasefee,
     /// `gas_limit` in the tx is bigger than `block_gas_limit`.
     CallerGasLimitMoreThanBlock,
+    /// `gas_limit` in the tx is bigger than the maximum transaction gas limit (EIP-7825).
+    GasLimitTooHigh {
+        max: u64,
+        have: u64,
+    },
     /// Initial gas for a Call is bigger than `gas_limit`.
     ///
     /// Initial gas for a Call contains:
     /// - initial stipend gas
     /// - gas for access list and input data
     CallGasCostMoreThanGasLimit,
+    /// Gas limit does not cover the EIP-7623 calldata floor gas.
+    GasFloorMoreThanGasLimit,
     /// EIP-3607 Reject transactions from senders with deployed code
     RejectCallerWithCode,
     /// Transaction account does not have enough amount of ether to cover transferred value and gas_limit*gas_price.
@@ -286,6 +356,11 @@ pub enum InvalidTransaction {
     },
     /// EIP-3860: Limit and meter initcode
     CreateInitCodeSizeLimit,
+    /// Transaction data is larger than the configured maximum.
+    InputTooLarge {
+        max: usize,
+        have: usize,
+    },
     /// Transaction chain id does not match the config chain id.
     InvalidChainId,
     /// Access list is not supported for blocks before the Berlin hardfork.
@@ -343,9 +418,15 @@ impl fmt::Display for InvalidTransaction {
             Self::CallerGasLimitMoreThanBlock => {
                 write!(f, "caller gas limit exceeds the block gas limit")
             }
+            Self::GasLimitTooHigh { max, have } => {
+                write!(f, "transaction gas limit too high, have {have}, max {max}")
+            }
             Self::CallGasCostMoreThanGasLimit => {
                 write!(f, "call gas cost exceeds the gas limit")
             }
+            Self::GasFloorMoreThanGasLimit => {
+                write!(f, "calldata floor gas exceeds the gas limit")
+            }
             Self::RejectCallerWithCode => {
                 write!(f, "reject transactions from senders with deployed code")
             }
@@ -367,6 +448,9 @@ impl fmt::Display for InvalidTransaction {
             Self::CreateInitCodeSizeLimit => {
                 write!(f, "create initcode size limit")
             }
+            Self::InputTooLarge { max, have } => {
+                write!(f, "transaction input too large, have {have}, max {max}")
+            }
             Self::InvalidChainId => write!(f, "invalid chain ID"),
             Self::AccessListNotSupported => write!(f, "access list not supported"),
             Self::MaxFeePerBlobGasNotSupported => {
@@ -468,6 +552,10 @@ pub enum HaltReason {
     SubRoutineStackOverflow,
     /// Check for target address validity is only done inside subcall.
     InvalidEXTCALLTarget,
+    /// Gas spent by a single instruction exceeded [`Cfg::per_opcode_gas_cap`](crate::Cfg::per_opcode_gas_cap).
+    OpcodeGasCapExceeded,
+    /// Execution did not finish before [`Cfg::execution_deadline`](crate::Cfg::execution_deadline).
+    Timeout,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -487,3 +575,76 @@ pub enum OutOfGasError {
     // When performing SSTORE the gasleft is less than or equal to 2300
     ReentrancySentry,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use state::{Account, AccountInfo, EvmStorageSlot};
+    use std::vec;
+
+    #[test]
+    fn storage_changes_skip_unchanged_accounts() {
+        let changed = Address::with_last_byte(1);
+        let unchanged = Address::with_last_byte(2);
+
+        let mut changed_account = Account::from(AccountInfo::default());
+        changed_account.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::from(10), U256::from(20)),
+        );
+        changed_account
+            .storage
+            .insert(U256::from(2), EvmStorageSlot::new(U256::from(30)));
+        let mut unchanged_account = Account::from(AccountInfo::default());
+        unchanged_account
+            .storage
+            .insert(U256::from(1), EvmStorageSlot::new(U256::from(40)));
+
+        let result_and_state = ResultAndState::<HaltReason> {
+            result: ExecutionResult::Revert {
+                gas_used: 0,
+                output: Bytes::new(),
+                output_truncated: false,
+            },
+            state: [(changed, changed_account), (unchanged, unchanged_account)]
+                .into_iter()
+                .collect(),
+            coinbase_reward: U256::ZERO,
+        };
+
+        let changes: Vec<_> = result_and_state.storage_changes().collect();
+        assert_eq!(
+            changes,
+            vec![(changed, U256::from(1), U256::from(10), U256::from(20))]
+        );
+    }
+
+    #[test]
+    fn without_balance_changes_keeps_storage_and_nonce() {
+        let caller = Address::with_last_byte(1);
+        let mut account = Account::from(AccountInfo::from_balance(U256::from(10_000_000)));
+        account.info.balance = U256::from(9_000_000);
+        account.info.nonce = 1;
+        account.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(0x2a)),
+        );
+        let result_and_state = ResultAndState::<HaltReason> {
+            result: ExecutionResult::Revert {
+                gas_used: 0,
+                output: Bytes::new(),
+                output_truncated: false,
+            },
+            state: [(caller, account)].into_iter().collect(),
+            coinbase_reward: U256::ZERO,
+        };
+
+        let state = result_and_state.without_balance_changes();
+        assert_eq!(state[&caller].info.balance, U256::from(10_000_000));
+        assert_eq!(state[&caller].info.nonce, 1);
+        assert_eq!(
+            state[&caller].storage[&U256::from(1)].present_value(),
+            U256::from(0x2a)
+        );
+    }
+}
@@ -116,6 +116,21 @@ pub trait Transaction {
         max_blob_fee.saturating_mul(blob_gas)
     }
 
+    /// Returns the maximum fee the caller pays for the transaction, excluding the transferred value.
+    ///
+    /// It is `gas_limit * max_fee_per_gas` plus, for EIP-4844 transactions, the
+    /// [maximum blob fee][Transaction::calc_max_data_fee]. Computed with saturating arithmetic,
+    /// so it never panics on adversarial inputs.
+    fn max_fee_cost(&self) -> U256 {
+        let gas_cost =
+            U256::from(self.gas_limit()).saturating_mul(U256::from(self.max_fee_per_gas()));
+        if self.tx_type() == TransactionType::Eip4844 {
+            gas_cost.saturating_add(self.calc_max_data_fee())
+        } else {
+            gas_cost
+        }
+    }
+
     /// Returns length of the authorization list.
     ///
     /// # Note
@@ -141,8 +156,15 @@ pub trait Transaction {
     /// Returns effective gas price is gas price field for Legacy and Eip2930 transaction.
     ///
     /// While for transactions after Eip1559 it is minimum of max_fee and `base + max_priority_fee`.
+    ///
+    /// Legacy gas price is returned as is, even if it is below the base fee. Such transactions
+    /// are rejected by the validation.
     fn effective_gas_price(&self, base_fee: u128) -> u128 {
         let max_fee = self.gas_price();
+        let tx_type = self.tx_type();
+        if tx_type == TransactionType::Legacy || tx_type == TransactionType::Eip2930 {
+            return max_fee;
+        }
         let Some(max_priority_fee) = self.max_priority_fee_per_gas() else {
             return max_fee;
         };
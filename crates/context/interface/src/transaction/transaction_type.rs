@@ -1,9 +1,10 @@
 /// Transaction types of all Ethereum transaction
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransactionType {
     /// Legacy transaction type
+    #[default]
     Legacy = 0,
     /// EIP-2930 Access List transaction type
     Eip2930,
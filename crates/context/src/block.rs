@@ -1,5 +1,6 @@
 use context_interface::block::{BlobExcessGasAndPrice, Block};
 use primitives::{Address, B256, U256};
+use std::collections::BTreeMap;
 
 /// The block environment
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -41,6 +42,15 @@ pub struct BlockEnv {
     ///
     /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
     pub blob_excess_gas_and_price: Option<BlobExcessGasAndPrice>,
+    /// Hashes of previous blocks returned by the `BLOCKHASH` opcode instead of the database.
+    ///
+    /// Only consulted for numbers inside the [`BLOCK_HASH_HISTORY`] window, numbers outside of
+    /// it still return zero. Numbers missing from the map are read from the database.
+    ///
+    /// By default, it is set to `None`.
+    ///
+    /// [`BLOCK_HASH_HISTORY`]: primitives::BLOCK_HASH_HISTORY
+    pub block_hashes: Option<BTreeMap<u64, B256>>,
 }
 
 impl BlockEnv {
@@ -91,6 +101,11 @@ impl Block for BlockEnv {
     fn blob_excess_gas_and_price(&self) -> Option<BlobExcessGasAndPrice> {
         self.blob_excess_gas_and_price
     }
+
+    #[inline]
+    fn block_hash_override(&self, number: u64) -> Option<B256> {
+        self.block_hashes.as_ref()?.get(&number).copied()
+    }
 }
 
 impl Default for BlockEnv {
@@ -104,6 +119,7 @@ impl Default for BlockEnv {
             difficulty: U256::ZERO,
             prevrandao: Some(B256::ZERO),
             blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0)),
+            block_hashes: None,
         }
     }
 }
@@ -1,11 +1,24 @@
-pub use context_interface::Cfg;
+pub use context_interface::{Cfg, InterceptFn, KzgVerifier, SstoreModel};
 
 use interpreter::MAX_CODE_SIZE;
-use specification::hardfork::SpecId;
+use primitives::{keccak256, Address, HashMap, B256};
+use specification::{chain_spec::ChainSpec, eip7825, hardfork::SpecId};
+use std::sync::Arc;
 
 /// EVM configuration
+///
+/// # Function hooks
+///
+/// [`code_hasher`](Self::code_hasher), [`address_alias`](Self::address_alias) and
+/// [`intercept_addresses`](Self::intercept_addresses) hold function pointers, while
+/// [`chain_spec`](Self::chain_spec) and [`kzg_settings`](Self::kzg_settings) hold trait objects.
+/// `PartialEq` compares them by address, which is not guaranteed to be unique for the same
+/// function or value, so equality of configs with hooks set is unreliable. They are skipped by
+/// serde and deserialize to their defaults.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
+// Hooks are compared by address, see the type documentation.
+#[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
 #[non_exhaustive]
 pub struct CfgEnv<SPEC: Into<SpecId> = SpecId> {
     /// Chain ID of the EVM
@@ -22,8 +35,220 @@ pub struct CfgEnv<SPEC: Into<SpecId> = SpecId> {
     ///
     /// By default it is `0x6000` (~25kb).
     pub limit_contract_code_size: Option<usize>,
+    /// If some it will override the EIP-3860: Limit and meter initcode size limit.
+    ///
+    /// By default it is twice the contract code size limit (`0xC000` ~49kb).
+    pub max_initcode_size: Option<usize>,
     /// Skips the nonce validation against the account's nonce
     pub disable_nonce_check: bool,
+    /// Skips charging the caller for gas.
+    ///
+    /// Gas is still metered and reported, but it is neither required from, deducted from nor
+    /// reimbursed to the caller, and the beneficiary is not paid. The value transfer still
+    /// requires the balance. Useful for `eth_call` like simulations.
+    ///
+    /// By default, it is set to `false`.
+    pub disable_fee_charge: bool,
+    /// Disables the EIP-7623 calldata floor while keeping the rest of Prague.
+    ///
+    /// Transactions are charged only for execution and their gas limit is not required to cover
+    /// the floor. Useful for chains that did not adopt EIP-7623.
+    ///
+    /// By default, it is set to `false`.
+    pub disable_calldata_floor: bool,
+    /// Disables enforcement of the gas limit during execution.
+    ///
+    /// Gas is still metered but running out of it never halts, so every reachable path can be
+    /// explored, e.g. by coverage or symbolic analysis tools. Frames report the gas spent above
+    /// their limit in `Gas::exceeded`, the transaction uses and pays for at most its gas limit.
+    ///
+    /// By default, it is set to `false`.
+    pub disable_gas_metering: bool,
+    /// Executes the whole transaction under static call restrictions.
+    ///
+    /// Any state changing opcode (SSTORE, LOG, CREATE, SELFDESTRUCT, value transferring CALL)
+    /// halts even in the top level call. Transactions that transfer value or create a contract
+    /// are halted before execution starts.
+    ///
+    /// Useful for `eth_call` like simulations. By default, it is set to `false`.
+    pub read_only: bool,
+    /// Address that is credited with the EIP-1559 base fee.
+    ///
+    /// Some L2s redirect the base fee to a fee recipient instead of burning it.
+    ///
+    /// By default, it is set to `None` and the base fee is burned.
+    pub base_fee_recipient: Option<Address>,
+    /// Forces EIP-3651 coinbase warming on or off regardless of the spec.
+    ///
+    /// Useful for replaying historical transactions with exact gas.
+    ///
+    /// By default, it is set to `None` and the coinbase is warm starting from Shanghai.
+    pub warm_coinbase: Option<bool>,
+    /// Forces EIP-161 state clearing on or off regardless of the spec.
+    ///
+    /// When set, it decides whether empty accounts are considered non existing and whether
+    /// created accounts start with nonce one. `Some(true)` additionally removes touched empty
+    /// accounts from the finalized state. Useful for replaying transactions around the Spurious
+    /// Dragon fork boundary.
+    ///
+    /// By default, it is set to `None` and state clearing is enabled from Spurious Dragon.
+    pub force_empty_account_removal: Option<bool>,
+    /// Maximum input length in bytes that precompiles accept.
+    ///
+    /// Only honored when precompiles are wrapped in a size limited provider.
+    ///
+    /// By default, it is set to `None` and inputs are not limited.
+    pub precompile_max_input_len: Option<usize>,
+    /// Maximum size in bytes of the output of `RETURN` and `REVERT`.
+    ///
+    /// Larger outputs are truncated to the limit and the result is flagged as truncated. Memory
+    /// is only expanded and charged up to the limit. This diverges from consensus and is meant
+    /// for simulations.
+    ///
+    /// By default, it is set to `None` and outputs are not limited.
+    pub max_return_size: Option<usize>,
+    /// Overrides the hash function used to compute the code hash of newly created contracts.
+    ///
+    /// By default, it is set to `None` and code is hashed with keccak256.
+    ///
+    /// Not serialized and compared by address, see [function hooks](Self#function-hooks).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub code_hasher: Option<fn(&[u8]) -> B256>,
+    /// Activation rules of the chain.
+    ///
+    /// Used by the precompile provider to select the active precompiles from the block number
+    /// and timestamp. Every other feature follows [`spec`](Self::spec).
+    ///
+    /// By default, it is set to `None` and the precompiles follow the spec.
+    ///
+    /// Not serialized and compared by address, see [function hooks](Self#function-hooks).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub chain_spec: Option<Arc<dyn ChainSpec>>,
+    /// Forces the `SSTORE` gas cost and refund rules of the given model regardless of the spec.
+    /// The cap on the transaction refund follows the model too.
+    ///
+    /// Useful for comparing gas usage across models on the same bytecode.
+    ///
+    /// By default, it is set to `None` and the model follows the spec.
+    pub sstore_model: Option<SstoreModel>,
+    /// Maximum dynamic gas a single instruction can spend, e.g. for copying, memory expansion
+    /// or `EXP` exponent bytes.
+    ///
+    /// Instructions exceeding it halt with `OpcodeGasCapExceeded` before the work is done.
+    /// Static costs, including account access and storage costs, are never capped. Meant for
+    /// sandboxing untrusted code.
+    ///
+    /// By default, it is set to `None` and dynamic gas is only limited by the gas limit.
+    pub per_opcode_gas_cap: Option<u64>,
+    /// Maps the transaction caller to the `msg.sender` seen by the called contract.
+    ///
+    /// Models L1 to L2 address aliasing of cross-domain messages, e.g. on Optimism and
+    /// Arbitrum. Only the top level call or create is affected, the caller still pays for gas
+    /// and value and created addresses are derived from it.
+    ///
+    /// By default, it is set to `None` and the caller is not aliased.
+    ///
+    /// Not serialized and compared by address, see [function hooks](Self#function-hooks).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub address_alias: Option<fn(Address) -> Address>,
+    /// Overrides the quotient limiting the gas refund to `gas_used / refund_quotient`.
+    ///
+    /// Spec sets it to 2, and to 5 since London (EIP-3529). Chains without refunds can set it
+    /// to zero, which disables refunds like `disable_gas_refund` does.
+    ///
+    /// By default, it is set to `None` and the quotient follows the spec.
+    pub refund_quotient: Option<u64>,
+    /// Overrides the nonce of newly created contracts.
+    ///
+    /// Spec sets it to 1 since Spurious Dragon (EIP-161), and to 0 before. The collision check
+    /// is not affected, an address with a nonce or code can still not be created at.
+    ///
+    /// By default, it is set to `None` and the nonce follows the spec.
+    pub contract_initial_nonce: Option<u64>,
+    /// Maximum size in bytes of the transaction data, for calls and creates alike.
+    ///
+    /// Larger transactions are rejected during validation with `InputTooLarge`. Unlike the
+    /// initcode limit, this is not part of consensus and is meant for spam protection.
+    ///
+    /// By default, it is set to `None` and the data is not limited.
+    pub max_tx_input_size: Option<usize>,
+    /// Maximum gas limit of a transaction.
+    ///
+    /// Transactions with a higher gas limit are rejected during validation with
+    /// `GasLimitTooHigh`. Useful for custom chains that cap transactions differently.
+    ///
+    /// By default, it is set to `None` and the EIP-7825 cap of `2^24` applies from Osaka.
+    pub max_tx_gas_limit: Option<u64>,
+    /// Overrides the gas charged per word of data hashed with keccak256.
+    ///
+    /// Applies to the dynamic cost of `KECCAK256` and to the hashing of the init code by
+    /// `CREATE2` and of the container by `EOFCREATE`. Spec sets it to 6 gas per word. The
+    /// EIP-3860 initcode cost of 2 gas per word is not hashing and is not affected.
+    ///
+    /// By default, it is set to `None` and the cost follows the spec.
+    pub keccak_word_cost: Option<u64>,
+    /// Reserves the whole `0x01..=0xff` address range for precompiles.
+    ///
+    /// Calls to addresses in the range that are not active precompiles return successfully with
+    /// empty output and no gas used, without loading or running the code of the account. Value
+    /// is still transferred.
+    ///
+    /// By default, it is set to `false`.
+    pub reserved_precompile_range: bool,
+    /// Overrides the gas stipend added to value transferring `CALL` and `CALLCODE`.
+    ///
+    /// The stipend is given to the callee on top of the forwarded gas and is not charged to the
+    /// caller, unused stipend is returned to the caller like any other unused gas. The EIP-1706
+    /// `SSTORE` sentry keeps using the spec stipend.
+    ///
+    /// By default, it is set to `None` and the stipend is 2300 gas.
+    pub call_stipend: Option<u64>,
+    /// Captures the program counter, stack and memory of the top level frame when it halts,
+    /// and reports them in [`ExecutionResult::Halt`].
+    ///
+    /// Capturing does not change gas or behavior, it only copies the state of the frame.
+    ///
+    /// By default, it is set to `false`.
+    ///
+    /// [`ExecutionResult::Halt`]: context_interface::result::ExecutionResult::Halt
+    pub capture_halt_state: bool,
+    /// Addresses whose calls are handled by a native function instead of a frame.
+    ///
+    /// Checked at call dispatch before precompiles, for the address whose code would run. A
+    /// lighter alternative to a custom precompile provider.
+    ///
+    /// By default, it is empty.
+    ///
+    /// Not serialized and compared by address, see [function hooks](Self#function-hooks).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub intercept_addresses: HashMap<Address, InterceptFn>,
+    /// Verifier of the KZG proofs checked by the point evaluation precompile.
+    ///
+    /// Set it to the settings of a custom trusted setup, e.g. for testnets.
+    ///
+    /// By default, it is set to `None` and proofs are verified against the mainnet setup.
+    ///
+    /// Not serialized and compared by address, see [function hooks](Self#function-hooks).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub kzg_settings: Option<Arc<dyn KzgVerifier>>,
+    /// Wall-clock deadline after which precompiles are interrupted.
+    ///
+    /// Only honored when precompiles are wrapped in a deadline aware provider.
+    ///
+    /// By default, it is set to `None`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub precompile_deadline: Option<std::time::Instant>,
+    /// Wall-clock deadline after which the transaction halts with
+    /// [HaltReason::Timeout][context_interface::result::HaltReason::Timeout].
+    ///
+    /// The deadline is checked when a frame starts or resumes, and every
+    /// [`DEADLINE_CHECK_INTERVAL`](interpreter::DEADLINE_CHECK_INTERVAL) instructions.
+    ///
+    /// By default, it is set to `None`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub execution_deadline: Option<std::time::Instant>,
     /// A hard memory limit in bytes beyond which
     /// [OutOfGasError::Memory][context_interface::result::OutOfGasError::Memory] cannot be resized.
     ///
@@ -94,6 +319,11 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
         self.limit_contract_code_size.unwrap_or(MAX_CODE_SIZE)
     }
 
+    fn max_initcode_size(&self) -> usize {
+        self.max_initcode_size
+            .unwrap_or_else(|| self.max_code_size().saturating_mul(2))
+    }
+
     fn is_eip3607_disabled(&self) -> bool {
         cfg_if::cfg_if! {
             if #[cfg(feature = "optional_eip3607")] {
@@ -138,6 +368,118 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
         self.disable_nonce_check
     }
 
+    fn is_fee_charge_disabled(&self) -> bool {
+        self.disable_fee_charge
+    }
+
+    fn is_calldata_floor_disabled(&self) -> bool {
+        self.disable_calldata_floor
+    }
+
+    fn is_gas_metering_disabled(&self) -> bool {
+        self.disable_gas_metering
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn base_fee_recipient(&self) -> Option<Address> {
+        self.base_fee_recipient
+    }
+
+    fn is_coinbase_warm(&self) -> bool {
+        self.warm_coinbase
+            .unwrap_or_else(|| self.spec.into().is_enabled_in(SpecId::SHANGHAI))
+    }
+
+    fn force_empty_account_removal(&self) -> Option<bool> {
+        self.force_empty_account_removal
+    }
+
+    fn precompile_max_input_len(&self) -> Option<usize> {
+        self.precompile_max_input_len
+    }
+
+    fn max_return_size(&self) -> Option<usize> {
+        self.max_return_size
+    }
+
+    fn code_hasher(&self) -> fn(&[u8]) -> B256 {
+        self.code_hasher.unwrap_or(|code| keccak256(code))
+    }
+
+    fn chain_spec(&self) -> Option<&dyn ChainSpec> {
+        self.chain_spec.as_deref()
+    }
+
+    fn sstore_model(&self) -> Option<SstoreModel> {
+        self.sstore_model
+    }
+
+    fn per_opcode_gas_cap(&self) -> Option<u64> {
+        self.per_opcode_gas_cap
+    }
+
+    fn address_alias(&self) -> fn(Address) -> Address {
+        self.address_alias.unwrap_or(|address| address)
+    }
+
+    fn refund_quotient(&self) -> Option<u64> {
+        self.refund_quotient
+    }
+
+    fn contract_initial_nonce(&self) -> Option<u64> {
+        self.contract_initial_nonce
+    }
+
+    fn max_tx_input_size(&self) -> Option<usize> {
+        self.max_tx_input_size
+    }
+
+    fn max_tx_gas_limit(&self) -> Option<u64> {
+        self.max_tx_gas_limit.or_else(|| {
+            self.spec
+                .into()
+                .is_enabled_in(SpecId::OSAKA)
+                .then_some(eip7825::TX_GAS_LIMIT_CAP)
+        })
+    }
+
+    fn keccak_word_cost(&self) -> Option<u64> {
+        self.keccak_word_cost
+    }
+
+    fn is_precompile_range_reserved(&self) -> bool {
+        self.reserved_precompile_range
+    }
+
+    fn call_stipend(&self) -> Option<u64> {
+        self.call_stipend
+    }
+
+    fn is_halt_state_captured(&self) -> bool {
+        self.capture_halt_state
+    }
+
+    fn intercept(&self, address: &Address) -> Option<InterceptFn> {
+        self.intercept_addresses.get(address).copied()
+    }
+
+    fn kzg_settings(&self) -> Option<&dyn KzgVerifier> {
+        self.kzg_settings.as_deref()
+    }
+
+    #[cfg(feature = "std")]
+    fn precompile_deadline(&self) -> Option<std::time::Instant> {
+        self.precompile_deadline
+    }
+
+    #[cfg(feature = "std")]
+    fn execution_deadline(&self) -> Option<std::time::Instant> {
+        self.execution_deadline
+    }
+
     fn is_base_fee_check_disabled(&self) -> bool {
         cfg_if::cfg_if! {
             if #[cfg(feature = "optional_no_base_fee")] {
@@ -154,8 +496,37 @@ impl Default for CfgEnv {
         Self {
             chain_id: 1,
             limit_contract_code_size: None,
+            max_initcode_size: None,
             spec: SpecId::PRAGUE,
             disable_nonce_check: false,
+            disable_fee_charge: false,
+            disable_calldata_floor: false,
+            disable_gas_metering: false,
+            read_only: false,
+            base_fee_recipient: None,
+            warm_coinbase: None,
+            force_empty_account_removal: None,
+            precompile_max_input_len: None,
+            max_return_size: None,
+            code_hasher: None,
+            chain_spec: None,
+            sstore_model: None,
+            per_opcode_gas_cap: None,
+            address_alias: None,
+            refund_quotient: None,
+            contract_initial_nonce: None,
+            max_tx_input_size: None,
+            max_tx_gas_limit: None,
+            keccak_word_cost: None,
+            reserved_precompile_range: false,
+            call_stipend: None,
+            capture_halt_state: false,
+            intercept_addresses: HashMap::default(),
+            kzg_settings: None,
+            #[cfg(feature = "std")]
+            precompile_deadline: None,
+            #[cfg(feature = "std")]
+            execution_deadline: None,
             #[cfg(feature = "memory_limit")]
             memory_limit: (1 << 32) - 1,
             #[cfg(feature = "optional_balance_check")]
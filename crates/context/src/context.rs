@@ -7,7 +7,7 @@ use context_interface::{
     journaled_state::{AccountLoad, Eip7702CodeLoad},
     transaction::TransactionSetter,
     Block, BlockGetter, Cfg, CfgGetter, DatabaseGetter, ErrorGetter, Journal, JournalGetter,
-    Transaction, TransactionGetter,
+    Transaction, TransactionGetter, TransactionType,
 };
 use database_interface::{Database, EmptyDB};
 use derive_where::derive_where;
@@ -74,6 +74,14 @@ impl<
             error: Ok(()),
         }
     }
+
+    /// Sets the spec of the configuration and the journal.
+    #[must_use]
+    pub fn with_spec(mut self, spec: SpecId) -> Self {
+        self.cfg.spec = spec;
+        self.journaled_state.set_spec_id(spec);
+        self
+    }
 }
 
 impl<BLOCK, TX, CFG, DB, JOURNAL, CHAIN> Context<BLOCK, TX, CFG, DB, JOURNAL, CHAIN>
@@ -219,6 +227,17 @@ where
         }
     }
 
+    /// Marks addresses as warm loaded in every transaction executed with this context.
+    ///
+    /// Addresses are kept in the journal, so this needs to be called after [`Self::with_db`].
+    #[must_use]
+    pub fn with_warm_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        for address in addresses {
+            self.journaled_state.warm_account_persistent(address);
+        }
+        self
+    }
+
     /// Modifies the context configuration.
     #[must_use]
     pub fn modify_cfg_chained<F>(mut self, f: F) -> Self
@@ -394,6 +413,9 @@ where
         }
 
         if diff <= BLOCK_HASH_HISTORY {
+            if let Some(hash) = self.block().block_hash_override(requested_number) {
+                return Some(hash);
+            }
             return self
                 .journaled_state
                 .db()
@@ -405,6 +427,15 @@ where
         Some(B256::ZERO)
     }
 
+    fn blob_hash(&mut self, index: usize) -> Option<B256> {
+        if self.tx.tx_type() != TransactionType::Eip4844 {
+            return None;
+        }
+        let hash = self.tx.blob_versioned_hashes().get(index).copied()?;
+        self.journaled_state.record_blob_hash_read(index);
+        Some(hash)
+    }
+
     fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad> {
         self.journaled_state
             .load_account_delegated(address)
@@ -562,3 +593,39 @@ impl<BLOCK: Block, TX, SPEC, DB: Database, JOURNAL: Journal<Database = DB>, CHAI
         self.block = block;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::BTreeMap, vec::Vec};
+
+    #[test]
+    fn block_hashes_override_database() {
+        let in_window = B256::with_last_byte(0xaa);
+        let out_of_window = B256::with_last_byte(0xbb);
+        let mut context = Context::builder().modify_block_chained(|block| {
+            block.number = 300;
+            block.block_hashes = Some(BTreeMap::from([(250, in_window), (10, out_of_window)]));
+        });
+
+        assert_eq!(context.block_hash(250), Some(in_window));
+        // Hashes outside of the last 256 blocks are not available.
+        assert_eq!(context.block_hash(10), Some(B256::ZERO));
+    }
+
+    #[test]
+    fn blob_hash_reads_are_recorded() {
+        let blob_hashes: Vec<_> = (1..=3).map(B256::with_last_byte).collect();
+        let mut context = Context::builder().modify_tx_chained(|tx| {
+            tx.tx_type = TransactionType::Eip4844.into();
+            tx.blob_hashes = blob_hashes.clone();
+        });
+
+        // Reads blob hashes 0, 2, 2 and 3, the last one is out of bounds.
+        for index in [0, 2, 2] {
+            assert_eq!(context.blob_hash(index), Some(blob_hashes[index]));
+        }
+        assert_eq!(context.blob_hash(3), None);
+        assert_eq!(context.journaled_state.blob_hashes_read(), 2);
+    }
+}
@@ -31,6 +31,8 @@ pub struct JournaledState<DB> {
     pub transient_storage: TransientStorage,
     /// Emitted logs
     pub logs: Vec<Log>,
+    /// Call depth at which each of the [logs][Self::logs] was emitted.
+    pub log_depths: Vec<usize>,
     /// The current call stack depth
     pub depth: usize,
     /// The journal of state changes, one for each call
@@ -45,6 +47,11 @@ pub struct JournaledState<DB> {
     /// [EIP-161]: https://eips.ethereum.org/EIPS/eip-161
     /// [EIP-6780]: https://eips.ethereum.org/EIPS/eip-6780
     pub spec: SpecId,
+    /// Overrides the EIP-161 state clearing of the [spec][Self::spec] when set.
+    ///
+    /// With `Some(true)`, touched empty accounts are removed from the finalized state by
+    /// marking them as selfdestructed.
+    pub force_empty_account_removal: Option<bool>,
     /// Warm loaded addresses are used to check if loaded address
     /// should be considered cold or warm loaded when the account
     /// is first accessed.
@@ -52,8 +59,27 @@ pub struct JournaledState<DB> {
     /// Note that this not include newly loaded accounts, account and storage
     /// is considered warm if it is found in the `State`.
     pub warm_preloaded_addresses: HashSet<Address>,
+    /// Addresses that are warm loaded in every transaction.
+    ///
+    /// They are added back to `warm_preloaded_addresses` when the journal is cleared.
+    pub persistent_warm_addresses: HashSet<Address>,
     /// Precompile addresses
     pub precompiles: HashSet<Address>,
+    /// Bytecode overrides that are applied when the account is loaded from the database.
+    ///
+    /// Overrides are kept between transactions.
+    pub code_overrides: HashMap<Address, Bytecode>,
+    /// Indices of the transaction blob versioned hashes read by the `BLOBHASH` opcode.
+    pub blob_hash_reads: HashSet<usize>,
+    /// Entries undone by [`checkpoint_revert`][Self::checkpoint_revert], in the order they were
+    /// journaled.
+    ///
+    /// Reverted entries are collected only if this is `Some`, consumers are expected to take them.
+    pub reverted_entries: Option<Vec<JournalEntry>>,
+    /// Number of times the journal was cleared or finalized.
+    ///
+    /// Checkpoints taken in a previous generation refer to a discarded journal.
+    pub generation: u64,
 }
 
 impl<DB: Database> Journal for JournaledState<DB> {
@@ -114,6 +140,11 @@ impl<DB: Database> Journal for JournaledState<DB> {
         self.warm_preloaded_addresses.insert(address);
     }
 
+    fn warm_account_persistent(&mut self, address: Address) {
+        self.persistent_warm_addresses.insert(address);
+        self.warm_preloaded_addresses.insert(address);
+    }
+
     fn warm_precompiles(&mut self, address: HashSet<Address>) {
         self.precompiles = address;
         self.warm_preloaded_addresses
@@ -125,6 +156,20 @@ impl<DB: Database> Journal for JournaledState<DB> {
         &self.precompiles
     }
 
+    fn is_account_warm(&self, address: Address) -> bool {
+        match self.state.get(&address) {
+            Some(account) => !account.is_cold(),
+            None => self.warm_preloaded_addresses.contains(&address),
+        }
+    }
+
+    fn is_storage_warm(&self, address: Address, key: U256) -> bool {
+        self.state
+            .get(&address)
+            .and_then(|account| account.storage.get(&key))
+            .is_some_and(|slot| !slot.is_cold)
+    }
+
     /// Returns call depth.
     #[inline]
     fn depth(&self) -> usize {
@@ -144,6 +189,10 @@ impl<DB: Database> Journal for JournaledState<DB> {
         self.spec = spec_id;
     }
 
+    fn set_empty_account_removal(&mut self, removal: Option<bool>) {
+        self.force_empty_account_removal = removal;
+    }
+
     fn transfer(
         &mut self,
         from: &Address,
@@ -193,14 +242,34 @@ impl<DB: Database> Journal for JournaledState<DB> {
         self.set_code_with_hash(address, code, hash);
     }
 
+    fn set_code_override(&mut self, address: Address, code: Bytecode) {
+        self.code_overrides.insert(address, code);
+    }
+
+    fn record_blob_hash_read(&mut self, index: usize) {
+        self.blob_hash_reads.insert(index);
+    }
+
+    fn blob_hashes_read(&self) -> usize {
+        self.blob_hash_reads.len()
+    }
+
     fn clear(&mut self) {
         // Clears the JournaledState. Preserving only the spec.
         self.state.clear();
         self.transient_storage.clear();
         self.logs.clear();
+        self.log_depths.clear();
+        self.blob_hash_reads.clear();
+        if let Some(reverted_entries) = &mut self.reverted_entries {
+            reverted_entries.clear();
+        }
         self.journal = vec![vec![]];
         self.depth = 0;
+        self.generation += 1;
         self.warm_preloaded_addresses.clear();
+        self.warm_preloaded_addresses
+            .extend(self.persistent_warm_addresses.iter());
     }
 
     fn create_account_checkpoint(
@@ -219,20 +288,51 @@ impl<DB: Database> Journal for JournaledState<DB> {
             state,
             transient_storage,
             logs,
+            log_depths,
             depth,
             journal,
             // kept, see [Self::new]
             spec: _,
+            force_empty_account_removal,
             database: _,
             warm_preloaded_addresses: _,
+            persistent_warm_addresses: _,
             precompiles: _,
+            code_overrides,
+            blob_hash_reads,
+            reverted_entries,
+            generation,
         } = self;
 
         *transient_storage = TransientStorage::default();
+        blob_hash_reads.clear();
+        if let Some(reverted_entries) = reverted_entries {
+            reverted_entries.clear();
+        }
         *journal = vec![vec![]];
         *depth = 0;
-        let state = mem::take(state);
+        *generation += 1;
+        let mut state = mem::take(state);
+
+        // EIP-161 forced by the configuration, databases remove selfdestructed accounts.
+        if *force_empty_account_removal == Some(true) {
+            for account in state.values_mut() {
+                if account.is_touched() && account.is_empty() {
+                    account.mark_selfdestruct();
+                }
+            }
+        }
+
+        // Overridden code is not part of the state, code will be fetched by its hash.
+        for (address, code) in code_overrides.iter() {
+            if let Some(account) = state.get_mut(address) {
+                if account.info.code.as_ref() == Some(code) {
+                    account.info.code = None;
+                }
+            }
+        }
         let logs = mem::take(logs);
+        log_depths.clear();
 
         Ok((state, logs))
     }
@@ -253,11 +353,18 @@ impl<DB: Database> JournaledState<DB> {
             state: HashMap::default(),
             transient_storage: TransientStorage::default(),
             logs: Vec::new(),
+            log_depths: Vec::new(),
             journal: vec![vec![]],
             depth: 0,
             spec,
+            force_empty_account_removal: None,
             warm_preloaded_addresses: HashSet::default(),
+            persistent_warm_addresses: HashSet::default(),
             precompiles: HashSet::default(),
+            code_overrides: HashMap::default(),
+            blob_hash_reads: HashSet::default(),
+            reverted_entries: None,
+            generation: 0,
         }
     }
 
@@ -267,6 +374,14 @@ impl<DB: Database> JournaledState<DB> {
         &mut self.state
     }
 
+    /// Returns `true` if EIP-161 state clearing is enabled, either by the spec or by the
+    /// [override][Self::force_empty_account_removal].
+    #[inline]
+    pub fn is_state_clear_enabled(&self) -> bool {
+        self.force_empty_account_removal
+            .unwrap_or_else(|| self.spec.is_enabled_in(SPURIOUS_DRAGON))
+    }
+
     /// Sets SpecId.
     #[inline]
     pub fn set_spec_id(&mut self, spec: SpecId) {
@@ -424,6 +539,9 @@ impl<DB: Database> JournaledState<DB> {
         balance: U256,
         spec_id: SpecId,
     ) -> Result<JournalCheckpoint, TransferError> {
+        let state_clear = self
+            .force_empty_account_removal
+            .unwrap_or_else(|| spec_id.is_enabled_in(SPURIOUS_DRAGON));
         // Enter subroutine
         let checkpoint = self.checkpoint();
 
@@ -457,7 +575,7 @@ impl<DB: Database> JournaledState<DB> {
         });
         target_acc.info.code = None;
         // EIP-161: State trie clearing (invariant-preserving alternative)
-        if spec_id.is_enabled_in(SPURIOUS_DRAGON) {
+        if state_clear {
             // nonce is going to be reset to zero in AccountCreated journal entry.
             target_acc.info.nonce = 1;
         }
@@ -599,6 +717,7 @@ impl<DB: Database> JournaledState<DB> {
         let checkpoint = JournalCheckpoint {
             log_i: self.logs.len(),
             journal_i: self.journal.len(),
+            generation: self.generation,
         };
         self.depth += 1;
         self.journal.push(Default::default());
@@ -614,7 +733,15 @@ impl<DB: Database> JournaledState<DB> {
     /// Reverts all changes to state until given checkpoint.
     #[inline]
     pub fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
-        let is_spurious_dragon_enabled = self.spec.is_enabled_in(SPURIOUS_DRAGON);
+        let is_spurious_dragon_enabled = self.is_state_clear_enabled();
+        if let Some(reverted_entries) = &mut self.reverted_entries {
+            reverted_entries.extend(
+                self.journal[checkpoint.journal_i..]
+                    .iter()
+                    .flatten()
+                    .cloned(),
+            );
+        }
         let state = &mut self.state;
         let transient_storage = &mut self.transient_storage;
         self.depth -= 1;
@@ -634,9 +761,88 @@ impl<DB: Database> JournaledState<DB> {
             });
 
         self.logs.truncate(checkpoint.log_i);
+        self.log_depths.truncate(checkpoint.log_i);
         self.journal.truncate(checkpoint.journal_i);
     }
 
+    /// Returns the net state changes made since the checkpoint.
+    ///
+    /// Journal entries recorded after the checkpoint are walked back from the current state, so
+    /// multiple writes to the same slot or balance coalesce into a single old to new change.
+    /// Changes that cancel out are omitted. Warming, touching and transient storage are ignored.
+    ///
+    /// Returns an error if the checkpoint was discarded, by reverting it or by clearing or
+    /// finalizing the journal.
+    pub fn diff_since(
+        &self,
+        checkpoint: JournalCheckpoint,
+    ) -> Result<StateDelta, StaleCheckpointError> {
+        // The journal of a reverted checkpoint is truncated, it stays in place once committed.
+        if checkpoint.generation != self.generation || checkpoint.journal_i >= self.journal.len() {
+            return Err(StaleCheckpointError);
+        }
+        let mut accounts: HashMap<Address, AccountDelta> = HashMap::default();
+        let entries = self.journal[checkpoint.journal_i..].iter().flatten().rev();
+        for entry in entries {
+            match entry {
+                JournalEntry::AccountDestroyed {
+                    address,
+                    target,
+                    had_balance,
+                    ..
+                } => {
+                    let account = account_delta(&mut accounts, &self.state, address);
+                    account.destroyed = true;
+                    account.balance.old += had_balance;
+                    if address != target {
+                        account_delta(&mut accounts, &self.state, target)
+                            .balance
+                            .old -= had_balance;
+                    }
+                }
+                JournalEntry::BalanceTransfer { from, to, balance } => {
+                    account_delta(&mut accounts, &self.state, from).balance.old += balance;
+                    account_delta(&mut accounts, &self.state, to).balance.old -= balance;
+                }
+                JournalEntry::NonceChange { address } => {
+                    account_delta(&mut accounts, &self.state, address).nonce.old -= 1
+                }
+                JournalEntry::AccountCreated { address } => {
+                    account_delta(&mut accounts, &self.state, address).created = true
+                }
+                JournalEntry::CodeChange { address } => {
+                    account_delta(&mut accounts, &self.state, address).code_changed = true
+                }
+                JournalEntry::StorageChanged {
+                    address,
+                    key,
+                    had_value,
+                } => {
+                    let present = self.state[address].storage[key].present_value;
+                    // Entries are walked backwards, the earliest write holds the old value.
+                    account_delta(&mut accounts, &self.state, address)
+                        .storage
+                        .insert(*key, Change::new(*had_value, present));
+                }
+                JournalEntry::AccountWarmed { .. }
+                | JournalEntry::AccountTouched { .. }
+                | JournalEntry::StorageWarmed { .. }
+                | JournalEntry::TransientStorageChange { .. } => {}
+            }
+        }
+
+        accounts.retain(|_, account| {
+            account.storage.retain(|_, slot| slot.is_changed());
+            account.balance.is_changed()
+                || account.nonce.is_changed()
+                || !account.storage.is_empty()
+                || account.code_changed
+                || account.created
+                || account.destroyed
+        });
+        Ok(StateDelta { accounts })
+    }
+
     /// Performs selfdestruct action.
     /// Transfers balance from address to target. Check if target exist/is_cold
     ///
@@ -654,10 +860,10 @@ impl<DB: Database> JournaledState<DB> {
         address: Address,
         target: Address,
     ) -> Result<StateLoad<SelfDestructResult>, DB::Error> {
-        let spec = self.spec;
+        let state_clear = self.is_state_clear_enabled();
         let account_load = self.load_account(target)?;
         let is_cold = account_load.is_cold;
-        let is_empty = account_load.state_clear_aware_is_empty(spec);
+        let is_empty = account_load.is_empty_with_state_clear(state_clear);
 
         if address != target {
             // Both accounts are loaded before this point, `address` as we execute its contract.
@@ -730,11 +936,17 @@ impl<DB: Database> JournaledState<DB> {
                     .unwrap_or(Account::new_not_existing()),
             ),
         };
-        // preload storages.
-        for storage_key in storage_keys.into_iter() {
-            if let Entry::Vacant(entry) = account.storage.entry(storage_key) {
-                let storage = self.database.storage(address, storage_key)?;
-                entry.insert(EvmStorageSlot::new(storage));
+        // preload storages, fetching the missing ones in a single batch.
+        let mut missing: Vec<U256> = storage_keys
+            .into_iter()
+            .filter(|key| !account.storage.contains_key(key))
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        if !missing.is_empty() {
+            let values = self.database.storage_batch(address, &missing)?;
+            for (key, value) in missing.into_iter().zip(values) {
+                account.storage.insert(key, EvmStorageSlot::new(value));
             }
         }
         Ok(account)
@@ -748,9 +960,9 @@ impl<DB: Database> JournaledState<DB> {
 
     #[inline]
     pub fn load_account_delegated(&mut self, address: Address) -> Result<AccountLoad, DB::Error> {
-        let spec = self.spec;
+        let state_clear = self.is_state_clear_enabled();
         let account = self.load_code(address)?;
-        let is_empty = account.state_clear_aware_is_empty(spec);
+        let is_empty = account.is_empty_with_state_clear(state_clear);
 
         let mut account_load = AccountLoad {
             is_empty,
@@ -789,12 +1001,16 @@ impl<DB: Database> JournaledState<DB> {
                 }
             }
             Entry::Vacant(vac) => {
-                let account = if let Some(account) = self.database.basic(address)? {
+                let mut account = if let Some(account) = self.database.basic(address)? {
                     account.into()
                 } else {
                     Account::new_not_existing()
                 };
 
+                if let Some(code) = self.code_overrides.get(&address) {
+                    account.info.code = Some(code.clone());
+                }
+
                 // precompiles are warm loaded so we need to take that into account
                 let is_cold = !self.warm_preloaded_addresses.contains(&address);
 
@@ -836,8 +1052,9 @@ impl<DB: Database> JournaledState<DB> {
     pub fn sload(&mut self, address: Address, key: U256) -> Result<StateLoad<U256>, DB::Error> {
         // assume acc is warm
         let account = self.state.get_mut(&address).unwrap();
-        // only if account is created in this tx we can assume that storage is empty.
-        let is_newly_created = account.is_created();
+        // only if account is created in this tx or its storage was cleared we can assume that
+        // storage is empty.
+        let is_newly_created = account.is_created() || account.is_storage_cleared();
         let (value, is_cold) = match account.storage.entry(key) {
             Entry::Occupied(occ) => {
                 let slot = occ.into_mut();
@@ -977,6 +1194,7 @@ impl<DB: Database> JournaledState<DB> {
     #[inline]
     pub fn log(&mut self, log: Log) {
         self.logs.push(log);
+        self.log_depths.push(self.depth);
     }
 }
 
@@ -1045,3 +1263,256 @@ pub enum JournalEntry {
     /// Revert: Revert to previous bytecode.
     CodeChange { address: Address },
 }
+
+/// Net state changes between a checkpoint and the current state, see
+/// [`JournaledState::diff_since`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDelta {
+    /// Changed accounts.
+    pub accounts: HashMap<Address, AccountDelta>,
+}
+
+/// Error of [`JournaledState::diff_since`] for a checkpoint that was discarded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaleCheckpointError;
+
+impl core::fmt::Display for StaleCheckpointError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("checkpoint was discarded")
+    }
+}
+
+impl core::error::Error for StaleCheckpointError {}
+
+/// Net changes of an account, see [`StateDelta`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountDelta {
+    pub balance: Change<U256>,
+    pub nonce: Change<u64>,
+    /// Changed storage slots.
+    pub storage: HashMap<U256, Change<U256>>,
+    /// `true` if code was deployed to the account.
+    pub code_changed: bool,
+    pub created: bool,
+    pub destroyed: bool,
+}
+
+/// Returns the delta of the account, initialized with its current values.
+fn account_delta<'a>(
+    accounts: &'a mut HashMap<Address, AccountDelta>,
+    state: &EvmState,
+    address: &Address,
+) -> &'a mut AccountDelta {
+    accounts.entry(*address).or_insert_with(|| {
+        let info = &state[address].info;
+        AccountDelta {
+            balance: Change::unchanged(info.balance),
+            nonce: Change::unchanged(info.nonce),
+            storage: HashMap::default(),
+            code_changed: false,
+            created: false,
+            destroyed: false,
+        }
+    })
+}
+
+/// Value before and after a change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: Copy + PartialEq> Change<T> {
+    /// Creates a change from the old to the new value.
+    pub fn new(old: T, new: T) -> Self {
+        Self { old, new }
+    }
+
+    fn unchanged(value: T) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Returns `true` if the new value differs from the old one.
+    pub fn is_changed(&self) -> bool {
+        self.old != self.new
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::opcode::{PUSH1, STOP};
+    use core::{cell::RefCell, convert::Infallible};
+    use database::CacheDB;
+    use database_interface::{DatabaseRef, EmptyDB};
+    use state::AccountInfo;
+
+    /// Records the storage requests, every slot holds its index plus one.
+    #[derive(Default)]
+    struct StorageRequests(RefCell<Vec<Vec<U256>>>);
+
+    impl DatabaseRef for StorageRequests {
+        type Error = Infallible;
+
+        fn basic_ref(&self, _address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(Some(AccountInfo::default()))
+        }
+
+        fn code_by_hash_ref(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Ok(Bytecode::default())
+        }
+
+        fn storage_ref(&self, _address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.0.borrow_mut().push(vec![index]);
+            Ok(index + U256::from(1))
+        }
+
+        fn storage_batch_ref(
+            &self,
+            _address: Address,
+            indices: &[U256],
+        ) -> Result<Vec<U256>, Self::Error> {
+            self.0.borrow_mut().push(indices.to_vec());
+            Ok(indices.iter().map(|index| index + U256::from(1)).collect())
+        }
+
+        fn block_hash_ref(&self, _number: u64) -> Result<B256, Self::Error> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    #[test]
+    fn access_list_slots_are_fetched_in_one_batch() {
+        let address = Address::with_last_byte(1);
+        let mut journal =
+            JournaledState::new(SpecId::PRAGUE, CacheDB::new(StorageRequests::default()));
+
+        let keys = [3, 1, 2, 1].map(U256::from);
+        journal.warm_account_and_storage(address, keys).unwrap();
+        assert_eq!(
+            *journal.database.db.0.borrow(),
+            [[1, 2, 3].map(U256::from).to_vec()]
+        );
+        for key in keys {
+            assert_eq!(
+                journal.sload(address, key).unwrap().data,
+                key + U256::from(1)
+            );
+        }
+
+        // Cached slots are not requested again.
+        journal
+            .warm_account_and_storage(address, [2, 4].map(U256::from))
+            .unwrap();
+        assert_eq!(journal.database.db.0.borrow().len(), 2);
+        assert_eq!(journal.database.db.0.borrow()[1], [U256::from(4)]);
+    }
+
+    #[test]
+    fn warm_status_query_does_not_warm() {
+        let address = Address::with_last_byte(1);
+        let preloaded = Address::with_last_byte(2);
+        let mut journal = JournaledState::new(SpecId::PRAGUE, CacheDB::new(EmptyDB::default()));
+        journal.warm_preloaded_addresses.insert(preloaded);
+
+        assert!(!journal.is_account_warm(address));
+        assert!(journal.is_account_warm(preloaded));
+        // Querying did not warm the account.
+        assert!(journal.load_account(address).unwrap().is_cold);
+
+        assert!(journal.is_account_warm(address));
+        assert!(!journal.is_storage_warm(address, U256::ZERO));
+        assert!(journal.sload(address, U256::ZERO).unwrap().is_cold);
+        assert!(journal.is_storage_warm(address, U256::ZERO));
+    }
+
+    #[test]
+    fn diff_since_coalesces_slot_writes() {
+        let address = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_storage(address, U256::from(2), U256::from(3))
+            .unwrap();
+        let mut journal = JournaledState::new(SpecId::PRAGUE, db);
+        journal.load_account(address).unwrap();
+        journal
+            .sstore(address, U256::from(1), U256::from(9))
+            .unwrap();
+
+        let checkpoint = journal.checkpoint();
+        for (key, value) in [(1, 5), (1, 7), (2, 4), (2, 3)] {
+            journal
+                .sstore(address, U256::from(key), U256::from(value))
+                .unwrap();
+        }
+        journal.inc_nonce(address);
+        journal.checkpoint_commit();
+
+        let delta = journal.diff_since(checkpoint).unwrap();
+        let account = &delta.accounts[&address];
+        // Slot 1 is reported once, from the value at the checkpoint. Slot 2 is back to its value.
+        assert_eq!(
+            account.storage,
+            [(U256::from(1), Change::new(U256::from(9), U256::from(7)))]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(account.nonce, Change::new(0, 1));
+        assert!(!account.balance.is_changed());
+
+        // Nothing changed since a new checkpoint.
+        let checkpoint = journal.checkpoint();
+        assert_eq!(journal.diff_since(checkpoint), Ok(StateDelta::default()));
+    }
+
+    #[test]
+    fn diff_since_rejects_discarded_checkpoints() {
+        let address = Address::with_last_byte(1);
+        let mut journal = JournaledState::new(SpecId::PRAGUE, CacheDB::new(EmptyDB::default()));
+        journal.load_account(address).unwrap();
+
+        let checkpoint = journal.checkpoint();
+        journal.inc_nonce(address);
+        journal.checkpoint_revert(checkpoint);
+        assert_eq!(journal.diff_since(checkpoint), Err(StaleCheckpointError));
+
+        // The checkpoint of a previous transaction is stale even if its index exists again.
+        let checkpoint = journal.checkpoint();
+        journal.checkpoint_commit();
+        journal.clear();
+        journal.checkpoint();
+        journal.checkpoint();
+        assert_eq!(journal.diff_since(checkpoint), Err(StaleCheckpointError));
+    }
+
+    #[test]
+    fn code_override_is_not_committed() {
+        let contract = Address::with_last_byte(0x42);
+        let original = Bytecode::new_legacy([PUSH1, 0x01, STOP].into());
+        let patched = Bytecode::new_legacy([PUSH1, 0x02, STOP].into());
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(contract, AccountInfo::from_bytecode(original.clone()));
+        let mut journal = JournaledState::new(SpecId::PRAGUE, db);
+        journal.set_code_override(contract, patched.clone());
+
+        let account = journal.load_code(contract).unwrap().data;
+        assert_eq!(account.info.code, Some(patched));
+
+        // Overridden code is dropped, the database keeps the original code hash.
+        let (state, _) = journal.finalize().unwrap();
+        assert_eq!(state[&contract].info.code, None);
+        assert_eq!(state[&contract].info.code_hash, original.hash_slow());
+    }
+
+    #[test]
+    fn persistent_warm_addresses_survive_clear() {
+        let preloaded = Address::with_last_byte(0x42);
+        let mut journal = JournaledState::new(SpecId::PRAGUE, CacheDB::new(EmptyDB::default()));
+        journal.warm_account_persistent(preloaded);
+
+        for _ in 0..2 {
+            assert!(!journal.load_account(preloaded).unwrap().is_cold);
+            journal.clear();
+        }
+    }
+}
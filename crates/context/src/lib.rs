@@ -9,10 +9,14 @@ pub mod block;
 pub mod cfg;
 pub mod context;
 pub mod journaled_state;
+#[cfg(feature = "signer-recovery")]
+pub mod signed_tx;
 pub mod tx;
 
 pub use block::BlockEnv;
 pub use cfg::{Cfg, CfgEnv};
 pub use context::*;
 pub use journaled_state::*;
+#[cfg(feature = "signer-recovery")]
+pub use signed_tx::{recover_signer, RecoveryError, SignedTx};
 pub use tx::TxEnv;
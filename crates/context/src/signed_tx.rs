@@ -0,0 +1,541 @@
+//! Recovery of the transaction sender from signed transactions.
+use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header};
+use context_interface::transaction::TransactionType;
+use core::fmt;
+use precompile::secp256k1::ecrecover;
+use primitives::{alloy_primitives::B512, keccak256, Address, Bytes, TxKind, B256, U256};
+use specification::eip2::SECP256K1N_HALF;
+use std::vec::Vec;
+
+/// Entry of an [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<B256>,
+}
+
+impl AccessListItem {
+    fn fields_length(&self) -> usize {
+        self.address.length() + self.storage_keys.length()
+    }
+}
+
+impl Encodable for AccessListItem {
+    fn encode(&self, out: &mut dyn BufMut) {
+        list_header(self.fields_length()).encode(out);
+        self.address.encode(out);
+        self.storage_keys.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        list_length(self.fields_length())
+    }
+}
+
+impl Decodable for AccessListItem {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |buf| {
+            Ok(Self {
+                address: Decodable::decode(buf)?,
+                storage_keys: Decodable::decode(buf)?,
+            })
+        })
+    }
+}
+
+/// Signed EIP-7702 authorization as it appears in the transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedAuthorization {
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: u64,
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl SignedAuthorization {
+    fn fields_length(&self) -> usize {
+        self.chain_id.length()
+            + self.address.length()
+            + self.nonce.length()
+            + self.y_parity.length()
+            + self.r.length()
+            + self.s.length()
+    }
+}
+
+impl Encodable for SignedAuthorization {
+    fn encode(&self, out: &mut dyn BufMut) {
+        list_header(self.fields_length()).encode(out);
+        self.chain_id.encode(out);
+        self.address.encode(out);
+        self.nonce.encode(out);
+        self.y_parity.encode(out);
+        self.r.encode(out);
+        self.s.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        list_length(self.fields_length())
+    }
+}
+
+impl Decodable for SignedAuthorization {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        decode_list(buf, |buf| {
+            Ok(Self {
+                chain_id: Decodable::decode(buf)?,
+                address: Decodable::decode(buf)?,
+                nonce: Decodable::decode(buf)?,
+                y_parity: Decodable::decode(buf)?,
+                r: Decodable::decode(buf)?,
+                s: Decodable::decode(buf)?,
+            })
+        })
+    }
+}
+
+/// Decoded signed transaction of any of the Ethereum envelope types.
+///
+/// Fields that are not part of the transaction type are ignored when computing the signature
+/// hash.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedTx {
+    pub tx_type: TransactionType,
+    /// Chain ID of typed transactions.
+    ///
+    /// Legacy transactions take the chain ID from [`v`][Self::v] instead.
+    pub chain_id: u64,
+    pub nonce: u64,
+    /// Gas price of legacy and EIP-2930 transactions, max fee per gas of the other types.
+    pub gas_price: u128,
+    /// Max priority fee per gas, since EIP-1559 transactions.
+    pub gas_priority_fee: u128,
+    pub gas_limit: u64,
+    pub kind: TxKind,
+    pub value: U256,
+    pub data: Bytes,
+    pub access_list: Vec<AccessListItem>,
+    pub max_fee_per_blob_gas: u128,
+    pub blob_hashes: Vec<B256>,
+    pub authorization_list: Vec<SignedAuthorization>,
+    /// Signature `v` value.
+    ///
+    /// For legacy transactions it is `27` or `28`, or `chain_id * 2 + 35` or `chain_id * 2 + 36`
+    /// with [EIP-155] replay protection. For typed transactions it is the y parity.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub v: u64,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// Error that occurs when recovering the signer of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecoveryError {
+    /// Transaction type does not have a known signature scheme.
+    UnsupportedTransactionType,
+    /// EIP-4844 and EIP-7702 transactions can not create contracts.
+    MissingDestination,
+    /// `v` value is not valid for the transaction type.
+    InvalidV,
+    /// `s` value is greater than half of the curve order, as forbidden by EIP-2.
+    HighS,
+    /// Public key could not be recovered from the signature.
+    InvalidSignature,
+}
+
+impl core::error::Error for RecoveryError {}
+
+impl fmt::Display for RecoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::UnsupportedTransactionType => "unsupported transaction type",
+            Self::MissingDestination => "transaction type requires a destination address",
+            Self::InvalidV => "invalid signature v value",
+            Self::HighS => "signature s value is greater than secp256k1n/2",
+            Self::InvalidSignature => "invalid signature",
+        };
+        f.write_str(s)
+    }
+}
+
+impl SignedTx {
+    /// Decodes a signed transaction from its network encoding, a RLP list for legacy
+    /// transactions and an [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718) envelope for
+    /// typed transactions.
+    ///
+    /// EIP-4844 transactions are decoded from their canonical encoding, without the blobs.
+    pub fn decode_enveloped(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut tx = Self::default();
+        let first = *buf.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        // Legacy transactions start with a list header, typed ones with their type.
+        if first < alloy_rlp::EMPTY_LIST_CODE {
+            tx.tx_type = match first {
+                1 => TransactionType::Eip2930,
+                2 => TransactionType::Eip1559,
+                3 => TransactionType::Eip4844,
+                4 => TransactionType::Eip7702,
+                _ => return Err(alloy_rlp::Error::Custom("unsupported transaction type")),
+            };
+            *buf = &buf[1..];
+        }
+        decode_list(buf, |buf| {
+            tx.decode_fields(buf)?;
+            tx.v = Decodable::decode(buf)?;
+            tx.r = Decodable::decode(buf)?;
+            tx.s = Decodable::decode(buf)?;
+            Ok(tx)
+        })
+    }
+
+    /// Returns the hash the sender signed.
+    pub fn signature_hash(&self) -> Result<B256, RecoveryError> {
+        if matches!(
+            self.tx_type,
+            TransactionType::Eip4844 | TransactionType::Eip7702
+        ) && self.kind.is_create()
+        {
+            return Err(RecoveryError::MissingDestination);
+        }
+        let mut fields = Vec::new();
+        self.encode_fields(&mut fields);
+        // Typed transactions sign `type || rlp(fields)`.
+        let mut out = Vec::with_capacity(1 + list_length(fields.len()));
+        match self.tx_type {
+            TransactionType::Legacy => {
+                if let Some(chain_id) = self.legacy_chain_id()? {
+                    chain_id.encode(&mut fields);
+                    0u8.encode(&mut fields);
+                    0u8.encode(&mut fields);
+                }
+            }
+            TransactionType::Custom => return Err(RecoveryError::UnsupportedTransactionType),
+            tx_type => out.push(tx_type as u8),
+        }
+        list_header(fields.len()).encode(&mut out);
+        out.put_slice(&fields);
+        Ok(keccak256(out))
+    }
+
+    /// Returns the recovery id of the signature.
+    fn recovery_id(&self) -> Result<u8, RecoveryError> {
+        let parity = match self.tx_type {
+            TransactionType::Legacy => match self.v {
+                27 | 28 => self.v - 27,
+                v if v >= 35 => (v - 35) % 2,
+                _ => return Err(RecoveryError::InvalidV),
+            },
+            _ if self.v <= 1 => self.v,
+            _ => return Err(RecoveryError::InvalidV),
+        };
+        Ok(parity as u8)
+    }
+
+    /// Returns the EIP-155 chain ID encoded in `v` of a legacy transaction.
+    fn legacy_chain_id(&self) -> Result<Option<u64>, RecoveryError> {
+        match self.v {
+            27 | 28 => Ok(None),
+            v if v >= 35 => Ok(Some((v - 35) / 2)),
+            _ => Err(RecoveryError::InvalidV),
+        }
+    }
+
+    /// Encodes the fields of the transaction type without the signature.
+    fn encode_fields(&self, out: &mut dyn BufMut) {
+        match self.tx_type {
+            TransactionType::Legacy | TransactionType::Eip2930 => {
+                if self.tx_type == TransactionType::Eip2930 {
+                    self.chain_id.encode(out);
+                }
+                self.nonce.encode(out);
+                self.gas_price.encode(out);
+                self.gas_limit.encode(out);
+                self.kind.encode(out);
+                self.value.encode(out);
+                self.data.encode(out);
+                if self.tx_type == TransactionType::Eip2930 {
+                    self.access_list.encode(out);
+                }
+            }
+            _ => {
+                self.chain_id.encode(out);
+                self.nonce.encode(out);
+                self.gas_priority_fee.encode(out);
+                self.gas_price.encode(out);
+                self.gas_limit.encode(out);
+                self.kind.encode(out);
+                self.value.encode(out);
+                self.data.encode(out);
+                self.access_list.encode(out);
+                match self.tx_type {
+                    TransactionType::Eip4844 => {
+                        self.max_fee_per_blob_gas.encode(out);
+                        self.blob_hashes.encode(out);
+                    }
+                    TransactionType::Eip7702 => self.authorization_list.encode(out),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Decodes the fields of the transaction type without the signature, the inverse of
+    /// [`encode_fields`][Self::encode_fields].
+    fn decode_fields(&mut self, buf: &mut &[u8]) -> alloy_rlp::Result<()> {
+        match self.tx_type {
+            TransactionType::Legacy | TransactionType::Eip2930 => {
+                if self.tx_type == TransactionType::Eip2930 {
+                    self.chain_id = Decodable::decode(buf)?;
+                }
+                self.nonce = Decodable::decode(buf)?;
+                self.gas_price = Decodable::decode(buf)?;
+                self.gas_limit = Decodable::decode(buf)?;
+                self.kind = Decodable::decode(buf)?;
+                self.value = Decodable::decode(buf)?;
+                self.data = Decodable::decode(buf)?;
+                if self.tx_type == TransactionType::Eip2930 {
+                    self.access_list = Decodable::decode(buf)?;
+                }
+            }
+            _ => {
+                self.chain_id = Decodable::decode(buf)?;
+                self.nonce = Decodable::decode(buf)?;
+                self.gas_priority_fee = Decodable::decode(buf)?;
+                self.gas_price = Decodable::decode(buf)?;
+                self.gas_limit = Decodable::decode(buf)?;
+                self.kind = Decodable::decode(buf)?;
+                self.value = Decodable::decode(buf)?;
+                self.data = Decodable::decode(buf)?;
+                self.access_list = Decodable::decode(buf)?;
+                match self.tx_type {
+                    TransactionType::Eip4844 => {
+                        self.max_fee_per_blob_gas = Decodable::decode(buf)?;
+                        self.blob_hashes = Decodable::decode(buf)?;
+                    }
+                    TransactionType::Eip7702 => {
+                        self.authorization_list = Decodable::decode(buf)?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn list_header(payload_length: usize) -> Header {
+    Header {
+        list: true,
+        payload_length,
+    }
+}
+
+/// Returns the encoded length of a list with the given payload length.
+fn list_length(payload_length: usize) -> usize {
+    payload_length + length_of_length(payload_length)
+}
+
+/// Decodes a RLP list with `f`, which must consume the whole payload.
+fn decode_list<T>(
+    buf: &mut &[u8],
+    f: impl FnOnce(&mut &[u8]) -> alloy_rlp::Result<T>,
+) -> alloy_rlp::Result<T> {
+    let mut payload = Header::decode_bytes(buf, true)?;
+    let value = f(&mut payload)?;
+    if !payload.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength);
+    }
+    Ok(value)
+}
+
+/// Recovers the sender of the signed transaction.
+///
+/// The recovered address can be used as [`TxEnv::caller`][crate::TxEnv::caller].
+pub fn recover_signer(tx: &SignedTx) -> Result<Address, RecoveryError> {
+    if tx.s > SECP256K1N_HALF {
+        return Err(RecoveryError::HighS);
+    }
+    let recid = tx.recovery_id()?;
+    let hash = tx.signature_hash()?;
+
+    let mut sig = B512::ZERO;
+    sig[..32].copy_from_slice(&tx.r.to_be_bytes::<32>());
+    sig[32..].copy_from_slice(&tx.s.to_be_bytes::<32>());
+    ecrecover(&sig, recid, &hash)
+        .map(Address::from_word)
+        .ok_or(RecoveryError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+    use primitives::{address, b256, hex};
+    use std::vec;
+
+    /// Sender of the EIP-155 example transaction.
+    const SENDER: Address = address!("9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+    fn sign(mut tx: SignedTx) -> SignedTx {
+        let key = SigningKey::from_slice(&[0x46; 32]).unwrap();
+        let hash = tx.signature_hash().unwrap();
+        let (sig, recid) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+        tx.r = U256::from_be_slice(&sig.r().to_bytes());
+        tx.s = U256::from_be_slice(&sig.s().to_bytes());
+        tx.v = match tx.tx_type {
+            TransactionType::Legacy => 27 + recid.to_byte() as u64,
+            _ => recid.to_byte() as u64,
+        };
+        tx
+    }
+
+    fn eip155_example() -> SignedTx {
+        SignedTx {
+            nonce: 9,
+            gas_price: 20_000_000_000,
+            gas_limit: 21_000,
+            kind: TxKind::Call(address!("3535353535353535353535353535353535353535")),
+            value: U256::from(1_000_000_000_000_000_000u64),
+            v: 37,
+            r: U256::from_be_bytes(hex!(
+                "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276"
+            )),
+            s: U256::from_be_bytes(hex!(
+                "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+            )),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn recovers_eip155_example() {
+        let tx = eip155_example();
+        assert_eq!(
+            tx.signature_hash().unwrap(),
+            b256!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53")
+        );
+        assert_eq!(recover_signer(&tx), Ok(SENDER));
+
+        let mut tx = tx;
+        tx.v = 30;
+        assert_eq!(recover_signer(&tx), Err(RecoveryError::InvalidV));
+    }
+
+    /// Encodes the transaction as it is sent over the network.
+    fn encode(tx: &SignedTx) -> Vec<u8> {
+        let mut fields = Vec::new();
+        tx.encode_fields(&mut fields);
+        tx.v.encode(&mut fields);
+        tx.r.encode(&mut fields);
+        tx.s.encode(&mut fields);
+        let mut out = Vec::new();
+        if tx.tx_type != TransactionType::Legacy {
+            out.push(tx.tx_type as u8);
+        }
+        list_header(fields.len()).encode(&mut out);
+        out.extend(fields);
+        out
+    }
+
+    #[test]
+    fn recovers_mainnet_transactions() {
+        for (raw, hash, sender) in [
+            // Legacy with EIP-155 replay protection.
+            (
+                &hex!("f8aa808512ec276caf83010e2b94dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb000000000000000000000000fdae129ecc2c27d166a3131098bc05d143fa258e0000000000000000000000000000000000000000000000000000000002faf08025a0c81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141a01dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274")[..],
+                b256!("929ff27a5c7833953df23103c4eb55ebdfb698678139d751c51932163877fada"),
+                address!("c26ad91f4e7a0cad84c4b9315f420ca9217e315d"),
+            ),
+            // EIP-2930 with an access list.
+            (
+                &hex!("01f90126018223ff850a02ffee00830f4240940000000000a8fb09af944ab3baf7a9b3e1ab29d880b876200200001525000000000b69ffb300000000557b933a7c2c45672b610f8954a3deb39a51a8cae53ec727dbdeb9e2d5456c3be40cff031ab40a55724d5c9c618a2152e99a45649a3b8cf198321f46720b722f4ec38f99ba3bb1303258d2e816e6a95b25647e01bd0967c1b9599fa3521939871d1d0888f845d694724d5c9c618a2152e99a45649a3b8cf198321f46c0d694720b722f4ec38f99ba3bb1303258d2e816e6a95bc0d69425647e01bd0967c1b9599fa3521939871d1d0888c001a08323efae7b9993bd31a58da7924359d24b5504aa2b33194fcc5ae206e65d2e62a054ce201e3b4b5cd38eb17c56ee2f9111b2e164efcd57b3e70fa308a0a51f7014")[..],
+                keccak256(hex!("01f90126018223ff850a02ffee00830f4240940000000000a8fb09af944ab3baf7a9b3e1ab29d880b876200200001525000000000b69ffb300000000557b933a7c2c45672b610f8954a3deb39a51a8cae53ec727dbdeb9e2d5456c3be40cff031ab40a55724d5c9c618a2152e99a45649a3b8cf198321f46720b722f4ec38f99ba3bb1303258d2e816e6a95b25647e01bd0967c1b9599fa3521939871d1d0888f845d694724d5c9c618a2152e99a45649a3b8cf198321f46c0d694720b722f4ec38f99ba3bb1303258d2e816e6a95bc0d69425647e01bd0967c1b9599fa3521939871d1d0888c001a08323efae7b9993bd31a58da7924359d24b5504aa2b33194fcc5ae206e65d2e62a054ce201e3b4b5cd38eb17c56ee2f9111b2e164efcd57b3e70fa308a0a51f7014")),
+                address!("e9c790e8fde820ded558a4771b72eec916c04763"),
+            ),
+            // EIP-1559.
+            (
+                &hex!("02f87a018201df851344ead983851344ead983826d2294c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2882b40d6d551c8970c84d0e30db0c001a05616cdaec839ca14d209b59eafb706e623169dc9d0fa58fbf13931cef5b5e3b0a03e708f8044bd158d29c2e250b6a98ea637c3bc460beeea63a8f00f7cebac432a")[..],
+                b256!("781d57642f4e3277fe01d370bd45ba1361b475bea6a35f26814e02a0a2b26549"),
+                address!("057f8d0f6fb2703197363f75c002f766f1c4287a"),
+            ),
+        ] {
+            assert_eq!(keccak256(raw), hash);
+            let tx = SignedTx::decode_enveloped(&mut &raw[..]).unwrap();
+            assert_eq!(encode(&tx), raw);
+            assert_eq!(recover_signer(&tx), Ok(sender), "{hash}");
+        }
+    }
+
+    #[test]
+    fn recovers_all_envelope_types() {
+        let base = SignedTx {
+            chain_id: 1,
+            access_list: vec![AccessListItem {
+                address: SENDER,
+                storage_keys: vec![B256::with_last_byte(1)],
+            }],
+            gas_priority_fee: 1_000_000_000,
+            max_fee_per_blob_gas: 1,
+            blob_hashes: vec![B256::with_last_byte(1)],
+            authorization_list: vec![SignedAuthorization {
+                chain_id: U256::from(1),
+                address: SENDER,
+                nonce: 1,
+                y_parity: 1,
+                r: U256::from(2),
+                s: U256::from(3),
+            }],
+            ..eip155_example()
+        };
+        let legacy = SignedTx {
+            v: 27,
+            ..base.clone()
+        };
+        assert_eq!(recover_signer(&sign(legacy)), Ok(SENDER));
+
+        for tx_type in [
+            TransactionType::Eip2930,
+            TransactionType::Eip1559,
+            TransactionType::Eip4844,
+            TransactionType::Eip7702,
+        ] {
+            let tx = sign(SignedTx {
+                tx_type,
+                ..base.clone()
+            });
+            assert_eq!(recover_signer(&tx), Ok(SENDER));
+            // Fields of other types are dropped by the encoding.
+            let decoded = SignedTx::decode_enveloped(&mut &encode(&tx)[..]).unwrap();
+            assert_eq!(encode(&decoded), encode(&tx));
+            assert_eq!(recover_signer(&decoded), Ok(SENDER));
+
+            // Every type commits to a different payload.
+            let mut other = tx.clone();
+            other.tx_type = TransactionType::Eip2930;
+            if tx_type != TransactionType::Eip2930 {
+                assert_ne!(recover_signer(&other), Ok(SENDER));
+            }
+
+            let mut high_s = tx.clone();
+            high_s.s = SECP256K1N_HALF + U256::from(1);
+            assert_eq!(recover_signer(&high_s), Err(RecoveryError::HighS));
+        }
+
+        let create = SignedTx {
+            tx_type: TransactionType::Eip4844,
+            kind: TxKind::Create,
+            v: 0,
+            ..base
+        };
+        assert_eq!(
+            recover_signer(&create),
+            Err(RecoveryError::MissingDestination)
+        );
+    }
+}
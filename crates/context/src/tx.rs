@@ -165,3 +165,42 @@ impl Transaction for TxEnv {
         self.gas_priority_fee
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use context_interface::TransactionType;
+
+    fn tx(tx_type: TransactionType, gas_price: u128, gas_priority_fee: Option<u128>) -> TxEnv {
+        TxEnv {
+            tx_type: tx_type as u8,
+            gas_price,
+            gas_priority_fee,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn effective_gas_price_per_tx_type() {
+        // Legacy gas price is used as is, even with a priority fee set or below the base fee.
+        for tx_type in [TransactionType::Legacy, TransactionType::Eip2930] {
+            assert_eq!(tx(tx_type, 100, Some(0)).effective_gas_price(90), 100);
+            assert_eq!(tx(tx_type, 80, None).effective_gas_price(90), 80);
+        }
+
+        for tx_type in [
+            TransactionType::Eip1559,
+            TransactionType::Eip4844,
+            TransactionType::Eip7702,
+        ] {
+            // Capped by the priority fee.
+            assert_eq!(tx(tx_type, 100, Some(5)).effective_gas_price(90), 95);
+            // Capped by the max fee.
+            assert_eq!(tx(tx_type, 100, Some(20)).effective_gas_price(90), 100);
+            assert_eq!(
+                tx(tx_type, u128::MAX, Some(1)).effective_gas_price(u128::MAX),
+                u128::MAX
+            );
+        }
+    }
+}
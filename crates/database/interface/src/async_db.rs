@@ -2,8 +2,10 @@ use core::future::Future;
 
 use crate::{DBErrorMarker, Database, DatabaseRef};
 use core::error::Error;
+use futures::future::try_join_all;
 use primitives::{Address, B256, U256};
 use state::{AccountInfo, Bytecode};
+use std::vec::Vec;
 use tokio::runtime::{Handle, Runtime};
 
 /// The async EVM database interface
@@ -39,6 +41,28 @@ pub trait DatabaseAsync {
         &mut self,
         number: u64,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
+
+    /// Gets basic account information of multiple accounts.
+    ///
+    /// Results are returned in the same order as `addresses`.
+    ///
+    /// Default implementation awaits [DatabaseAsync::basic_async] for every address, backends
+    /// that support batched requests should override it to reduce the number of round-trips.
+    fn basic_batch(
+        &mut self,
+        addresses: Vec<Address>,
+    ) -> impl Future<Output = Result<Vec<Option<AccountInfo>>, Self::Error>> + Send
+    where
+        Self: Send,
+    {
+        async move {
+            let mut accounts = Vec::with_capacity(addresses.len());
+            for address in addresses {
+                accounts.push(self.basic_async(address).await?);
+            }
+            Ok(accounts)
+        }
+    }
 }
 
 /// The async EVM database interface
@@ -74,6 +98,49 @@ pub trait DatabaseAsyncRef {
         &self,
         number: u64,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
+
+    /// Gets basic account information of multiple accounts.
+    ///
+    /// Results are returned in the same order as `addresses`.
+    ///
+    /// Default implementation joins [DatabaseAsyncRef::basic_async_ref] futures of all addresses,
+    /// backends that support batched requests should override it to reduce the number of
+    /// round-trips.
+    fn basic_batch_async_ref(
+        &self,
+        addresses: Vec<Address>,
+    ) -> impl Future<Output = Result<Vec<Option<AccountInfo>>, Self::Error>> + Send
+    where
+        Self: Sync,
+    {
+        try_join_all(
+            addresses
+                .into_iter()
+                .map(|address| self.basic_async_ref(address)),
+        )
+    }
+
+    /// Gets storage values of address at multiple indices.
+    ///
+    /// Results are returned in the same order as `indices`.
+    ///
+    /// Default implementation joins [DatabaseAsyncRef::storage_async_ref] futures of all indices,
+    /// backends that support batched requests should override it to reduce the number of
+    /// round-trips.
+    fn storage_batch_async_ref(
+        &self,
+        address: Address,
+        indices: Vec<U256>,
+    ) -> impl Future<Output = Result<Vec<U256>, Self::Error>> + Send
+    where
+        Self: Sync,
+    {
+        try_join_all(
+            indices
+                .into_iter()
+                .map(move |index| self.storage_async_ref(address, index)),
+        )
+    }
 }
 
 /// Wraps a [DatabaseAsync] or [DatabaseAsyncRef] to provide a [`Database`] implementation.
@@ -120,7 +187,7 @@ impl<T> WrapDatabaseAsync<T> {
     }
 }
 
-impl<T: DatabaseAsync> Database for WrapDatabaseAsync<T> {
+impl<T: DatabaseAsync + Send> Database for WrapDatabaseAsync<T> {
     type Error = T::Error;
 
     #[inline]
@@ -128,6 +195,18 @@ impl<T: DatabaseAsync> Database for WrapDatabaseAsync<T> {
         self.rt.block_on(self.db.basic_async(address))
     }
 
+    /// Fetches all accounts with a single [DatabaseAsync::basic_batch] request.
+    ///
+    /// Wrap in a caching database, like `CacheDB`, and call its `basic_batch` to prefetch
+    /// accounts of a known access list before synchronous execution begins.
+    #[inline]
+    fn basic_batch(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.rt.block_on(self.db.basic_batch(addresses.to_vec()))
+    }
+
     #[inline]
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
         self.rt.block_on(self.db.code_by_hash_async(code_hash))
@@ -144,7 +223,7 @@ impl<T: DatabaseAsync> Database for WrapDatabaseAsync<T> {
     }
 }
 
-impl<T: DatabaseAsyncRef> DatabaseRef for WrapDatabaseAsync<T> {
+impl<T: DatabaseAsyncRef + Sync> DatabaseRef for WrapDatabaseAsync<T> {
     type Error = T::Error;
 
     #[inline]
@@ -152,6 +231,19 @@ impl<T: DatabaseAsyncRef> DatabaseRef for WrapDatabaseAsync<T> {
         self.rt.block_on(self.db.basic_async_ref(address))
     }
 
+    /// Fetches all accounts with [DatabaseAsyncRef::basic_batch_async_ref].
+    ///
+    /// Wrap in a caching database, like `CacheDB`, and call its `basic_batch` to prefetch
+    /// accounts of a known access list before synchronous execution begins.
+    #[inline]
+    fn basic_batch_ref(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.rt
+            .block_on(self.db.basic_batch_async_ref(addresses.to_vec()))
+    }
+
     #[inline]
     fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
         self.rt.block_on(self.db.code_by_hash_async_ref(code_hash))
@@ -162,6 +254,16 @@ impl<T: DatabaseAsyncRef> DatabaseRef for WrapDatabaseAsync<T> {
         self.rt.block_on(self.db.storage_async_ref(address, index))
     }
 
+    #[inline]
+    fn storage_batch_ref(
+        &self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.rt
+            .block_on(self.db.storage_batch_async_ref(address, indices.to_vec()))
+    }
+
     #[inline]
     fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
         self.rt.block_on(self.db.block_hash_async_ref(number))
@@ -188,3 +290,142 @@ impl HandleOrRuntime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Backend counting account requests and the most requests in flight at once.
+    #[derive(Default)]
+    struct CountingDb {
+        calls: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl DatabaseAsyncRef for CountingDb {
+        type Error = Infallible;
+
+        async fn basic_async_ref(
+            &self,
+            address: Address,
+        ) -> Result<Option<AccountInfo>, Infallible> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(Some(AccountInfo {
+                nonce: address[19] as u64,
+                ..Default::default()
+            }))
+        }
+
+        async fn code_by_hash_async_ref(&self, _code_hash: B256) -> Result<Bytecode, Infallible> {
+            Ok(Bytecode::default())
+        }
+
+        async fn storage_async_ref(
+            &self,
+            _address: Address,
+            _index: U256,
+        ) -> Result<U256, Infallible> {
+            Ok(U256::ZERO)
+        }
+
+        async fn block_hash_async_ref(&self, _number: u64) -> Result<B256, Infallible> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    /// Backend counting account requests and batches.
+    #[derive(Default)]
+    struct CountingAsyncDb {
+        basic_calls: usize,
+        batch_calls: usize,
+    }
+
+    impl DatabaseAsync for CountingAsyncDb {
+        type Error = Infallible;
+
+        async fn basic_async(
+            &mut self,
+            _address: Address,
+        ) -> Result<Option<AccountInfo>, Infallible> {
+            self.basic_calls += 1;
+            Ok(Some(AccountInfo::default()))
+        }
+
+        async fn code_by_hash_async(&mut self, _code_hash: B256) -> Result<Bytecode, Infallible> {
+            Ok(Bytecode::default())
+        }
+
+        async fn storage_async(
+            &mut self,
+            _address: Address,
+            _index: U256,
+        ) -> Result<U256, Infallible> {
+            Ok(U256::ZERO)
+        }
+
+        async fn block_hash_async(&mut self, _number: u64) -> Result<B256, Infallible> {
+            Ok(B256::ZERO)
+        }
+
+        async fn basic_batch(
+            &mut self,
+            addresses: Vec<Address>,
+        ) -> Result<Vec<Option<AccountInfo>>, Infallible> {
+            self.batch_calls += 1;
+            Ok(addresses
+                .iter()
+                .map(|address| {
+                    Some(AccountInfo {
+                        nonce: address[19] as u64,
+                        ..Default::default()
+                    })
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn basic_batch_sends_one_batch_request() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let mut db = WrapDatabaseAsync::with_runtime(CountingAsyncDb::default(), runtime);
+        let addresses: Vec<_> = (1..=3).map(Address::with_last_byte).collect();
+
+        let accounts = Database::basic_batch(&mut db, &addresses).unwrap();
+        let nonces: Vec<_> = accounts
+            .iter()
+            .map(|info| info.as_ref().unwrap().nonce)
+            .collect();
+        assert_eq!(nonces, [1, 2, 3]);
+        assert_eq!(db.db.batch_calls, 1);
+        assert_eq!(db.db.basic_calls, 0);
+    }
+
+    #[test]
+    fn basic_batch_joins_account_requests() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let db = WrapDatabaseAsync::with_runtime(CountingDb::default(), runtime);
+        let addresses: Vec<_> = (1..=3).map(Address::with_last_byte).collect();
+
+        let accounts = db.basic_batch_ref(&addresses).unwrap();
+        let nonces: Vec<_> = accounts
+            .iter()
+            .map(|info| info.as_ref().unwrap().nonce)
+            .collect();
+        assert_eq!(nonces, [1, 2, 3]);
+        assert_eq!(db.db.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(db.db.max_in_flight.load(Ordering::SeqCst), 3);
+    }
+}
@@ -11,7 +11,7 @@ use auto_impl::auto_impl;
 use core::error::Error;
 use primitives::{Address, HashMap, B256, U256};
 use state::{Account, AccountInfo, Bytecode};
-use std::string::String;
+use std::{string::String, vec::Vec};
 
 #[cfg(feature = "asyncdb")]
 pub mod async_db;
@@ -42,12 +42,45 @@ pub trait Database {
     /// Gets basic account information.
     fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error>;
 
+    /// Gets basic account information of multiple accounts.
+    ///
+    /// Results are returned in the same order as `addresses`.
+    ///
+    /// Default implementation calls [`Database::basic`] for every address, backends that
+    /// support batched requests should override it to reduce the number of round-trips.
+    fn basic_batch(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        addresses
+            .iter()
+            .map(|address| self.basic(*address))
+            .collect()
+    }
+
     /// Gets account code by its hash.
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error>;
 
     /// Gets storage value of address at index.
     fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error>;
 
+    /// Gets storage values of address at multiple indices.
+    ///
+    /// Values are returned in the same order as `indices`.
+    ///
+    /// Default implementation calls [`Database::storage`] for every index, backends that support
+    /// batched requests should override it to reduce the number of round-trips.
+    fn storage_batch(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        indices
+            .iter()
+            .map(|index| self.storage(address, *index))
+            .collect()
+    }
+
     /// Gets block hash by block number.
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error>;
 }
@@ -73,12 +106,35 @@ pub trait DatabaseRef {
     /// Gets basic account information.
     fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error>;
 
+    /// Gets basic account information of multiple accounts, see [`Database::basic_batch`].
+    fn basic_batch_ref(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        addresses
+            .iter()
+            .map(|address| self.basic_ref(*address))
+            .collect()
+    }
+
     /// Gets account code by its hash.
     fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error>;
 
     /// Gets storage value of address at index.
     fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error>;
 
+    /// Gets storage values of address at multiple indices, see [`Database::storage_batch`].
+    fn storage_batch_ref(
+        &self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        indices
+            .iter()
+            .map(|index| self.storage_ref(address, *index))
+            .collect()
+    }
+
     /// Gets block hash by block number.
     fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error>;
 }
@@ -102,6 +158,14 @@ impl<T: DatabaseRef> Database for WrapDatabaseRef<T> {
         self.0.basic_ref(address)
     }
 
+    #[inline]
+    fn basic_batch(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.0.basic_batch_ref(addresses)
+    }
+
     #[inline]
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
         self.0.code_by_hash_ref(code_hash)
@@ -112,6 +176,15 @@ impl<T: DatabaseRef> Database for WrapDatabaseRef<T> {
         self.0.storage_ref(address, index)
     }
 
+    #[inline]
+    fn storage_batch(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.0.storage_batch_ref(address, indices)
+    }
+
     #[inline]
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
         self.0.block_hash_ref(number)
@@ -109,6 +109,25 @@ impl<T: Transport + Clone, N: Network, P: Provider<T, N>> DatabaseAsyncRef for A
             .block_id(self.block_number)
             .await?)
     }
+
+    /// Fetches all slots with a single `eth_getProof` request.
+    async fn storage_batch_async_ref(
+        &self,
+        address: Address,
+        indices: Vec<U256>,
+    ) -> Result<Vec<U256>, Self::Error> {
+        let keys = indices.into_iter().map(B256::from).collect();
+        let proof = self
+            .provider
+            .get_proof(address, keys)
+            .block_id(self.block_number)
+            .await?;
+        Ok(proof
+            .storage_proof
+            .into_iter()
+            .map(|slot| slot.value)
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -136,4 +155,29 @@ mod tests {
         let acc_info = wrapped_alloydb.basic_ref(address).unwrap().unwrap();
         assert!(acc_info.exists());
     }
+
+    #[test]
+    #[ignore = "flaky RPC"]
+    fn storage_batch_matches_single_reads() {
+        let client = ProviderBuilder::new().on_http(
+            "https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27"
+                .parse()
+                .unwrap(),
+        );
+        let alloydb = AlloyDB::new(client, BlockId::from(16148323));
+        let wrapped_alloydb = WrapDatabaseAsync::new(alloydb).unwrap();
+
+        // ETH/USDT pair on Uniswap V2, slots 6 to 8 hold the tokens and reserves.
+        let address: Address = "0x0d4a11d5EEaaC28EC3F61d100daF4d40471f1852"
+            .parse()
+            .unwrap();
+        let indices = [U256::from(6), U256::from(7), U256::from(8)];
+
+        let values = wrapped_alloydb
+            .storage_batch_ref(address, &indices)
+            .unwrap();
+        for (index, value) in indices.into_iter().zip(values) {
+            assert_eq!(wrapped_alloydb.storage_ref(address, index).unwrap(), value);
+        }
+    }
 }
@@ -0,0 +1,347 @@
+use core::{error::Error, fmt};
+use database_interface::{DBErrorMarker, Database, DatabaseCommit, DatabaseRef};
+use primitives::{Address, HashMap, B256, KECCAK_EMPTY, U256};
+use sled::{
+    transaction::{ConflictableTransactionError, TransactionError},
+    Transactional, Tree,
+};
+use state::{Account, AccountInfo, Bytecode};
+use std::path::Path;
+
+/// Error of the [`DiskDb`].
+#[derive(Debug)]
+pub enum DiskDbError {
+    /// Error of the underlying store.
+    Sled(sled::Error),
+    /// Stored value has an unexpected length.
+    Corrupted,
+}
+
+impl DBErrorMarker for DiskDbError {}
+
+impl fmt::Display for DiskDbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sled(e) => write!(f, "sled error: {e}"),
+            Self::Corrupted => f.write_str("corrupted database entry"),
+        }
+    }
+}
+
+impl Error for DiskDbError {}
+
+impl From<sled::Error> for DiskDbError {
+    fn from(e: sled::Error) -> Self {
+        Self::Sled(e)
+    }
+}
+
+impl From<TransactionError<DiskDbError>> for DiskDbError {
+    fn from(e: TransactionError<DiskDbError>) -> Self {
+        match e {
+            TransactionError::Abort(e) => e,
+            TransactionError::Storage(e) => Self::Sled(e),
+        }
+    }
+}
+
+/// Persistent database backed by a [sled] key-value store.
+///
+/// State is kept in separate trees:
+/// - accounts: `address` → `balance || nonce || code_hash`,
+/// - storage: `address || slot` → `value`, zero values are not stored,
+/// - contracts: `code_hash` → original bytecode,
+/// - block hashes: `number` (big endian) → `hash`.
+///
+/// Every [`DatabaseCommit::commit`] is applied in a single transaction and flushed to disk.
+#[derive(Clone, Debug)]
+pub struct DiskDb {
+    db: sled::Db,
+    accounts: Tree,
+    storage: Tree,
+    contracts: Tree,
+    block_hashes: Tree,
+}
+
+impl DiskDb {
+    /// Opens the database at the path, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DiskDbError> {
+        let db = sled::open(path)?;
+        Ok(Self {
+            accounts: db.open_tree("accounts")?,
+            storage: db.open_tree("storage")?,
+            contracts: db.open_tree("contracts")?,
+            block_hashes: db.open_tree("block_hashes")?,
+            db,
+        })
+    }
+
+    /// Inserts the account info, and its code if present.
+    pub fn insert_account_info(
+        &self,
+        address: Address,
+        mut info: AccountInfo,
+    ) -> Result<(), DiskDbError> {
+        if let Some(code) = prepare_code(&mut info) {
+            self.contracts.insert(info.code_hash, code)?;
+        }
+        self.accounts
+            .insert(address, encode_account(&info).as_slice())?;
+        Ok(())
+    }
+
+    /// Inserts the value of the account storage slot.
+    pub fn insert_account_storage(
+        &self,
+        address: Address,
+        slot: U256,
+        value: U256,
+    ) -> Result<(), DiskDbError> {
+        let key = storage_key(address, slot);
+        if value.is_zero() {
+            self.storage.remove(key)?;
+        } else {
+            self.storage
+                .insert(key, value.to_be_bytes::<32>().as_slice())?;
+        }
+        Ok(())
+    }
+
+    /// Inserts the hash of the block.
+    pub fn insert_block_hash(&self, number: u64, hash: B256) -> Result<(), DiskDbError> {
+        self.block_hashes
+            .insert(number.to_be_bytes(), hash.as_slice())?;
+        Ok(())
+    }
+
+    /// Flushes all pending writes to disk.
+    pub fn flush(&self) -> Result<(), DiskDbError> {
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Commits the changes in a single transaction, see [`DatabaseCommit::commit`].
+    pub fn try_commit(&mut self, changes: HashMap<Address, Account>) -> Result<(), DiskDbError> {
+        let mut accounts = Vec::new();
+        let mut slots = Vec::new();
+        let mut contracts = Vec::new();
+        let mut cleared_slots = Vec::new();
+        for (address, mut account) in changes {
+            if !account.is_touched() {
+                continue;
+            }
+            if account.is_selfdestructed() || account.is_created() {
+                for key in self.storage.scan_prefix(address).keys() {
+                    cleared_slots.push(key?);
+                }
+            }
+            if account.is_selfdestructed() {
+                accounts.push((address, None));
+                continue;
+            }
+            if let Some(code) = prepare_code(&mut account.info) {
+                contracts.push((account.info.code_hash, code));
+            }
+            accounts.push((address, Some(encode_account(&account.info))));
+            slots.extend(
+                account
+                    .storage
+                    .into_iter()
+                    .map(|(slot, value)| (storage_key(address, slot), value.present_value())),
+            );
+        }
+
+        (&self.accounts, &self.storage, &self.contracts).transaction(
+            |(tx_accounts, tx_storage, tx_contracts)| {
+                for key in &cleared_slots {
+                    tx_storage.remove(key)?;
+                }
+                for (address, account) in &accounts {
+                    match account {
+                        Some(account) => tx_accounts.insert(address.as_slice(), account.as_slice()),
+                        None => tx_accounts.remove(address.as_slice()),
+                    }?;
+                }
+                for (key, value) in &slots {
+                    if value.is_zero() {
+                        tx_storage.remove(key.as_slice())?;
+                    } else {
+                        tx_storage.insert(key.as_slice(), value.to_be_bytes::<32>().as_slice())?;
+                    }
+                }
+                for (code_hash, code) in &contracts {
+                    tx_contracts.insert(code_hash.as_slice(), code.as_slice())?;
+                }
+                Ok::<_, ConflictableTransactionError<DiskDbError>>(())
+            },
+        )?;
+        self.flush()
+    }
+}
+
+/// Sets the code hash of the account and returns the code that needs to be stored.
+fn prepare_code(info: &mut AccountInfo) -> Option<Vec<u8>> {
+    let code = info.code.as_ref().filter(|code| !code.is_empty());
+    if let Some(code) = code {
+        if info.code_hash == KECCAK_EMPTY {
+            info.code_hash = code.hash_slow();
+        }
+    }
+    if info.code_hash.is_zero() {
+        info.code_hash = KECCAK_EMPTY;
+    }
+    code.map(|code| code.original_byte_slice().to_vec())
+}
+
+fn storage_key(address: Address, slot: U256) -> [u8; 52] {
+    let mut key = [0; 52];
+    key[..20].copy_from_slice(address.as_slice());
+    key[20..].copy_from_slice(&slot.to_be_bytes::<32>());
+    key
+}
+
+fn encode_account(info: &AccountInfo) -> [u8; 72] {
+    let mut value = [0; 72];
+    value[..32].copy_from_slice(&info.balance.to_be_bytes::<32>());
+    value[32..40].copy_from_slice(&info.nonce.to_be_bytes());
+    value[40..].copy_from_slice(info.code_hash.as_slice());
+    value
+}
+
+fn decode_account(value: &[u8]) -> Result<AccountInfo, DiskDbError> {
+    let value: &[u8; 72] = value.try_into().map_err(|_| DiskDbError::Corrupted)?;
+    Ok(AccountInfo {
+        balance: U256::from_be_slice(&value[..32]),
+        nonce: u64::from_be_bytes(value[32..40].try_into().unwrap()),
+        code_hash: B256::from_slice(&value[40..]),
+        code: None,
+    })
+}
+
+fn decode_word(value: &[u8]) -> Result<[u8; 32], DiskDbError> {
+    value.try_into().map_err(|_| DiskDbError::Corrupted)
+}
+
+impl DatabaseRef for DiskDb {
+    type Error = DiskDbError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.accounts
+            .get(address)?
+            .map(|value| decode_account(&value))
+            .transpose()
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Ok(self
+            .contracts
+            .get(code_hash)?
+            .map(|code| Bytecode::new_raw(code.to_vec().into()))
+            .unwrap_or_default())
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage
+            .get(storage_key(address, index))?
+            .map_or(Ok(U256::ZERO), |value| {
+                decode_word(&value).map(U256::from_be_bytes)
+            })
+    }
+
+    /// Returns zero for blocks whose hash was not inserted.
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hashes
+            .get(number.to_be_bytes())?
+            .map_or(Ok(B256::ZERO), |value| decode_word(&value).map(B256::from))
+    }
+}
+
+impl Database for DiskDb {
+    type Error = DiskDbError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+impl DatabaseCommit for DiskDb {
+    /// # Panics
+    ///
+    /// Panics if the store fails, use [`DiskDb::try_commit`] to handle the error.
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.try_commit(changes)
+            .expect("failed to commit to disk database");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::opcode::{PUSH1, STOP};
+    use primitives::Bytes;
+    use state::{AccountStatus, EvmStorageSlot};
+
+    #[test]
+    fn committed_state_persists_after_reopen() {
+        let path = std::env::temp_dir().join(format!("revm-disk-db-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        let address = Address::with_last_byte(0xaa);
+        let destroyed = Address::with_last_byte(0xbb);
+        let code = Bytecode::new_raw(Bytes::from_static(&[PUSH1, 0x01, STOP]));
+
+        {
+            let mut db = DiskDb::open(&path).unwrap();
+            db.insert_block_hash(7, B256::with_last_byte(7)).unwrap();
+            db.insert_account_info(destroyed, AccountInfo::from_balance(U256::from(1)))
+                .unwrap();
+            db.insert_account_storage(destroyed, U256::from(1), U256::from(1))
+                .unwrap();
+
+            let mut account = Account::from(AccountInfo {
+                balance: U256::from(100),
+                nonce: 2,
+                code: Some(code.clone()),
+                ..Default::default()
+            });
+            account.status = AccountStatus::Touched;
+            account.storage.insert(
+                U256::from(5),
+                EvmStorageSlot::new_changed(U256::ZERO, U256::from(42)),
+            );
+            let mut selfdestructed = Account::from(AccountInfo::default());
+            selfdestructed.status = AccountStatus::Touched | AccountStatus::SelfDestructed;
+            db.commit(
+                [(address, account), (destroyed, selfdestructed)]
+                    .into_iter()
+                    .collect(),
+            );
+        }
+
+        let mut db = DiskDb::open(&path).unwrap();
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(100));
+        assert_eq!(info.nonce, 2);
+        assert_eq!(info.code_hash, code.hash_slow());
+        assert_eq!(db.code_by_hash(info.code_hash).unwrap(), code);
+        assert_eq!(db.storage(address, U256::from(5)).unwrap(), U256::from(42));
+        assert_eq!(db.storage(address, U256::from(6)).unwrap(), U256::ZERO);
+        assert_eq!(db.block_hash(7).unwrap(), B256::with_last_byte(7));
+        assert_eq!(db.basic(destroyed).unwrap(), None);
+        assert_eq!(db.storage(destroyed, U256::from(1)).unwrap(), U256::ZERO);
+
+        drop(db);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -116,6 +116,35 @@ impl<ExtDB> CacheDB<ExtDB> {
     }
 }
 
+#[cfg(any(test, feature = "test-utils"))]
+impl<ExtDB> CacheDB<ExtDB> {
+    /// Inserts `n` accounts funded with `balance` and returns their addresses in order.
+    ///
+    /// Address `i` is the last 20 bytes of `keccak256(i)`, with `i` encoded as a big-endian 256-bit
+    /// word, so the same addresses are returned on every run. Addresses that are already present
+    /// in the cache are skipped, calling this multiple times returns distinct accounts.
+    pub fn create_funded_accounts(&mut self, n: usize, balance: U256) -> Vec<Address> {
+        let mut addresses = Vec::with_capacity(n);
+        let mut counter = U256::ZERO;
+        while addresses.len() < n {
+            let address = Address::from_word(primitives::keccak256(counter.to_be_bytes::<32>()));
+            counter += U256::from(1);
+            if self.accounts.contains_key(&address) {
+                continue;
+            }
+            self.insert_account_info(
+                address,
+                AccountInfo {
+                    balance,
+                    ..Default::default()
+                },
+            );
+            addresses.push(address);
+        }
+        addresses
+    }
+}
+
 impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
     /// Returns the account for the given address.
     ///
@@ -124,14 +153,10 @@ impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
         let db = &self.db;
         match self.accounts.entry(address) {
             Entry::Occupied(entry) => Ok(entry.into_mut()),
-            Entry::Vacant(entry) => Ok(entry.insert(
-                db.basic_ref(address)?
-                    .map(|info| DbAccount {
-                        info,
-                        ..Default::default()
-                    })
-                    .unwrap_or_else(DbAccount::new_not_existing),
-            )),
+            Entry::Vacant(entry) => {
+                let info = db.basic_ref(address)?;
+                Ok(entry.insert(info.into()))
+            }
         }
     }
 
@@ -204,25 +229,45 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
     fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
         let basic = match self.accounts.entry(address) {
             Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(
-                self.db
-                    .basic_ref(address)?
-                    .map(|info| DbAccount {
-                        info,
-                        ..Default::default()
-                    })
-                    .unwrap_or_else(DbAccount::new_not_existing),
-            ),
+            Entry::Vacant(entry) => {
+                let info = self.db.basic_ref(address)?;
+                entry.insert(info.into())
+            }
         };
         Ok(basic.info())
     }
 
+    /// Fetches the accounts missing from the cache with a single [`DatabaseRef::basic_batch_ref`].
+    fn basic_batch(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        let mut missing: Vec<Address> = addresses
+            .iter()
+            .filter(|address| !self.accounts.contains_key(*address))
+            .copied()
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        if !missing.is_empty() {
+            let infos = self.db.basic_batch_ref(&missing)?;
+            for (address, info) in missing.into_iter().zip(infos) {
+                self.accounts.insert(address, info.into());
+            }
+        }
+        Ok(addresses
+            .iter()
+            .map(|address| self.accounts[address].info())
+            .collect())
+    }
+
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
         match self.contracts.entry(code_hash) {
             Entry::Occupied(entry) => Ok(entry.get().clone()),
             Entry::Vacant(entry) => {
                 // If you return code bytes when basic fn is called this function is not needed.
-                Ok(entry.insert(self.db.code_by_hash_ref(code_hash)?).clone())
+                let code = self.db.code_by_hash_ref(code_hash)?;
+                Ok(entry.insert(code).clone())
             }
         }
     }
@@ -267,6 +312,43 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
         }
     }
 
+    /// Fetches the slots missing from the cache with a single [`DatabaseRef::storage_batch_ref`].
+    fn storage_batch(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        let account = match self.accounts.entry(address) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let info = self.db.basic_ref(address)?;
+                entry.insert(info.into())
+            }
+        };
+        if !matches!(
+            account.account_state,
+            AccountState::StorageCleared | AccountState::NotExisting
+        ) {
+            let mut missing: Vec<U256> = indices
+                .iter()
+                .filter(|index| !account.storage.contains_key(*index))
+                .copied()
+                .collect();
+            missing.sort_unstable();
+            missing.dedup();
+            if !missing.is_empty() {
+                let values = self.db.storage_batch_ref(address, &missing)?;
+                for (index, value) in missing.into_iter().zip(values) {
+                    account.storage.insert(index, value);
+                }
+            }
+        }
+        Ok(indices
+            .iter()
+            .map(|index| account.storage.get(index).copied().unwrap_or_default())
+            .collect())
+    }
+
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
         match self.block_hashes.entry(U256::from(number)) {
             Entry::Occupied(entry) => Ok(*entry.get()),
@@ -444,9 +526,86 @@ impl Database for BenchmarkDB {
 #[cfg(test)]
 mod tests {
     use super::{CacheDB, EmptyDB};
-    use database_interface::Database;
-    use primitives::{Address, HashMap, U256};
-    use state::AccountInfo;
+    use core::{cell::Cell, convert::Infallible};
+    use database_interface::{Database, DatabaseRef};
+    use primitives::{Address, HashMap, HashSet, B256, U256};
+    use state::{AccountInfo, Bytecode};
+    use std::vec::Vec;
+
+    /// Backend counting account requests.
+    #[derive(Default)]
+    struct CountingDb {
+        basic_calls: Cell<usize>,
+        batch_calls: Cell<usize>,
+    }
+
+    impl DatabaseRef for CountingDb {
+        type Error = Infallible;
+
+        fn basic_ref(&self, _address: Address) -> Result<Option<AccountInfo>, Infallible> {
+            self.basic_calls.set(self.basic_calls.get() + 1);
+            Ok(Some(AccountInfo::default()))
+        }
+
+        fn basic_batch_ref(
+            &self,
+            addresses: &[Address],
+        ) -> Result<Vec<Option<AccountInfo>>, Infallible> {
+            self.batch_calls.set(self.batch_calls.get() + 1);
+            Ok(addresses
+                .iter()
+                .map(|_| Some(AccountInfo::default()))
+                .collect())
+        }
+
+        fn code_by_hash_ref(&self, _code_hash: B256) -> Result<Bytecode, Infallible> {
+            Ok(Bytecode::default())
+        }
+
+        fn storage_ref(&self, _address: Address, _index: U256) -> Result<U256, Infallible> {
+            Ok(U256::ZERO)
+        }
+
+        fn block_hash_ref(&self, _number: u64) -> Result<B256, Infallible> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    #[test]
+    fn basic_batch_warms_the_cache() {
+        let mut db = CacheDB::new(CountingDb::default());
+        let addresses: Vec<_> = (1..=3).map(Address::with_last_byte).collect();
+
+        db.basic(addresses[0]).unwrap();
+        db.basic_batch(&addresses).unwrap();
+        assert_eq!(db.db.basic_calls.get(), 1);
+        assert_eq!(db.db.batch_calls.get(), 1);
+
+        for address in &addresses {
+            db.basic(*address).unwrap();
+        }
+        db.basic_batch(&addresses).unwrap();
+        assert_eq!(db.db.basic_calls.get(), 1);
+        assert_eq!(db.db.batch_calls.get(), 1);
+    }
+
+    #[test]
+    fn funded_accounts_are_deterministic() {
+        let balance = U256::from(1_000);
+        let mut db = CacheDB::new(EmptyDB::default());
+        let accounts = db.create_funded_accounts(3, balance);
+        assert_eq!(
+            accounts,
+            CacheDB::new(EmptyDB::default()).create_funded_accounts(3, balance)
+        );
+        assert_eq!(accounts.iter().collect::<HashSet<_>>().len(), 3);
+        for address in &accounts {
+            assert_eq!(db.basic(*address).unwrap().unwrap().balance, balance);
+        }
+
+        let more = db.create_funded_accounts(2, balance);
+        assert!(more.iter().all(|address| !accounts.contains(address)));
+    }
 
     #[test]
     fn test_insert_account_storage() {
@@ -2,16 +2,26 @@
 
 #[cfg(feature = "alloydb")]
 mod alloydb;
+#[cfg(feature = "disk")]
+mod disk_db;
 
 pub mod in_memory_db;
+#[cfg(all(any(test, feature = "test-utils"), feature = "std"))]
+mod slow_db;
 pub mod states;
+mod witness;
 
 #[cfg(feature = "alloydb")]
-pub use alloydb::{AlloyDB, BlockId};
+pub use alloydb::{AlloyDB, BlockId, DBTransportError};
+#[cfg(feature = "disk")]
+pub use disk_db::{DiskDb, DiskDbError};
 
 pub use in_memory_db::*;
+#[cfg(all(any(test, feature = "test-utils"), feature = "std"))]
+pub use slow_db::SlowDb;
 pub use states::{
     AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox,
     OriginalValuesKnown, PlainAccount, RevertToSlot, State, StateBuilder, StateDBBox,
     StorageWithOriginalValues, TransitionAccount, TransitionState,
 };
+pub use witness::{RecordingDb, StateWitness, WitnessError};
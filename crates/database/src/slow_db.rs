@@ -0,0 +1,166 @@
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use database_interface::{Database, DatabaseCommit, DatabaseRef};
+use primitives::{Address, HashMap, B256, U256};
+use state::{Account, AccountInfo, Bytecode};
+
+/// Database wrapper that sleeps for a fixed latency on every read, to simulate a remote
+/// database in tests and benchmarks.
+///
+/// Every call is counted as one read, a batched account or storage read is a single read. The number of
+/// reads and the total simulated latency can be used to measure the effect of caching and
+/// batching in front of it.
+#[derive(Debug, Default)]
+pub struct SlowDb<D> {
+    db: D,
+    latency: Duration,
+    reads: AtomicU64,
+    latency_nanos: AtomicU64,
+}
+
+impl<D> SlowDb<D> {
+    /// Wraps the database, every read sleeps for `latency`.
+    pub fn new(db: D, latency: Duration) -> Self {
+        Self {
+            db,
+            latency,
+            reads: AtomicU64::new(0),
+            latency_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of reads so far.
+    pub fn reads(&self) -> u64 {
+        self.reads.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total latency simulated so far.
+    pub fn total_latency(&self) -> Duration {
+        Duration::from_nanos(self.latency_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns the wrapped database.
+    pub fn into_inner(self) -> D {
+        self.db
+    }
+
+    fn read(&self) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        self.latency_nanos
+            .fetch_add(self.latency.as_nanos() as u64, Ordering::Relaxed);
+        std::thread::sleep(self.latency);
+    }
+}
+
+impl<D: DatabaseRef> DatabaseRef for SlowDb<D> {
+    type Error = D::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.read();
+        self.db.basic_ref(address)
+    }
+
+    fn basic_batch_ref(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.read();
+        self.db.basic_batch_ref(addresses)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.read();
+        self.db.code_by_hash_ref(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.read();
+        self.db.storage_ref(address, index)
+    }
+
+    fn storage_batch_ref(
+        &self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.read();
+        self.db.storage_batch_ref(address, indices)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.read();
+        self.db.block_hash_ref(number)
+    }
+}
+
+impl<D: Database> Database for SlowDb<D> {
+    type Error = D::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.read();
+        self.db.basic(address)
+    }
+
+    fn basic_batch(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        self.read();
+        self.db.basic_batch(addresses)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.read();
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.read();
+        self.db.storage(address, index)
+    }
+
+    fn storage_batch(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.read();
+        self.db.storage_batch(address, indices)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.read();
+        self.db.block_hash(number)
+    }
+}
+
+impl<D: DatabaseCommit> DatabaseCommit for SlowDb<D> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CacheDB;
+    use bytecode::opcode::STOP;
+    use primitives::Bytes;
+
+    #[test]
+    fn cached_code_reads_incur_latency_once() {
+        let code = Bytecode::new_raw(Bytes::from_static(&[STOP]));
+        let mut remote = CacheDB::new(database_interface::EmptyDB::default());
+        remote.insert_contract(&mut AccountInfo::from_bytecode(code.clone()));
+
+        let mut db = CacheDB::new(SlowDb::new(remote, Duration::from_millis(1)));
+        for _ in 0..10 {
+            assert_eq!(db.code_by_hash(code.hash_slow()).unwrap(), code);
+        }
+
+        assert_eq!(db.db.reads(), 1);
+        assert_eq!(db.db.total_latency(), Duration::from_millis(1));
+    }
+}
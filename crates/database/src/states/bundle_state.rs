@@ -861,6 +861,96 @@ impl BundleState {
     }
 }
 
+#[cfg(feature = "std")]
+impl BundleState {
+    /// Renders the bundle state in a human readable form.
+    ///
+    /// Accounts are sorted by address and labeled with their status:
+    /// - `untouched`: loaded but not changed.
+    /// - `created`: account did not exist before.
+    /// - `changed`: account info or storage changed.
+    /// - `destroyed`: account was selfdestructed and does not exist anymore.
+    /// - `wiped`: account was selfdestructed and created again, its previous storage is wiped.
+    ///
+    /// Every changed info field is printed as `old -> new`, followed by changed storage slots.
+    /// Slots of wiped and destroyed accounts are all printed as they replace the wiped storage.
+    pub fn pretty_print(&self) -> String {
+        use std::{fmt::Write, string::String};
+
+        fn field<T: PartialEq + core::fmt::Display>(
+            out: &mut String,
+            name: &str,
+            old: Option<T>,
+            new: Option<T>,
+        ) {
+            if old == new {
+                return;
+            }
+            let show = |value: Option<T>| value.map_or("<none>".into(), |v| v.to_string());
+            let _ = writeln!(out, "  {name:<10} {} -> {}", show(old), show(new));
+        }
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "BundleState: {} accounts, {} contracts",
+            self.state.len(),
+            self.contracts.len()
+        );
+
+        let accounts: BTreeMap<_, _> = self.state.iter().collect();
+        for (address, account) in accounts {
+            let wiped = account.status.was_destroyed();
+            let label = if account.status.is_not_modified() {
+                "untouched"
+            } else if wiped && account.info.is_none() {
+                "destroyed"
+            } else if wiped {
+                "wiped"
+            } else if account.original_info.is_none() {
+                "created"
+            } else {
+                "changed"
+            };
+            let _ = writeln!(out, "{address} {label}");
+
+            let (old, new) = (account.original_info.as_ref(), account.info.as_ref());
+            field(
+                &mut out,
+                "nonce",
+                old.map(|i| i.nonce),
+                new.map(|i| i.nonce),
+            );
+            field(
+                &mut out,
+                "balance",
+                old.map(|i| i.balance),
+                new.map(|i| i.balance),
+            );
+            field(
+                &mut out,
+                "code_hash",
+                old.map(|i| i.code_hash),
+                new.map(|i| i.code_hash),
+            );
+
+            let slots: BTreeMap<_, _> = account
+                .storage
+                .iter()
+                .filter(|(_, slot)| wiped || slot.is_changed())
+                .collect();
+            for (key, slot) in slots {
+                let _ = writeln!(
+                    out,
+                    "  slot {key:#x}: {:#x} -> {:#x}",
+                    slot.previous_or_original_value, slot.present_value
+                );
+            }
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1333,4 +1423,93 @@ mod tests {
             .insert(B256::default(), Bytecode::default());
         assert!(builder.get_contracts_mut().contains_key(&B256::default()));
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pretty_print_distinguishes_wiped_accounts() {
+        let info = |nonce, balance| AccountInfo {
+            nonce,
+            balance: U256::from(balance),
+            code_hash: KECCAK_EMPTY,
+            code: None,
+        };
+        let slot = |old: u64, new: u64| StorageSlot::new_changed(U256::from(old), U256::from(new));
+        let account = |original, present, storage: Vec<(u64, StorageSlot)>, status| {
+            BundleAccount::new(
+                original,
+                present,
+                storage
+                    .into_iter()
+                    .map(|(key, slot)| (U256::from(key), slot))
+                    .collect(),
+                status,
+            )
+        };
+
+        let bundle = BundleState {
+            state: HashMap::from_iter([
+                (
+                    Address::with_last_byte(1),
+                    account(
+                        Some(info(1, 10)),
+                        Some(info(2, 5)),
+                        vec![(1, slot(0, 7)), (2, slot(3, 3))],
+                        AccountStatus::Changed,
+                    ),
+                ),
+                (
+                    Address::with_last_byte(2),
+                    account(
+                        Some(info(1, 10)),
+                        Some(info(1, 10)),
+                        vec![(1, slot(4, 0))],
+                        AccountStatus::DestroyedChanged,
+                    ),
+                ),
+                (
+                    Address::with_last_byte(3),
+                    account(
+                        Some(info(1, 10)),
+                        Some(info(1, 10)),
+                        vec![],
+                        AccountStatus::Loaded,
+                    ),
+                ),
+                (
+                    Address::with_last_byte(4),
+                    account(Some(info(1, 10)), None, vec![], AccountStatus::Destroyed),
+                ),
+                (
+                    Address::with_last_byte(5),
+                    account(
+                        None,
+                        Some(info(0, 1)),
+                        vec![],
+                        AccountStatus::InMemoryChange,
+                    ),
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let expected = "\
+BundleState: 5 accounts, 0 contracts
+0x0000000000000000000000000000000000000001 changed
+  nonce      1 -> 2
+  balance    10 -> 5
+  slot 0x1: 0x0 -> 0x7
+0x0000000000000000000000000000000000000002 wiped
+  slot 0x1: 0x4 -> 0x0
+0x0000000000000000000000000000000000000003 untouched
+0x0000000000000000000000000000000000000004 destroyed
+  nonce      1 -> <none>
+  balance    10 -> <none>
+  code_hash  0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470 -> <none>
+0x0000000000000000000000000000000000000005 created
+  nonce      <none> -> 0
+  balance    <none> -> 1
+  code_hash  <none> -> 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+";
+        assert_eq!(bundle.pretty_print(), expected);
+    }
 }
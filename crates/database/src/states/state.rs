@@ -83,7 +83,8 @@ impl<DB: Database> State<DB> {
 
     /// Iterates over received balances and increment all account balances.
     ///
-    /// **Note**: If account is not found inside cache state it will be loaded from database.
+    /// **Note**: Accounts not found inside cache state are loaded from database with a single
+    /// [`Database::basic_batch`].
     ///
     /// Update will create transitions for all accounts that are updated.
     ///
@@ -95,12 +96,15 @@ impl<DB: Database> State<DB> {
         &mut self,
         balances: impl IntoIterator<Item = (Address, u128)>,
     ) -> Result<(), DB::Error> {
+        let balances: Vec<_> = balances
+            .into_iter()
+            .filter(|(_, balance)| *balance != 0)
+            .collect();
+        self.load_cache_accounts(balances.iter().map(|(address, _)| *address))?;
+
         // Make transition and update cache state
-        let mut transitions = Vec::new();
+        let mut transitions = Vec::with_capacity(balances.len());
         for (address, balance) in balances {
-            if balance == 0 {
-                continue;
-            }
             let original_account = self.load_cache_account(address)?;
             transitions.push((
                 address,
@@ -199,19 +203,50 @@ impl<DB: Database> State<DB> {
                 }
                 // If not found in bundle, load it from database
                 let info = self.database.basic(address)?;
-                let account = match info {
-                    None => CacheAccount::new_loaded_not_existing(),
-                    Some(acc) if acc.is_empty() => {
-                        CacheAccount::new_loaded_empty_eip161(HashMap::default())
-                    }
-                    Some(acc) => CacheAccount::new_loaded(acc, HashMap::default()),
-                };
-                Ok(entry.insert(account))
+                Ok(entry.insert(Self::cache_account(info)))
             }
             hash_map::Entry::Occupied(entry) => Ok(entry.into_mut()),
         }
     }
 
+    /// Loads the accounts that are neither cached nor in the preloaded bundle with a single
+    /// [`Database::basic_batch`].
+    fn load_cache_accounts(
+        &mut self,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<(), DB::Error> {
+        let mut missing: Vec<Address> = addresses
+            .into_iter()
+            .filter(|address| {
+                let in_bundle =
+                    self.use_preloaded_bundle && self.bundle_state.account(address).is_some();
+                !(in_bundle || self.cache.accounts.contains_key(address))
+            })
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let infos = self.database.basic_batch(&missing)?;
+        for (address, info) in missing.into_iter().zip(infos) {
+            self.cache
+                .accounts
+                .insert(address, Self::cache_account(info));
+        }
+        Ok(())
+    }
+
+    fn cache_account(info: Option<AccountInfo>) -> CacheAccount {
+        match info {
+            None => CacheAccount::new_loaded_not_existing(),
+            Some(acc) if acc.is_empty() => {
+                CacheAccount::new_loaded_empty_eip161(HashMap::default())
+            }
+            Some(acc) => CacheAccount::new_loaded(acc, HashMap::default()),
+        }
+    }
+
     // TODO : Make cache aware of transitions dropping by having global transition counter.
     /// Takess the [`BundleState`] changeset from the [`State`], replacing it
     /// with an empty one.
@@ -352,6 +387,37 @@ mod tests {
     /// This is important because the state transitions from different transactions in the same block may see
     /// different states of the same account as the old value, but the revert should reflect the
     /// state of the account before the block.
+    #[test]
+    #[cfg(feature = "std")]
+    fn increment_balances_loads_accounts_in_one_batch() {
+        use crate::{CacheDB, SlowDb};
+        use core::time::Duration;
+
+        let withdrawals: Vec<_> = (1..=100u8)
+            .map(|i| (Address::with_last_byte(i), i as u128 * 1000))
+            .collect();
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, _) in withdrawals.iter().step_by(2) {
+            db.insert_account_info(*address, AccountInfo::from_balance(U256::from(7)));
+        }
+
+        let mut state = State::builder()
+            .with_database(SlowDb::new(db, Duration::ZERO))
+            .with_bundle_update()
+            .build();
+        state.increment_balances(withdrawals.clone()).unwrap();
+        assert_eq!(state.database.reads(), 1);
+
+        for (index, (address, amount)) in withdrawals.into_iter().enumerate() {
+            let existing = if index % 2 == 0 { 7 } else { 0 };
+            assert_eq!(
+                state.basic(address).unwrap().unwrap().balance,
+                U256::from(existing + amount)
+            );
+        }
+        assert_eq!(state.database.reads(), 1);
+    }
+
     #[test]
     fn reverts_preserve_old_values() {
         let mut state = State::builder().with_bundle_update().build();
@@ -0,0 +1,392 @@
+use crate::{CacheDB, CacheState};
+use core::{
+    cell::{Ref, RefCell},
+    error::Error,
+    fmt,
+};
+use database_interface::{DBErrorMarker, Database, DatabaseCommit, DatabaseRef};
+use primitives::{Address, HashMap, B256, U256};
+use state::{Account, AccountInfo, Bytecode};
+
+/// Pre-state read by a transaction, recorded by [`RecordingDb`].
+///
+/// Contains every account, storage slot, bytecode and block hash the execution read from the
+/// database, with the values it read. It implements [`DatabaseRef`], so the transaction can be
+/// re-executed from the witness alone. Reads of anything that was not recorded fail with a
+/// [`WitnessError`] instead of returning a default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateWitness {
+    /// Accounts without their code, `None` if the account does not exist.
+    pub accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Storage slots by account.
+    pub storage: HashMap<Address, HashMap<U256, U256>>,
+    /// Bytecode by code hash.
+    pub code: HashMap<B256, Bytecode>,
+    /// Block hashes by block number.
+    pub block_hashes: HashMap<u64, B256>,
+}
+
+impl StateWitness {
+    /// Returns the recorded accounts, storage and code as a [`CacheState`].
+    ///
+    /// Storage of accounts that do not exist is not included. Block hashes are not part of the
+    /// cache state and are dropped.
+    pub fn to_cache_state(&self) -> CacheState {
+        let mut state = CacheState::default();
+        for (address, info) in &self.accounts {
+            match info {
+                Some(info) => state.insert_account_with_storage(
+                    *address,
+                    info.clone(),
+                    self.storage.get(address).cloned().unwrap_or_default(),
+                ),
+                None => state.insert_not_existing(*address),
+            }
+        }
+        state.contracts.extend(
+            self.code
+                .iter()
+                .map(|(code_hash, code)| (*code_hash, code.clone())),
+        );
+        state
+    }
+
+    fn record_account(&mut self, address: Address, info: &Option<AccountInfo>) {
+        if self.accounts.contains_key(&address) {
+            return;
+        }
+        let info = info.clone().map(|mut info| {
+            // Code is kept once by hash, execution loads it with `code_by_hash`.
+            if let Some(code) = info.code.take() {
+                self.code.insert(info.code_hash, code);
+            }
+            info
+        });
+        self.accounts.insert(address, info);
+    }
+
+    fn record_code(&mut self, code_hash: B256, code: &Bytecode) {
+        self.code.entry(code_hash).or_insert_with(|| code.clone());
+    }
+
+    fn record_storage(&mut self, address: Address, index: U256, value: U256) {
+        self.storage
+            .entry(address)
+            .or_default()
+            .entry(index)
+            .or_insert(value);
+    }
+
+    fn record_block_hash(&mut self, number: u64, hash: B256) {
+        self.block_hashes.entry(number).or_insert(hash);
+    }
+}
+
+/// Error of a read that is not covered by the [`StateWitness`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WitnessError {
+    MissingAccount(Address),
+    MissingStorage(Address, U256),
+    MissingCode(B256),
+    MissingBlockHash(u64),
+}
+
+impl DBErrorMarker for WitnessError {}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAccount(address) => write!(f, "account {address} is not in the witness"),
+            Self::MissingStorage(address, index) => {
+                write!(f, "storage slot {index} of {address} is not in the witness")
+            }
+            Self::MissingCode(code_hash) => write!(f, "code {code_hash} is not in the witness"),
+            Self::MissingBlockHash(number) => {
+                write!(f, "hash of block {number} is not in the witness")
+            }
+        }
+    }
+}
+
+impl Error for WitnessError {}
+
+impl DatabaseRef for StateWitness {
+    type Error = WitnessError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.accounts
+            .get(&address)
+            .cloned()
+            .ok_or(WitnessError::MissingAccount(address))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code
+            .get(&code_hash)
+            .cloned()
+            .ok_or(WitnessError::MissingCode(code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage
+            .get(&address)
+            .and_then(|storage| storage.get(&index))
+            .copied()
+            .ok_or(WitnessError::MissingStorage(address, index))
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hashes
+            .get(&number)
+            .copied()
+            .ok_or(WitnessError::MissingBlockHash(number))
+    }
+}
+
+/// Database wrapper that records every read into a [`StateWitness`].
+///
+/// Only the first read of each item is recorded, as it is the value of the pre-state. Writes
+/// are forwarded to the wrapped database and not recorded, so the witness of a block is
+/// recorded by executing its transactions on top of a cache like [`CacheDB`](crate::CacheDB)
+/// that is wrapping this database. Reads through [`DatabaseRef`] are recorded too.
+#[derive(Debug, Default)]
+pub struct RecordingDb<D> {
+    db: D,
+    witness: RefCell<StateWitness>,
+}
+
+impl<D> RecordingDb<D> {
+    /// Wraps the database with an empty witness.
+    pub fn new(db: D) -> Self {
+        Self {
+            db,
+            witness: RefCell::default(),
+        }
+    }
+
+    /// Returns the reads recorded so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while a read through [`DatabaseRef`] is being recorded.
+    pub fn witness(&self) -> Ref<'_, StateWitness> {
+        self.witness.borrow()
+    }
+
+    /// Consumes the wrapper and returns the recorded witness.
+    pub fn into_witness(self) -> StateWitness {
+        self.witness.into_inner()
+    }
+
+    /// Returns the wrapped database.
+    pub fn into_inner(self) -> D {
+        self.db
+    }
+}
+
+impl<D> CacheDB<RecordingDb<D>> {
+    /// Returns the state that was fetched from the underlying database, with the values as they
+    /// were fetched.
+    ///
+    /// Accounts and storage inserted into the cache are not included, so the returned state is
+    /// the minimal pre-state needed to replay the executed transactions.
+    pub fn accessed_state(&self) -> CacheState {
+        self.db.witness().to_cache_state()
+    }
+}
+
+impl<D: Database> Database for RecordingDb<D> {
+    type Error = D::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        self.witness.get_mut().record_account(address, &info);
+        Ok(info)
+    }
+
+    fn basic_batch(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<Option<AccountInfo>>, Self::Error> {
+        let infos = self.db.basic_batch(addresses)?;
+        let witness = self.witness.get_mut();
+        for (address, info) in addresses.iter().zip(&infos) {
+            witness.record_account(*address, info);
+        }
+        Ok(infos)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.witness.get_mut().record_code(code_hash, &code);
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.witness.get_mut().record_storage(address, index, value);
+        Ok(value)
+    }
+
+    fn storage_batch(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        let values = self.db.storage_batch(address, indices)?;
+        let witness = self.witness.get_mut();
+        for (index, value) in indices.iter().zip(&values) {
+            witness.record_storage(address, *index, *value);
+        }
+        Ok(values)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.witness.get_mut().record_block_hash(number, hash);
+        Ok(hash)
+    }
+}
+
+impl<D: DatabaseRef> DatabaseRef for RecordingDb<D> {
+    type Error = D::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic_ref(address)?;
+        self.witness.borrow_mut().record_account(address, &info);
+        Ok(info)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash_ref(code_hash)?;
+        self.witness.borrow_mut().record_code(code_hash, &code);
+        Ok(code)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage_ref(address, index)?;
+        self.witness
+            .borrow_mut()
+            .record_storage(address, index, value);
+        Ok(value)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash_ref(number)?;
+        self.witness.borrow_mut().record_block_hash(number, hash);
+        Ok(hash)
+    }
+}
+
+impl<D: DatabaseCommit> DatabaseCommit for RecordingDb<D> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CacheDB;
+    use database_interface::{EmptyDB, WrapDatabaseRef};
+
+    #[test]
+    fn cache_records_first_reads_through_database_ref() {
+        let address = Address::with_last_byte(1);
+        let mut pre_state = CacheDB::new(EmptyDB::default());
+        pre_state.insert_account_info(address, AccountInfo::from_balance(U256::from(10)));
+        pre_state
+            .insert_account_storage(address, U256::ZERO, U256::from(1))
+            .unwrap();
+
+        let mut cache = CacheDB::new(RecordingDb::new(pre_state));
+        assert_eq!(cache.storage(address, U256::ZERO).unwrap(), U256::from(1));
+        cache
+            .insert_account_storage(address, U256::ZERO, U256::from(2))
+            .unwrap();
+        assert_eq!(cache.storage(address, U256::ZERO).unwrap(), U256::from(2));
+
+        let witness = cache.db.into_witness();
+        assert_eq!(
+            witness.accounts[&address].as_ref().unwrap().balance,
+            U256::from(10)
+        );
+        assert_eq!(witness.storage[&address][&U256::ZERO], U256::from(1));
+    }
+
+    #[test]
+    fn accessed_state_contains_fetched_slot() {
+        let caller = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(0xc0);
+        let mut backend = CacheDB::new(EmptyDB::default());
+        backend.insert_account_info(contract, AccountInfo::default());
+        for (slot, value) in [(1, 42), (2, 7)] {
+            backend
+                .insert_account_storage(contract, U256::from(slot), U256::from(value))
+                .unwrap();
+        }
+        backend.insert_account_info(caller, AccountInfo::from_balance(U256::from(1000)));
+
+        let mut cache = CacheDB::new(RecordingDb::new(backend));
+        assert_eq!(
+            cache.storage(contract, U256::from(1)).unwrap(),
+            U256::from(42)
+        );
+        let mut info = cache.basic(caller).unwrap().unwrap();
+        info.nonce = 1;
+        cache.insert_account_info(caller, info);
+
+        let accessed = cache.accessed_state();
+        let mut accounts: Vec<_> = accessed.accounts.keys().copied().collect();
+        accounts.sort();
+        assert_eq!(accounts, [caller, contract]);
+        let account = |address| accessed.accounts[&address].account.as_ref().unwrap();
+        assert_eq!(
+            account(contract).storage,
+            [(U256::from(1), U256::from(42))].into_iter().collect()
+        );
+        assert!(account(caller).storage.is_empty());
+        // Values are recorded as fetched, before the cache was modified.
+        assert_eq!(account(caller).info.nonce, 0);
+    }
+
+    #[test]
+    fn witness_replays_reads_without_database() {
+        let address = Address::with_last_byte(1);
+        let code = Bytecode::new_legacy([0x00].into());
+        let mut pre_state = CacheDB::new(EmptyDB::default());
+        pre_state.insert_account_info(address, AccountInfo::from_bytecode(code.clone()));
+        pre_state
+            .insert_account_storage(address, U256::ZERO, U256::from(7))
+            .unwrap();
+
+        let mut recording = RecordingDb::new(pre_state);
+        let info = recording.basic(address).unwrap().unwrap();
+        let storage = recording.storage(address, U256::ZERO).unwrap();
+        let block_hash = recording.block_hash(5).unwrap();
+        let witness = recording.into_witness();
+        // Code is only kept by hash.
+        assert!(witness.accounts[&address].as_ref().unwrap().code.is_none());
+
+        let mut replay = WrapDatabaseRef(witness);
+        assert_eq!(
+            replay.basic(address).unwrap().unwrap().code_hash,
+            info.code_hash
+        );
+        assert_eq!(replay.code_by_hash(info.code_hash).unwrap(), code);
+        assert_eq!(replay.storage(address, U256::ZERO).unwrap(), storage);
+        assert_eq!(replay.block_hash(5).unwrap(), block_hash);
+
+        // Reads outside of the witness fail instead of defaulting.
+        let unknown = Address::with_last_byte(0xcc);
+        assert_eq!(
+            replay.basic(unknown),
+            Err(WitnessError::MissingAccount(unknown))
+        );
+        assert_eq!(
+            replay.storage(address, U256::from(1)),
+            Err(WitnessError::MissingStorage(address, U256::from(1)))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use interpreter::InterpreterResult;
 use primitives::{Address, Bytes};
+use std::vec::Vec;
 
 pub trait PrecompileProvider: Clone {
     type Context;
@@ -17,9 +18,32 @@ pub trait PrecompileProvider: Clone {
         gas_limit: u64,
     ) -> Result<Option<InterpreterResult>, Self::Error>;
 
+    /// Runs the precompile like [`PrecompileProvider::run`], calling `interrupt` during long
+    /// computations and failing the precompile once it returns `true`.
+    ///
+    /// Default implementation ignores `interrupt`, providers override it to check it between
+    /// chunks of expensive precompiles.
+    fn run_interruptible(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+        interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        let _ = interrupt;
+        self.run(context, address, bytes, gas_limit)
+    }
+
     /// Get the warm addresses.
     fn warm_addresses(&self) -> impl Iterator<Item = Address>;
 
     /// Check if the address is a precompile.
     fn contains(&self, address: &Address) -> bool;
+
+    /// Returns addresses of all precompiles that are active under the current configuration.
+    ///
+    /// Custom providers must return their full set, including chain specific precompiles, as
+    /// tooling relies on it, e.g. to exclude precompiles from access lists.
+    fn active_addresses(&self) -> Vec<Address>;
 }
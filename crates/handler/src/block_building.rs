@@ -0,0 +1,60 @@
+use context_interface::Transaction;
+use std::vec::Vec;
+
+/// Selects the transactions to include in a block from transactions paired with their total tip.
+///
+/// Transactions are picked greedily by tip per gas, highest first, while their cumulative gas
+/// limit fits `block_gas_limit`. The gas limit of a transaction is its worst case gas usage.
+/// Transactions that do not fit are skipped, so smaller ones after them can still be picked.
+/// Ties keep the input order.
+///
+/// Selected transactions are returned in the order they were picked. Nonce order of
+/// transactions from the same sender is not taken into account.
+pub fn pack_transactions<TX: Transaction>(
+    mut txs: Vec<(TX, u64)>,
+    block_gas_limit: u64,
+) -> Vec<TX> {
+    // Compare `tip_a / gas_a` with `tip_b / gas_b` without rounding.
+    txs.sort_by(|(a, a_tip), (b, b_tip)| {
+        let a_weighted = *a_tip as u128 * b.gas_limit() as u128;
+        let b_weighted = *b_tip as u128 * a.gas_limit() as u128;
+        b_weighted.cmp(&a_weighted)
+    });
+
+    let mut gas_left = block_gas_limit;
+    txs.into_iter()
+        .filter_map(|(tx, _)| {
+            gas_left = gas_left.checked_sub(tx.gas_limit())?;
+            Some(tx)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use context::TxEnv;
+    use std::vec;
+
+    #[test]
+    fn packing_maximizes_total_tip() {
+        let tx = |nonce, gas_limit| TxEnv {
+            nonce,
+            gas_limit,
+            ..Default::default()
+        };
+        // The largest tip has the lowest tip per gas, the two others together pay more.
+        let txs = vec![
+            (tx(0, 60_000), 600),
+            (tx(1, 50_000), 550),
+            (tx(2, 50_000), 540),
+            (tx(3, 30_000), 30),
+        ];
+
+        let nonces = |packed: Vec<TxEnv>| packed.iter().map(|tx| tx.nonce).collect::<Vec<_>>();
+        assert_eq!(nonces(pack_transactions(txs.clone(), 100_000)), [1, 2]);
+        // The second transaction does not fit, the smaller ones after it still do.
+        assert_eq!(nonces(pack_transactions(txs.clone(), 95_000)), [1, 3]);
+        assert!(nonces(pack_transactions(txs, 20_000)).is_empty());
+    }
+}
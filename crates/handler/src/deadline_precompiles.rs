@@ -0,0 +1,283 @@
+use crate::precompile_provider::into_interpreter_result;
+use context_interface::{Cfg, CfgGetter};
+use handler_interface::PrecompileProvider;
+use interpreter::InterpreterResult;
+use precompile::{PrecompileError, PrecompileErrors};
+use primitives::{Address, Bytes};
+use std::{time::Instant, vec::Vec};
+
+/// [`PrecompileProvider`] wrapper that interrupts precompiles once the
+/// [`Cfg::precompile_deadline`] has passed.
+///
+/// The deadline is checked before and after every call of the wrapped provider. Calls that
+/// start or finish after the deadline fail with [`PrecompileError::Timeout`], the output of a
+/// call that finished late is discarded.
+///
+/// Precompiles always run through the wrapped provider, using
+/// [`PrecompileProvider::run_interruptible`] so that providers can check the deadline during
+/// long computations, e.g. between modexp exponent chunks and BLS12-381 pairs.
+#[derive(Clone, Debug)]
+pub struct DeadlinePrecompiles<P> {
+    pub inner: P,
+    pub deadline: Option<Instant>,
+}
+
+impl<P> PrecompileProvider for DeadlinePrecompiles<P>
+where
+    P: PrecompileProvider,
+    P::Context: CfgGetter,
+    P::Error: From<PrecompileErrors>,
+{
+    type Context = P::Context;
+    type Error = P::Error;
+
+    fn new(context: &mut Self::Context) -> Self {
+        let deadline = context.cfg().precompile_deadline();
+        Self {
+            inner: P::new(context),
+            deadline,
+        }
+    }
+
+    fn run(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        self.run_interruptible(context, address, bytes, gas_limit, &mut || false)
+    }
+
+    fn run_interruptible(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+        interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        let Some(deadline) = self.deadline else {
+            return self
+                .inner
+                .run_interruptible(context, address, bytes, gas_limit, interrupt);
+        };
+        let timeout = || into_interpreter_result(Err(PrecompileError::Timeout.into()), gas_limit);
+        if !self.inner.contains(address) {
+            return Ok(None);
+        }
+        if Instant::now() >= deadline {
+            return timeout().map(Some);
+        }
+
+        let result =
+            self.inner
+                .run_interruptible(context, address, bytes, gas_limit, &mut || {
+                    Instant::now() >= deadline || interrupt()
+                })?;
+        if result.is_some() && Instant::now() >= deadline {
+            return timeout().map(Some);
+        }
+        Ok(result)
+    }
+
+    fn warm_addresses(&self) -> impl Iterator<Item = Address> {
+        self.inner.warm_addresses()
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.inner.contains(address)
+    }
+
+    fn active_addresses(&self) -> Vec<Address> {
+        self.inner.active_addresses()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthPrecompileProvider;
+    use context::Context;
+    use interpreter::InstructionResult;
+    use std::{time::Duration, vec};
+
+    type Provider<CTX> = DeadlinePrecompiles<EthPrecompileProvider<CTX, PrecompileErrors>>;
+
+    /// Provider that takes `delay` to run every precompile.
+    #[derive(Clone)]
+    struct Slow<P> {
+        inner: P,
+        delay: Duration,
+    }
+
+    impl<P: PrecompileProvider> PrecompileProvider for Slow<P> {
+        type Context = P::Context;
+        type Error = P::Error;
+
+        fn new(context: &mut Self::Context) -> Self {
+            Self {
+                inner: P::new(context),
+                delay: Duration::from_millis(20),
+            }
+        }
+
+        fn run(
+            &mut self,
+            context: &mut Self::Context,
+            address: &Address,
+            bytes: &Bytes,
+            gas_limit: u64,
+        ) -> Result<Option<InterpreterResult>, Self::Error> {
+            std::thread::sleep(self.delay);
+            self.inner.run(context, address, bytes, gas_limit)
+        }
+
+        fn warm_addresses(&self) -> impl Iterator<Item = Address> {
+            self.inner.warm_addresses()
+        }
+
+        fn contains(&self, address: &Address) -> bool {
+            self.inner.contains(address)
+        }
+
+        fn active_addresses(&self) -> Vec<Address> {
+            self.inner.active_addresses()
+        }
+    }
+
+    /// Provider that replaces modexp with a precompile returning its input.
+    #[derive(Clone)]
+    struct CustomModexp<P>(P);
+
+    impl<P: PrecompileProvider> PrecompileProvider for CustomModexp<P> {
+        type Context = P::Context;
+        type Error = P::Error;
+
+        fn new(context: &mut Self::Context) -> Self {
+            Self(P::new(context))
+        }
+
+        fn run(
+            &mut self,
+            context: &mut Self::Context,
+            address: &Address,
+            bytes: &Bytes,
+            gas_limit: u64,
+        ) -> Result<Option<InterpreterResult>, Self::Error> {
+            if *address == Address::with_last_byte(5) {
+                return Ok(Some(InterpreterResult::new(
+                    InstructionResult::Return,
+                    bytes.clone(),
+                    interpreter::Gas::new(gas_limit),
+                )));
+            }
+            self.0.run(context, address, bytes, gas_limit)
+        }
+
+        fn warm_addresses(&self) -> impl Iterator<Item = Address> {
+            self.0.warm_addresses()
+        }
+
+        fn contains(&self, address: &Address) -> bool {
+            self.0.contains(address)
+        }
+
+        fn active_addresses(&self) -> Vec<Address> {
+            self.0.active_addresses()
+        }
+    }
+
+    #[test]
+    fn runs_precompiles_of_wrapped_provider() {
+        let modexp = Address::with_last_byte(5);
+        let input = Bytes::from_static(&[1, 2, 3]);
+
+        let mut context = Context::builder().modify_cfg_chained(|cfg| {
+            cfg.precompile_deadline = Some(Instant::now() + Duration::from_secs(60))
+        });
+        let mut precompiles = DeadlinePrecompiles::<
+            CustomModexp<EthPrecompileProvider<_, PrecompileErrors>>,
+        >::new(&mut context);
+        let result = precompiles
+            .run(&mut context, &modexp, &input, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(result.output, input);
+    }
+
+    #[test]
+    fn interrupts_precompiles_after_deadline() {
+        let identity = Address::with_last_byte(4);
+        let input = Bytes::from_static(&[1, 2, 3]);
+
+        let mut context = Context::builder().modify_cfg_chained(|cfg| {
+            cfg.precompile_deadline = Some(Instant::now() + Duration::from_secs(60))
+        });
+        let mut precompiles = Provider::new(&mut context);
+        let result = precompiles
+            .run(&mut context, &identity, &input, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(result.output, input);
+
+        let mut context = Context::builder()
+            .modify_cfg_chained(|cfg| cfg.precompile_deadline = Some(Instant::now()));
+        let mut precompiles = Provider::new(&mut context);
+        let result = precompiles
+            .run(&mut context, &identity, &input, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileError);
+        assert!(precompiles
+            .run(&mut context, &Address::ZERO, &input, 1_000_000)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn discards_output_of_precompiles_finishing_after_deadline() {
+        let identity = Address::with_last_byte(4);
+        let input = Bytes::from_static(&[1, 2, 3]);
+
+        let mut context = Context::builder().modify_cfg_chained(|cfg| {
+            cfg.precompile_deadline = Some(Instant::now() + Duration::from_millis(10))
+        });
+        let mut precompiles =
+            DeadlinePrecompiles::<Slow<EthPrecompileProvider<_, PrecompileErrors>>>::new(
+                &mut context,
+            );
+        let result = precompiles
+            .run(&mut context, &identity, &input, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileError);
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn interrupts_long_modexp() {
+        let modexp = Address::with_last_byte(5);
+        // 512 byte base and modulus with a 1024 byte exponent, takes seconds without checks.
+        let mut input = vec![0u8; 96];
+        input[30..32].copy_from_slice(&512u16.to_be_bytes());
+        input[62..64].copy_from_slice(&1024u16.to_be_bytes());
+        input[94..96].copy_from_slice(&512u16.to_be_bytes());
+        input.resize(96 + 512 + 1024 + 512, 0xff);
+        let input = Bytes::from(input);
+
+        let mut context = Context::builder().modify_cfg_chained(|cfg| {
+            cfg.precompile_deadline = Some(Instant::now() + Duration::from_millis(10))
+        });
+        let mut precompiles = Provider::new(&mut context);
+        let start = Instant::now();
+        let result = precompiles
+            .run(&mut context, &modexp, &input, u64::MAX)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileError);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
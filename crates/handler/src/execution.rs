@@ -4,13 +4,15 @@ use context_interface::{
     result::InvalidTransaction, BlockGetter, Cfg, CfgGetter, ErrorGetter, JournalDBError,
     JournalGetter, Transaction, TransactionGetter,
 };
-use handler_interface::{util::FrameOrFrameResult, ExecutionHandler, Frame as FrameTrait};
+use handler_interface::{
+    util::FrameOrFrameResult, ExecutionHandler, Frame as FrameTrait, FrameOrResultGen,
+};
 use interpreter::{
     interpreter::{EthInstructionProvider, EthInterpreter},
-    CallInputs, CallScheme, CallValue, CreateInputs, CreateScheme, EOFCreateInputs, EOFCreateKind,
-    FrameInput, Gas,
+    CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, CreateScheme,
+    EOFCreateInputs, EOFCreateKind, FrameInput, Gas, InstructionResult, InterpreterResult,
 };
-use primitives::TxKind;
+use primitives::{Bytes, TxKind};
 use specification::hardfork::SpecId;
 use std::boxed::Box;
 
@@ -48,19 +50,48 @@ where
     ) -> Result<FrameOrFrameResult<Self::Frame>, Self::Error> {
         // Make new frame action.
         let spec = context.cfg().spec().into();
+        let is_read_only = context.cfg().is_read_only();
         let tx = context.tx();
         let input = tx.input().clone();
 
+        if is_read_only {
+            // Value transfer and contract creation are state changes made by the transaction
+            // itself, so they are halted before the first frame is created.
+            let result = |result| InterpreterResult::new(result, Bytes::new(), Gas::new(gas_limit));
+            match tx.kind() {
+                TxKind::Call(_) if !tx.value().is_zero() => {
+                    return Ok(FrameOrResultGen::Result(FrameResult::Call(
+                        CallOutcome::new(
+                            result(InstructionResult::CallNotAllowedInsideStatic),
+                            0..0,
+                        ),
+                    )));
+                }
+                TxKind::Create => {
+                    return Ok(FrameOrResultGen::Result(FrameResult::Create(
+                        CreateOutcome::new(
+                            result(InstructionResult::StateChangeDuringStaticCall),
+                            None,
+                        ),
+                    )));
+                }
+                _ => (),
+            }
+        }
+
         let init_frame: FrameInput = match tx.kind() {
             TxKind::Call(target_address) => FrameInput::Call(Box::new(CallInputs {
                 input,
                 gas_limit,
+                gas_limit_forwarded: gas_limit,
+                stipend_added: 0,
+                gas_before_call: gas_limit,
                 target_address,
                 bytecode_address: target_address,
                 caller: tx.caller(),
                 value: CallValue::Transfer(tx.value()),
                 scheme: CallScheme::Call,
-                is_static: false,
+                is_static: is_read_only,
                 is_eof: false,
                 return_memory_offset: 0..0,
             })),
@@ -98,6 +129,7 @@ where
         let refunded = gas.refunded();
 
         // Spend the gas limit. Gas is reimbursed when the tx returns successfully.
+        // Gas spent above the limit while unmetered is dropped, as it was never paid for.
         *gas = Gas::new_spent(context.tx().gas_limit());
 
         if instruction_result.is_ok_or_revert() {
@@ -215,3 +247,59 @@ impl<CTX: JournalGetter, T: From<InvalidTransaction> + From<JournalDBError<CTX>>
 //         assert_eq!(gas.refunded(), 0);
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{transact, TestContext};
+    use bytecode::{
+        opcode::{PUSH1, SSTORE, STOP},
+        Bytecode,
+    };
+    use context::Context;
+    use context_interface::result::{ExecutionResult, HaltReason};
+    use database::BenchmarkDB;
+    use primitives::{Address, TxKind, U256};
+
+    fn read_only_context(bytecode: Bytecode) -> TestContext<BenchmarkDB> {
+        Context::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .modify_cfg_chained(|cfg| cfg.read_only = true)
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+    }
+
+    #[test]
+    fn read_only_top_level_sstore_halts() {
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP].into());
+        let mut context = read_only_context(bytecode);
+
+        let result = transact(&mut context).unwrap();
+        assert_eq!(
+            result.result,
+            ExecutionResult::Halt {
+                reason: HaltReason::StateChangeDuringStaticCall,
+                gas_used: 100_000,
+                context: None,
+            }
+        );
+        assert!(result.state[&Address::ZERO].storage.is_empty());
+    }
+
+    #[test]
+    fn read_only_rejects_value_transfer() {
+        let mut context = read_only_context(Bytecode::new_legacy([STOP].into()));
+        context.modify_tx(|tx| tx.value = U256::from(1));
+
+        let result = transact(&mut context).unwrap();
+        assert!(matches!(
+            result.result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CallNotAllowedInsideStatic,
+                ..
+            }
+        ));
+    }
+}
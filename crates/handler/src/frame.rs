@@ -2,6 +2,7 @@ use super::frame_data::*;
 use bytecode::{Eof, EOF_MAGIC_BYTES};
 use context_interface::{
     journaled_state::{Journal, JournalCheckpoint},
+    result::HaltContext,
     BlockGetter, Cfg, CfgGetter, ErrorGetter, JournalDBError, JournalGetter, Transaction,
     TransactionGetter,
 };
@@ -10,7 +11,7 @@ use handler_interface::{Frame, FrameOrResultGen, PrecompileProvider};
 use interpreter::{
     gas,
     interpreter::{EthInterpreter, ExtBytecode, InstructionProvider},
-    interpreter_types::{LoopControl, ReturnData, RuntimeFlag},
+    interpreter_types::{Jumps, LoopControl, ReturnData, RuntimeFlag},
     return_ok, return_revert, CallInputs, CallOutcome, CallValue, CreateInputs, CreateOutcome,
     CreateScheme, EOFCreateInputs, EOFCreateKind, FrameInput, Gas, Host, InputsImpl,
     InstructionResult, Interpreter, InterpreterAction, InterpreterResult, InterpreterTypes,
@@ -24,7 +25,7 @@ use specification::{
 };
 use state::Bytecode;
 use std::borrow::ToOwned;
-use std::{rc::Rc, sync::Arc};
+use std::{boxed::Box, rc::Rc, sync::Arc};
 
 pub struct EthFrame<CTX, ERROR, IW: InterpreterTypes, PRECOMPILE, INSTRUCTIONS> {
     _phantom: core::marker::PhantomData<fn() -> (CTX, ERROR)>,
@@ -87,17 +88,17 @@ where
         mut precompile: PRECOMPILE,
         instructions: INSTRUCTION,
     ) -> Result<FrameOrResultGen<Self, FrameResult>, ERROR> {
-        let gas = Gas::new(inputs.gas_limit);
+        let is_unmetered = context.cfg().is_gas_metering_disabled();
+        let mut gas = Gas::new(inputs.gas_limit);
+        gas.set_unmetered(is_unmetered);
 
         let return_result = |instruction_result: InstructionResult| {
-            Ok(FrameOrResultGen::Result(FrameResult::Call(CallOutcome {
-                result: InterpreterResult {
-                    result: instruction_result,
-                    gas,
-                    output: Bytes::new(),
-                },
-                memory_offset: inputs.return_memory_offset.clone(),
-            })))
+            Ok(FrameOrResultGen::Result(FrameResult::Call(
+                CallOutcome::new(
+                    InterpreterResult::new(instruction_result, Bytes::new(), gas),
+                    inputs.return_memory_offset.clone(),
+                ),
+            )))
         };
 
         // Check depth
@@ -128,21 +129,55 @@ where
         }
         let is_ext_delegate_call = inputs.scheme.is_ext_delegate_call();
         if !is_ext_delegate_call {
-            if let Some(result) = precompile.run(
+            if let Some(intercept) = context.cfg().intercept(&inputs.bytecode_address) {
+                let (gas_used, output) = intercept(&inputs.input, inputs.gas_limit);
+                let mut gas = gas;
+                let result = if gas.record_cost(gas_used) {
+                    context.journal().checkpoint_commit();
+                    InterpreterResult::new(InstructionResult::Return, output, gas)
+                } else {
+                    context.journal().checkpoint_revert(checkpoint);
+                    gas.spend_all();
+                    InterpreterResult::new(InstructionResult::OutOfGas, Bytes::new(), gas)
+                };
+                return Ok(FrameOrResultGen::Result(FrameResult::Call(
+                    CallOutcome::new(result, inputs.return_memory_offset.clone()),
+                )));
+            }
+            // Unmetered precompiles run without a limit, gas above the limit of the frame is
+            // recorded as exceeded.
+            let precompile_gas_limit = if is_unmetered {
+                u64::MAX
+            } else {
+                inputs.gas_limit
+            };
+            if let Some(mut result) = precompile.run(
                 context,
                 &inputs.bytecode_address,
                 &inputs.input,
-                inputs.gas_limit,
+                precompile_gas_limit,
             )? {
+                if is_unmetered {
+                    let mut gas = gas;
+                    let _ = gas.record_cost(result.gas.spent());
+                    result.gas = gas;
+                }
                 if result.result.is_ok() {
                     context.journal().checkpoint_commit();
                 } else {
                     context.journal().checkpoint_revert(checkpoint);
                 }
-                return Ok(FrameOrResultGen::Result(FrameResult::Call(CallOutcome {
-                    result,
-                    memory_offset: inputs.return_memory_offset.clone(),
-                })));
+                return Ok(FrameOrResultGen::Result(FrameResult::Call(
+                    CallOutcome::new(result, inputs.return_memory_offset.clone()),
+                )));
+            }
+            let address = inputs.bytecode_address.as_slice();
+            if context.cfg().is_precompile_range_reserved()
+                && address[..19].iter().all(|byte| *byte == 0)
+                && address[19] != 0
+            {
+                context.journal().checkpoint_commit();
+                return return_result(InstructionResult::Stop);
             }
         }
 
@@ -163,6 +198,7 @@ where
             return return_result(InstructionResult::Stop);
         }
 
+        let mut delegation_source = None;
         if let Bytecode::Eip7702(eip7702_bytecode) = bytecode {
             let account = &context
                 .journal()
@@ -170,30 +206,45 @@ where
                 .info;
             bytecode = account.code.clone().unwrap_or_default();
             code_hash = account.code_hash();
+            delegation_source = Some(eip7702_bytecode.delegated_address);
         }
 
+        // Top level call is the message that enters the chain, its sender can be aliased.
+        let caller_address = if depth == 0 {
+            context.cfg().address_alias()(inputs.caller)
+        } else {
+            inputs.caller
+        };
+
         // Create interpreter and executes call and push new CallStackFrame.
         let interpreter_input = InputsImpl {
             target_address: inputs.target_address,
-            caller_address: inputs.caller,
+            caller_address,
             input: inputs.input.clone(),
             call_value: inputs.value.get(),
+            delegation_source,
         };
 
+        let mut interpreter = Interpreter::new(
+            memory.clone(),
+            ExtBytecode::new_with_hash(bytecode, code_hash),
+            interpreter_input,
+            inputs.is_static,
+            false,
+            context.cfg().spec().into(),
+            inputs.gas_limit,
+        );
+        interpreter
+            .control
+            .gas
+            .set_unmetered(context.cfg().is_gas_metering_disabled());
+
         Ok(FrameOrResultGen::Frame(Self::new(
             FrameData::Call(CallFrame {
                 return_memory_range: inputs.return_memory_offset.clone(),
             }),
             depth,
-            Interpreter::new(
-                memory.clone(),
-                ExtBytecode::new_with_hash(bytecode, code_hash),
-                interpreter_input,
-                inputs.is_static,
-                false,
-                context.cfg().spec().into(),
-                inputs.gas_limit,
-            ),
+            interpreter,
             checkpoint,
             precompile,
             instructions,
@@ -214,14 +265,10 @@ where
         let spec = context.cfg().spec().into();
         let return_error = |e| {
             Ok(FrameOrResultGen::Result(FrameResult::Create(
-                CreateOutcome {
-                    result: InterpreterResult {
-                        result: e,
-                        gas: Gas::new(inputs.gas_limit),
-                        output: Bytes::new(),
-                    },
-                    address: None,
-                },
+                CreateOutcome::new(
+                    InterpreterResult::new(e, Bytes::new(), Gas::new(inputs.gas_limit)),
+                    None,
+                ),
             )))
         };
 
@@ -278,28 +325,52 @@ where
             Ok(checkpoint) => checkpoint,
             Err(e) => return return_error(e.into()),
         };
+        // Reverting the account creation resets the nonce, so it does not need a journal entry.
+        if let Some(nonce) = context.cfg().contract_initial_nonce() {
+            context
+                .journal()
+                .load_account(created_address)?
+                .data
+                .info
+                .nonce = nonce;
+        }
 
         let bytecode = ExtBytecode::new(Bytecode::new_legacy(inputs.init_code.clone()));
 
+        // Created address is derived from the caller, only the init code sees the alias.
+        let caller_address = if depth == 0 {
+            context.cfg().address_alias()(inputs.caller)
+        } else {
+            inputs.caller
+        };
+
         let interpreter_input = InputsImpl {
             target_address: created_address,
-            caller_address: inputs.caller,
+            caller_address,
             input: Bytes::new(),
             call_value: inputs.value,
+            delegation_source: None,
         };
 
+        let mut interpreter = Interpreter::new(
+            memory.clone(),
+            bytecode,
+            interpreter_input,
+            false,
+            false,
+            spec,
+            inputs.gas_limit,
+        );
+        interpreter.runtime_flag.is_legacy_init = true;
+        interpreter
+            .control
+            .gas
+            .set_unmetered(context.cfg().is_gas_metering_disabled());
+
         Ok(FrameOrResultGen::Frame(Self::new(
             FrameData::Create(CreateFrame { created_address }),
             depth,
-            Interpreter::new(
-                memory.clone(),
-                bytecode,
-                interpreter_input,
-                false,
-                false,
-                spec,
-                inputs.gas_limit,
-            ),
+            interpreter,
             checkpoint,
             precompile,
             instructions,
@@ -320,14 +391,10 @@ where
         let spec = context.cfg().spec().into();
         let return_error = |e| {
             Ok(FrameOrResultGen::Result(FrameResult::EOFCreate(
-                CreateOutcome {
-                    result: InterpreterResult {
-                        result: e,
-                        gas: Gas::new(inputs.gas_limit),
-                        output: Bytes::new(),
-                    },
-                    address: None,
-                },
+                CreateOutcome::new(
+                    InterpreterResult::new(e, Bytes::new(), Gas::new(inputs.gas_limit)),
+                    None,
+                ),
             )))
         };
 
@@ -397,26 +464,48 @@ where
             Ok(checkpoint) => checkpoint,
             Err(e) => return return_error(e.into()),
         };
+        // Reverting the account creation resets the nonce, so it does not need a journal entry.
+        if let Some(nonce) = context.cfg().contract_initial_nonce() {
+            context
+                .journal()
+                .load_account(created_address)?
+                .data
+                .info
+                .nonce = nonce;
+        }
+
+        let caller_address = if depth == 0 {
+            context.cfg().address_alias()(inputs.caller)
+        } else {
+            inputs.caller
+        };
 
         let interpreter_input = InputsImpl {
             target_address: created_address,
-            caller_address: inputs.caller,
+            caller_address,
             input,
             call_value: inputs.value,
+            delegation_source: None,
         };
 
+        let mut interpreter = Interpreter::new(
+            memory.clone(),
+            ExtBytecode::new(Bytecode::Eof(Arc::new(initcode))),
+            interpreter_input,
+            false,
+            true,
+            spec,
+            inputs.gas_limit,
+        );
+        interpreter
+            .control
+            .gas
+            .set_unmetered(context.cfg().is_gas_metering_disabled());
+
         Ok(FrameOrResultGen::Frame(Self::new(
             FrameData::EOFCreate(EOFCreateFrame { created_address }),
             depth,
-            Interpreter::new(
-                memory.clone(),
-                ExtBytecode::new(Bytecode::Eof(Arc::new(initcode))),
-                interpreter_input,
-                false,
-                true,
-                spec,
-                inputs.gas_limit,
-            ),
+            interpreter,
             checkpoint,
             precompile,
             instructions,
@@ -511,7 +600,31 @@ where
         let spec = context.cfg().spec().into();
 
         // Run interpreter
-        let next_action = self.interpreter.run(self.instructions.table(), context);
+        let opcode_gas_cap = context.cfg().per_opcode_gas_cap();
+        #[cfg(feature = "std")]
+        let next_action = match (context.cfg().execution_deadline(), opcode_gas_cap) {
+            (Some(deadline), opcode_gas_cap) => self.interpreter.run_with_deadline(
+                self.instructions.table(),
+                context,
+                deadline,
+                opcode_gas_cap,
+            ),
+            (None, Some(opcode_gas_cap)) => self.interpreter.run_with_opcode_gas_cap(
+                self.instructions.table(),
+                context,
+                opcode_gas_cap,
+            ),
+            (None, None) => self.interpreter.run(self.instructions.table(), context),
+        };
+        #[cfg(not(feature = "std"))]
+        let next_action = match opcode_gas_cap {
+            Some(opcode_gas_cap) => self.interpreter.run_with_opcode_gas_cap(
+                self.instructions.table(),
+                context,
+                opcode_gas_cap,
+            ),
+            None => self.interpreter.run(self.instructions.table(), context),
+        };
 
         let mut interpreter_result = match next_action {
             InterpreterAction::NewFrame(new_frame) => {
@@ -521,6 +634,18 @@ where
             InterpreterAction::None => unreachable!("InterpreterAction::None is not expected"),
         };
 
+        if self.depth == 0
+            && interpreter_result.result.is_halt()
+            && context.cfg().is_halt_state_captured()
+        {
+            interpreter_result.halt_context = Some(Box::new(HaltContext {
+                // Instruction pointer is advanced before the instruction is executed.
+                pc: self.interpreter.bytecode.pc().saturating_sub(1),
+                stack: self.interpreter.stack.data().clone(),
+                memory: Bytes::copy_from_slice(self.interpreter.memory.borrow().context_memory()),
+            }));
+        }
+
         // Handle return from frame
         let result = match &self.data {
             FrameData::Call(frame) => {
@@ -538,28 +663,32 @@ where
             }
             FrameData::Create(frame) => {
                 let max_code_size = context.cfg().max_code_size();
-                return_create(
+                let code_hasher = context.cfg().code_hasher();
+                let deployed_code_attempt = return_create(
                     context.journal(),
                     self.checkpoint,
                     &mut interpreter_result,
                     frame.created_address,
                     max_code_size,
+                    code_hasher,
                     spec,
                 );
 
-                FrameOrResultGen::Result(FrameResult::Create(CreateOutcome::new(
-                    interpreter_result,
-                    Some(frame.created_address),
-                )))
+                let mut outcome =
+                    CreateOutcome::new(interpreter_result, Some(frame.created_address));
+                outcome.deployed_code_attempt = deployed_code_attempt;
+                FrameOrResultGen::Result(FrameResult::Create(outcome))
             }
             FrameData::EOFCreate(frame) => {
                 let max_code_size = context.cfg().max_code_size();
+                let code_hasher = context.cfg().code_hasher();
                 return_eofcreate(
                     context.journal(),
                     self.checkpoint,
                     &mut interpreter_result,
                     frame.created_address,
                     max_code_size,
+                    code_hasher,
                 );
 
                 FrameOrResultGen::Result(FrameResult::EOFCreate(CreateOutcome::new(
@@ -614,6 +743,10 @@ where
                 // Safe to push without stack limit check
                 let _ = interpreter.stack.push(item);
 
+                interpreter
+                    .control
+                    .gas()
+                    .record_exceeded(out_gas.exceeded());
                 // Return unspend gas.
                 if ins_result.is_ok_or_revert() {
                     interpreter.control.gas().erase_cost(out_gas.remaining());
@@ -646,6 +779,7 @@ where
                 );
 
                 let this_gas = interpreter.control.gas();
+                this_gas.record_exceeded(outcome.gas().exceeded());
                 if instruction_result.is_ok_or_revert() {
                     this_gas.erase_cost(outcome.gas().remaining());
                 }
@@ -678,6 +812,7 @@ where
                 );
 
                 let this_gas = interpreter.control.gas();
+                this_gas.record_exceeded(outcome.gas().exceeded());
                 if instruction_result.is_ok_or_revert() {
                     this_gas.erase_cost(outcome.gas().remaining());
                 }
@@ -698,18 +833,22 @@ where
     }
 }
 
+/// Finalizes the create frame, deploying the returned code.
+///
+/// Returns the code that could not be deployed because the code deposit ran out of gas.
 pub fn return_create<JOURNAL: Journal>(
     journal: &mut JOURNAL,
     checkpoint: JournalCheckpoint,
     interpreter_result: &mut InterpreterResult,
     address: Address,
     max_code_size: usize,
+    code_hasher: fn(&[u8]) -> B256,
     spec_id: SpecId,
-) {
+) -> Option<Bytes> {
     // If return is not ok revert and return.
     if !interpreter_result.result.is_ok() {
         journal.checkpoint_revert(checkpoint);
-        return;
+        return None;
     }
     // Host error if present on execution
     // If ok, check contract creation limit and calculate gas deduction on output len.
@@ -718,7 +857,7 @@ pub fn return_create<JOURNAL: Journal>(
     if spec_id.is_enabled_in(LONDON) && interpreter_result.output.first() == Some(&0xEF) {
         journal.checkpoint_revert(checkpoint);
         interpreter_result.result = InstructionResult::CreateContractStartingWithEF;
-        return;
+        return None;
     }
 
     // EIP-170: Contract code size limit
@@ -726,8 +865,9 @@ pub fn return_create<JOURNAL: Journal>(
     if spec_id.is_enabled_in(SPURIOUS_DRAGON) && interpreter_result.output.len() > max_code_size {
         journal.checkpoint_revert(checkpoint);
         interpreter_result.result = InstructionResult::CreateContractSizeLimit;
-        return;
+        return None;
     }
+    let mut deployed_code_attempt = None;
     let gas_for_code = interpreter_result.output.len() as u64 * gas::CODEDEPOSIT;
     if !interpreter_result.gas.record_cost(gas_for_code) {
         // Record code deposit gas cost and check if we are out of gas.
@@ -737,9 +877,9 @@ pub fn return_create<JOURNAL: Journal>(
         if spec_id.is_enabled_in(HOMESTEAD) {
             journal.checkpoint_revert(checkpoint);
             interpreter_result.result = InstructionResult::OutOfGas;
-            return;
+            return Some(interpreter_result.output.clone());
         } else {
-            interpreter_result.output = Bytes::new();
+            deployed_code_attempt = Some(core::mem::take(&mut interpreter_result.output));
         }
     }
     // If we have enough gas we can commit changes.
@@ -747,11 +887,13 @@ pub fn return_create<JOURNAL: Journal>(
 
     // Do analysis of bytecode straight away.
     let bytecode = Bytecode::new_legacy(interpreter_result.output.clone());
+    let hash = code_hasher(&interpreter_result.output);
 
     // Set code
-    journal.set_code(address, bytecode);
+    journal.set_code_with_hash(address, bytecode, hash);
 
     interpreter_result.result = InstructionResult::Return;
+    deployed_code_attempt
 }
 
 pub fn return_eofcreate<JOURNAL: Journal>(
@@ -760,6 +902,7 @@ pub fn return_eofcreate<JOURNAL: Journal>(
     interpreter_result: &mut InterpreterResult,
     address: Address,
     max_code_size: usize,
+    code_hasher: fn(&[u8]) -> B256,
 ) {
     // Note we still execute RETURN opcode and return the bytes.
     // In EOF those opcodes should abort execution.
@@ -793,7 +936,8 @@ pub fn return_eofcreate<JOURNAL: Journal>(
     let bytecode = Eof::decode(interpreter_result.output.clone()).expect("Eof is already verified");
 
     // Eof bytecode is going to be hashed.
-    journal.set_code(address, Bytecode::Eof(Arc::new(bytecode)));
+    let hash = code_hasher(&interpreter_result.output);
+    journal.set_code_with_hash(address, Bytecode::Eof(Arc::new(bytecode)), hash);
 }
 
 pub trait EthFrameContext:
@@ -826,3 +970,568 @@ impl<CTX: JournalGetter, T: From<JournalDBError<CTX>> + From<PrecompileErrors>>
     for T
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{transact, TestContext, TestError},
+        EthExecution,
+    };
+    use bytecode::opcode::{
+        ADD, CALL, CALLDATACOPY, CALLER, CREATE, EXTCODEHASH, GAS, MSTORE, POP, PUSH1, PUSH2,
+        PUSH20, PUSH3, PUSH4, PUSH5, RETURN, RETURNDATASIZE, REVERT, SSTORE, STOP,
+    };
+    use context::{Context, JournaledState};
+    use context_interface::result::{ExecutionResult, HaltReason, OutOfGasError};
+    use database::{BenchmarkDB, InMemoryDB};
+    use handler_interface::ExecutionHandler;
+    use interpreter::MAX_CODE_SIZE;
+    use primitives::{address, hex, TxKind};
+    use state::AccountInfo;
+    use std::vec;
+
+    #[test]
+    fn code_deposit_out_of_gas_keeps_deployed_code_attempt() {
+        let address = Address::with_last_byte(0xc0);
+        let mut journal = JournaledState::new(SpecId::PRAGUE, BenchmarkDB::default());
+        journal.load_account(address).unwrap();
+        let checkpoint = journal.checkpoint();
+        // 1000 bytes of code cost 200_000 gas to deposit.
+        let mut result = InterpreterResult::new(
+            InstructionResult::Return,
+            Bytes::from(vec![0; 1000]),
+            Gas::new(100_000),
+        );
+
+        let attempt = return_create(
+            &mut journal,
+            checkpoint,
+            &mut result,
+            address,
+            MAX_CODE_SIZE,
+            |code| keccak256(code),
+            SpecId::PRAGUE,
+        );
+        assert_eq!(result.result, InstructionResult::OutOfGas);
+        assert_eq!(attempt, Some(Bytes::from(vec![0; 1000])));
+    }
+
+    #[test]
+    fn delegated_account_reports_delegation_source() {
+        let delegated = Address::with_last_byte(0xaa);
+        let implementation = Address::with_last_byte(0xbb);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            delegated,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(implementation)),
+        );
+        db.insert_account_info(
+            implementation,
+            AccountInfo::from_bytecode(Bytecode::new_legacy([PUSH1, 0x00, POP, STOP].into())),
+        );
+        let mut context = Context::builder().with_db(db).modify_tx_chained(|tx| {
+            tx.caller = Address::with_last_byte(1);
+            tx.kind = TxKind::Call(delegated);
+        });
+
+        let mut execution = EthExecution::<TestContext<InMemoryDB>, TestError<InMemoryDB>>::new();
+        let FrameOrResultGen::Frame(frame) =
+            execution.init_first_frame(&mut context, 100_000).unwrap()
+        else {
+            panic!("expected a frame");
+        };
+        assert_eq!(frame.interpreter.input.target_address, delegated);
+        assert_eq!(frame.interpreter.delegation_source(), Some(implementation));
+    }
+
+    #[test]
+    fn intercepted_address_runs_native_function() {
+        fn reverse(input: &[u8], _gas_limit: u64) -> (u64, Bytes) {
+            (100, input.iter().rev().copied().collect())
+        }
+        let intercepted = Address::with_last_byte(0xff);
+
+        // Calls the intercepted address with 4 bytes and returns its output.
+        let mut code = vec![PUSH4, 0x01, 0x02, 0x03, 0x04, PUSH1, 0x00, MSTORE];
+        code.extend_from_slice(&[
+            PUSH1, 0x04, PUSH1, 0x00, PUSH1, 0x04, PUSH1, 28, PUSH1, 0x00,
+        ]);
+        code.push(PUSH20);
+        code.extend_from_slice(intercepted.as_slice());
+        code.extend_from_slice(&[GAS, CALL, POP, PUSH1, 0x04, PUSH1, 0x00, RETURN]);
+
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .modify_cfg_chained(|cfg| {
+                cfg.intercept_addresses.insert(intercepted, reverse);
+            })
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            });
+        let result = transact(&mut context).unwrap().result;
+        assert_eq!(result.output().unwrap()[..], [0x04, 0x03, 0x02, 0x01]);
+
+        // Consuming more than the gas limit runs out of gas.
+        context.modify_cfg(|cfg| {
+            cfg.intercept_addresses.insert(intercepted, |_, gas_limit| {
+                (gas_limit + 1, Bytes::from([1]))
+            });
+        });
+        let result = transact(&mut context).unwrap().result;
+        assert_eq!(result.output().unwrap()[..], [0; 4]);
+    }
+
+    #[test]
+    fn contract_code_size_override() {
+        // Deploys 30KB of zeroed memory as code.
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::default())
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Create;
+                tx.gas_limit = 10_000_000;
+                tx.data = vec![PUSH2, 0x78, 0x00, PUSH1, 0x00, RETURN].into();
+            });
+        assert!(matches!(
+            transact(&mut context).unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                ..
+            }
+        ));
+
+        context.modify_cfg(|cfg| cfg.limit_contract_code_size = Some(64 * 1024));
+        let result = transact(&mut context).unwrap();
+        assert!(result.result.is_success());
+        let created = result.state.values().find(|account| account.is_created());
+        assert_eq!(
+            created.unwrap().info.code.as_ref().unwrap().len(),
+            30 * 1024
+        );
+    }
+
+    #[test]
+    fn custom_code_hasher_is_used_for_created_contracts() {
+        fn length_hasher(code: &[u8]) -> B256 {
+            B256::with_last_byte(code.len() as u8)
+        }
+
+        // Init code returning a single zero byte.
+        let init_code = [PUSH1, 0x01, PUSH1, 0x00, RETURN];
+        // Creates a contract from `init_code` and stores its EXTCODEHASH in slot zero.
+        let mut code = vec![PUSH5];
+        code.extend_from_slice(&init_code);
+        code.extend_from_slice(&[
+            PUSH1,
+            0x00,
+            MSTORE,
+            PUSH1,
+            0x05,
+            PUSH1,
+            0x1b,
+            PUSH1,
+            0x00,
+            CREATE,
+            EXTCODEHASH,
+            PUSH1,
+            0x00,
+            SSTORE,
+            STOP,
+        ]);
+
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .modify_cfg_chained(|cfg| cfg.code_hasher = Some(length_hasher))
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            });
+
+        let result = transact(&mut context).unwrap();
+        assert!(result.result.is_success());
+        assert_eq!(
+            result.state[&Address::ZERO].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+    }
+
+    #[test]
+    fn contract_initial_nonce_override() {
+        let caller = Address::with_last_byte(1);
+        let created_nonce = |contract_initial_nonce| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::default())
+                .modify_cfg_chained(|cfg| cfg.contract_initial_nonce = contract_initial_nonce)
+                .modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Create;
+                    tx.gas_limit = 100_000;
+                    tx.data = [STOP].into();
+                });
+            let result = transact(&mut context).unwrap();
+            assert!(result.result.is_success());
+            result.state[&caller.create(0)].info.nonce
+        };
+
+        assert_eq!(created_nonce(None), 1);
+        assert_eq!(created_nonce(Some(0)), 0);
+        assert_eq!(created_nonce(Some(5)), 5);
+    }
+
+    #[test]
+    fn reserved_precompile_range_skips_execution() {
+        let reserved = Address::with_last_byte(0x20);
+        let transact_reserved = |reserved_precompile_range| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                Address::with_last_byte(1),
+                AccountInfo::from_balance(U256::from(10_000_000)),
+            );
+            db.insert_account_info(
+                reserved,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(
+                    [PUSH1, 0x00, PUSH1, 0x00, REVERT].into(),
+                )),
+            );
+            let mut context = Context::builder()
+                .with_db(db)
+                .modify_cfg_chained(|cfg| cfg.reserved_precompile_range = reserved_precompile_range)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(reserved);
+                    tx.gas_limit = 100_000;
+                });
+            transact(&mut context).unwrap().result
+        };
+
+        assert!(matches!(
+            transact_reserved(false),
+            ExecutionResult::Revert { .. }
+        ));
+        let result = transact_reserved(true);
+        assert!(result.is_success());
+        assert_eq!(result.output(), Some(&Bytes::new()));
+        assert_eq!(result.gas_used(), 21_000);
+    }
+
+    #[test]
+    fn halt_state_is_captured_on_stack_underflow() {
+        let transact_underflow = |capture_halt_state| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    [PUSH1, 0x01, PUSH1, 0x02, ADD, ADD].into(),
+                )))
+                .modify_cfg_chained(|cfg| cfg.capture_halt_state = capture_halt_state)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = 100_000;
+                });
+            transact(&mut context).unwrap().result
+        };
+
+        let ExecutionResult::Halt {
+            reason,
+            gas_used,
+            context,
+        } = transact_underflow(true)
+        else {
+            panic!("expected halt");
+        };
+        assert_eq!(reason, HaltReason::StackUnderflow);
+        let context = context.unwrap();
+        assert_eq!(context.pc, 5);
+        assert_eq!(context.stack, [U256::from(3)]);
+        assert!(context.memory.is_empty());
+
+        // Capturing does not change the result.
+        assert_eq!(
+            transact_underflow(false),
+            ExecutionResult::Halt {
+                reason,
+                gas_used,
+                context: None,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn infinite_loop_halts_at_execution_deadline() {
+        use bytecode::opcode::{JUMP, JUMPDEST};
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [JUMPDEST, PUSH1, 0x00, JUMP].into(),
+            )))
+            .modify_cfg_chained(|cfg| {
+                cfg.disable_gas_metering = true;
+                cfg.execution_deadline = Some(start + Duration::from_millis(50));
+            })
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            });
+        let result = transact(&mut context).unwrap().result;
+
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::Timeout,
+                ..
+            }
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn per_opcode_gas_cap_halts_large_copy() {
+        // Copies `size` bytes of calldata to memory.
+        let run = |size: u8, per_opcode_gas_cap| {
+            let code = [
+                PUSH3,
+                size,
+                0x00,
+                0x00,
+                PUSH1,
+                0x00,
+                PUSH1,
+                0x00,
+                CALLDATACOPY,
+                STOP,
+            ];
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+                .modify_cfg_chained(|cfg| cfg.per_opcode_gas_cap = per_opcode_gas_cap)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = 10_000_000;
+                });
+            transact(&mut context).unwrap().result
+        };
+
+        // 1MB copy.
+        assert!(run(0x10, None).is_success());
+        assert_eq!(
+            run(0x10, Some(100_000)),
+            ExecutionResult::Halt {
+                reason: HaltReason::OpcodeGasCapExceeded,
+                gas_used: 10_000_000,
+                context: None,
+            }
+        );
+        // 64KB copy costs 6144 for copying and 14336 for memory expansion.
+        assert!(run(0x01, Some(100_000)).is_success());
+    }
+
+    #[test]
+    fn per_opcode_gas_cap_ignores_static_and_forwarded_gas() {
+        let contract = Address::with_last_byte(0xc0);
+        // Writes a new slot for 22100 gas, then calls an empty account with one wei for 36600
+        // gas, forwarding all remaining gas.
+        let mut code = vec![PUSH1, 0x01, PUSH1, 0x00, SSTORE];
+        code.extend([PUSH1, 0x00].repeat(4));
+        code.extend([PUSH1, 0x01, PUSH20]);
+        code.extend_from_slice(Address::with_last_byte(0xee).as_slice());
+        code.extend_from_slice(&[GAS, CALL, STOP]);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                balance: U256::from(1),
+                ..AccountInfo::from_bytecode(Bytecode::new_legacy(code.into()))
+            },
+        );
+        let mut context = Context::builder()
+            .with_db(db)
+            .modify_cfg_chained(|cfg| cfg.per_opcode_gas_cap = Some(10_000))
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(contract);
+                tx.gas_limit = 1_000_000;
+            });
+        assert!(transact(&mut context).unwrap().result.is_success());
+    }
+
+    #[test]
+    fn disabled_gas_metering_runs_past_gas_limit() {
+        // Writes a new slot, costing 22100 gas.
+        let code = [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP];
+        let run = |disable_gas_metering| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+                .modify_cfg_chained(|cfg| cfg.disable_gas_metering = disable_gas_metering)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = 30_000;
+                });
+            transact(&mut context).unwrap()
+        };
+
+        assert_eq!(
+            run(false).result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+                gas_used: 30_000,
+                context: None,
+            }
+        );
+        let output = run(true);
+        assert!(output.result.is_success());
+        assert_eq!(output.result.gas_used(), 30_000);
+        assert_eq!(
+            output.state[&Address::ZERO].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+    }
+
+    #[test]
+    fn disabled_gas_metering_runs_precompile_past_gas_limit() {
+        // Only the intrinsic gas is provided, identity costs 15 + 3 * 32 for 1KB of input.
+        let run = |disable_gas_metering| {
+            let mut context: TestContext<_> = Context::builder()
+                .with_spec(SpecId::CANCUN)
+                .modify_cfg_chained(|cfg| cfg.disable_gas_metering = disable_gas_metering)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::with_last_byte(4));
+                    tx.data = vec![0; 1024].into();
+                    tx.gas_limit = 21_000 + 4 * 1024;
+                });
+            transact(&mut context).unwrap().result
+        };
+
+        assert!(matches!(
+            run(false),
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Precompile),
+                ..
+            }
+        ));
+        let result = run(true);
+        assert!(result.is_success());
+        assert_eq!(result.output().unwrap().len(), 1024);
+        assert_eq!(result.gas_used(), 21_000 + 4 * 1024);
+    }
+
+    #[test]
+    fn address_alias_applies_to_top_level_call_and_create() {
+        // Optimism L1 to L2 alias, adds an offset to the address.
+        fn alias(address: Address) -> Address {
+            let offset = U256::from_be_slice(&hex!("1111000000000000000000000000000000001111"));
+            let aliased = U256::from_be_slice(address.as_slice()).wrapping_add(offset);
+            Address::from_word(aliased.into())
+        }
+        let caller = Address::with_last_byte(1);
+        // Returns `msg.sender`.
+        let code = [
+            CALLER, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        let sender = |address_alias| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+                .modify_cfg_chained(|cfg| cfg.address_alias = address_alias)
+                .modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = 100_000;
+                });
+            let result = transact(&mut context).unwrap().result;
+            Address::from_word(B256::from_slice(result.output().unwrap()))
+        };
+
+        assert_eq!(sender(None), caller);
+        assert_eq!(
+            sender(Some(alias)),
+            address!("1111000000000000000000000000000000001112")
+        );
+
+        // Init code stores `msg.sender` in slot zero.
+        let create_sender = |address_alias| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::default())
+                .modify_cfg_chained(|cfg| cfg.address_alias = address_alias)
+                .modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Create;
+                    tx.gas_limit = 100_000;
+                    tx.data = [CALLER, PUSH1, 0x00, SSTORE, STOP].into();
+                });
+            let result = transact(&mut context).unwrap();
+            assert!(result.result.is_success());
+            // Created address is derived from the unaliased caller.
+            let slot = result.state[&caller.create(0)].storage[&U256::ZERO].present_value;
+            Address::from_word(slot.into())
+        };
+
+        assert_eq!(create_sender(None), caller);
+        assert_eq!(
+            create_sender(Some(alias)),
+            address!("1111000000000000000000000000000000001112")
+        );
+    }
+
+    #[test]
+    fn max_return_size_truncates_sub_call_return_data() {
+        let contract = Address::with_last_byte(0xaa);
+        let callee = Address::with_last_byte(0xbb);
+        // Calls the callee and returns the size of its return data.
+        let mut code = [PUSH1, 0x00].repeat(5);
+        code.push(PUSH20);
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[
+            GAS,
+            CALL,
+            RETURNDATASIZE,
+            PUSH1,
+            0x00,
+            MSTORE,
+            PUSH1,
+            0x20,
+            PUSH1,
+            0x00,
+            RETURN,
+        ]);
+        // Returns 64 bytes of zeroed memory.
+        let callee_code = vec![PUSH1, 0x40, PUSH1, 0x00, RETURN];
+
+        let mut db = InMemoryDB::default();
+        for (address, code) in [(contract, code), (callee, callee_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+        let mut context = Context::builder()
+            .with_db(db)
+            .modify_cfg_chained(|cfg| cfg.max_return_size = Some(32))
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(contract);
+                tx.gas_limit = 1_000_000;
+            });
+        let result = transact(&mut context).unwrap().result;
+        let ExecutionResult::Success {
+            output,
+            output_truncated,
+            ..
+        } = &result
+        else {
+            panic!("expected success, got {result:?}");
+        };
+        // Only the output of the sub call exceeds the limit.
+        assert!(!output_truncated);
+        assert_eq!(U256::from_be_slice(output.data()), U256::from(32));
+    }
+}
@@ -7,31 +7,45 @@ extern crate alloc as std;
 
 // Mainnet related handlers.
 
+mod block_building;
+#[cfg(feature = "std")]
+mod deadline_precompiles;
 mod execution;
 mod frame;
 mod frame_data;
 mod post_execution;
 mod pre_execution;
 mod precompile_provider;
+mod size_limited_precompiles;
+#[cfg(test)]
+mod test_utils;
 mod validation;
 
 // Public exports
 
+pub use block_building::pack_transactions;
+#[cfg(feature = "std")]
+pub use deadline_precompiles::DeadlinePrecompiles;
 pub use execution::{EthExecution, EthExecutionContext, EthExecutionError};
 pub use frame::{return_create, return_eofcreate, EthFrame, EthFrameContext, EthFrameError};
 pub use frame_data::{FrameData, FrameResult};
 pub use post_execution::{EthPostExecution, EthPostExecutionContext, EthPostExecutionError};
 pub use pre_execution::{
-    apply_eip7702_auth_list, EthPreExecution, EthPreExecutionContext, EthPreExecutionError,
+    apply_eip7702_auth_list, apply_eip7702_auth_list_with, AuthResult, Authorization,
+    EthPreExecution, EthPreExecutionContext, EthPreExecutionError,
 };
 use precompile::PrecompileErrors;
 pub use precompile_provider::EthPrecompileProvider;
 use primitives::Log;
+pub use size_limited_precompiles::SizeLimitedPrecompiles;
 use state::EvmState;
 use std::vec::Vec;
 pub use validation::{
-    validate_eip4844_tx, validate_initial_tx_gas, validate_priority_fee_tx,
-    validate_tx_against_account, validate_tx_env, EthValidation, EthValidationContext,
+    calldata_floor_gas, validate_block_gas_limit, validate_caller_balance, validate_caller_code,
+    validate_eip4844_tx, validate_initcode_size, validate_initial_tx_gas, validate_priority_fee_tx,
+    validate_tx_against_account, validate_tx_all, validate_tx_chain_id, validate_tx_env,
+    validate_tx_fees, validate_tx_gas_limit_cap, validate_tx_input_size, validate_tx_nonce,
+    validate_tx_type_enabled, validate_tx_type_fields, EthValidation, EthValidationContext,
     EthValidationError,
 };
 
@@ -5,7 +5,10 @@ use context_interface::{
     TransactionGetter,
 };
 use handler_interface::PostExecutionHandler;
-use interpreter::SuccessOrHalt;
+use interpreter::{
+    gas::{calc_tx_floor_cost, get_tokens_in_calldata},
+    SuccessOrHalt,
+};
 use primitives::{Log, U256};
 use specification::hardfork::SpecId;
 use state::EvmState;
@@ -57,7 +60,35 @@ where
         // Calculate gas refund for transaction.
         // If spec is set to london, it will decrease the maximum refund amount to 5th part of
         // gas spend. (Before london it was 2th part of gas spend)
-        gas.set_final_refund(context.cfg().spec().into().is_enabled_in(SpecId::LONDON));
+        // The cap follows the configured SSTORE model, as refunds are mostly its refunds.
+        // Configured quotient overrides the spec, disabled refunds are capped to zero.
+        let spec = context.cfg().spec().into();
+        let refund_spec = context
+            .cfg()
+            .sstore_model()
+            .map_or(spec, |model| model.spec_id());
+        let max_refund_quotient = if context.cfg().is_gas_refund_disabled() {
+            0
+        } else {
+            context.cfg().refund_quotient().unwrap_or(
+                if refund_spec.is_enabled_in(SpecId::LONDON) {
+                    5
+                } else {
+                    2
+                },
+            )
+        };
+        gas.set_final_refund_with_quotient(max_refund_quotient);
+
+        // EIP-7623: Increase calldata cost
+        // Transaction is charged at least the floor gas of its calldata.
+        if spec.is_enabled_in(SpecId::PRAGUE) && !context.cfg().is_calldata_floor_disabled() {
+            let floor_gas = calc_tx_floor_cost(get_tokens_in_calldata(context.tx().input(), true));
+            if gas.spent() - (gas.refunded() as u64) < floor_gas {
+                gas.set_spent(floor_gas);
+                gas.set_refund(0);
+            }
+        }
     }
 
     fn reimburse_caller(
@@ -65,6 +96,10 @@ where
         context: &mut Self::Context,
         exec_result: &mut Self::ExecResult,
     ) -> Result<(), Self::Error> {
+        // Gas was not paid for.
+        if context.cfg().is_fee_charge_disabled() {
+            return Ok(());
+        }
         let basefee = context.block().basefee() as u128;
         let caller = context.tx().caller();
         let effective_gas_price = context.tx().effective_gas_price(basefee);
@@ -89,32 +124,34 @@ where
         context: &mut Self::Context,
         exec_result: &mut Self::ExecResult,
     ) -> Result<(), Self::Error> {
-        let block = context.block();
-        let tx = context.tx();
-        let beneficiary = block.beneficiary();
-        let basefee = block.basefee() as u128;
-        let effective_gas_price = tx.effective_gas_price(basefee);
-        let gas = exec_result.gas();
+        // Gas was not paid for, there is no fee to distribute.
+        if context.cfg().is_fee_charge_disabled() {
+            return Ok(());
+        }
+        let beneficiary = context.block().beneficiary();
+        let basefee = context.block().basefee() as u128;
+        let gas_used = (exec_result.gas().spent() - exec_result.gas().refunded() as u64) as u128;
+        let is_london = context.cfg().spec().into().is_enabled_in(SpecId::LONDON);
+        let reward = coinbase_reward(context, exec_result);
 
         // Transfer fee to coinbase/beneficiary.
-        // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded.
-        let coinbase_gas_price = if context.cfg().spec().into().is_enabled_in(SpecId::LONDON) {
-            effective_gas_price.saturating_sub(basefee)
-        } else {
-            effective_gas_price
-        };
-
         let coinbase_account = context.journal().load_account(beneficiary)?;
 
         coinbase_account.data.mark_touch();
         coinbase_account.data.info.balance =
-            coinbase_account
+            coinbase_account.data.info.balance.saturating_add(reward);
+
+        // Credit the basefee to the recipient instead of burning it.
+        if let Some(recipient) = context.cfg().base_fee_recipient().filter(|_| is_london) {
+            let recipient_account = context.journal().load_account(recipient)?;
+
+            recipient_account.data.mark_touch();
+            recipient_account.data.info.balance = recipient_account
                 .data
                 .info
                 .balance
-                .saturating_add(U256::from(
-                    coinbase_gas_price * (gas.spent() - gas.refunded() as u64) as u128,
-                ));
+                .saturating_add(U256::from(basefee * gas_used));
+        }
 
         Ok(())
     }
@@ -126,11 +163,17 @@ where
     ) -> Result<Self::Output, Self::Error> {
         context.take_error()?;
 
+        let coinbase_reward = if context.cfg().is_fee_charge_disabled() {
+            U256::ZERO
+        } else {
+            coinbase_reward(context, &result)
+        };
+
         // Used gas with refund calculated.
         let gas_refunded = result.gas().refunded() as u64;
         let final_gas_used = result.gas().spent() - gas_refunded;
         let output = result.output();
-        let instruction_result = result.into_interpreter_result();
+        let mut instruction_result = result.into_interpreter_result();
 
         // Reset journal and return present state.
         let (state, logs) = context.journal().finalize()?;
@@ -142,14 +185,17 @@ where
                 gas_refunded,
                 logs,
                 output,
+                output_truncated: instruction_result.output_truncated,
             },
             SuccessOrHalt::Revert => ExecutionResult::Revert {
                 gas_used: final_gas_used,
                 output: output.into_data(),
+                output_truncated: instruction_result.output_truncated,
             },
             SuccessOrHalt::Halt(reason) => ExecutionResult::Halt {
                 reason,
                 gas_used: final_gas_used,
+                context: instruction_result.halt_context.take(),
             },
             // Only two internal return flags.
             flag @ (SuccessOrHalt::FatalExternalError | SuccessOrHalt::Internal(_)) => {
@@ -160,7 +206,11 @@ where
             }
         };
 
-        Ok(ResultAndState { result, state })
+        Ok(ResultAndState {
+            result,
+            state,
+            coinbase_reward,
+        })
     }
 
     fn clear(&self, context: &mut Self::Context) {
@@ -171,6 +221,21 @@ where
     }
 }
 
+/// Returns the fee of the transaction credited to the block beneficiary.
+///
+/// Since London (EIP-1559) the basefee is not part of it.
+fn coinbase_reward<CTX: EthPostExecutionContext>(context: &mut CTX, result: &FrameResult) -> U256 {
+    let basefee = context.block().basefee() as u128;
+    let effective_gas_price = context.tx().effective_gas_price(basefee);
+    let gas_used = (result.gas().spent() - result.gas().refunded() as u64) as u128;
+    let coinbase_gas_price = if context.cfg().spec().into().is_enabled_in(SpecId::LONDON) {
+        effective_gas_price.saturating_sub(basefee)
+    } else {
+        effective_gas_price
+    };
+    U256::from(coinbase_gas_price * gas_used)
+}
+
 /// Trait for post execution context.
 ///
 // TODO : Generalize FinalOutput.
@@ -196,3 +261,190 @@ impl<
 pub trait EthPostExecutionError<CTX: JournalGetter>: From<JournalDBError<CTX>> {}
 
 impl<CTX: JournalGetter, ERROR: From<JournalDBError<CTX>>> EthPostExecutionError<CTX> for ERROR {}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::transact;
+    use bytecode::{
+        opcode::{PUSH1, SSTORE, STOP},
+        Bytecode,
+    };
+    use context::Context;
+    use context_interface::SstoreModel;
+    use database::{BenchmarkDB, InMemoryDB};
+    use primitives::{Address, TxKind, U256};
+    use specification::hardfork::SpecId;
+    use state::AccountInfo;
+
+    #[test]
+    fn sstore_model_override() {
+        let contract = Address::with_last_byte(0xc0);
+        // Stores `value` to `slot`, slot 0 is set to 1 before the transaction.
+        let gas_used = |model, slot, value| {
+            let code = Bytecode::new_legacy([PUSH1, value, PUSH1, slot, SSTORE, STOP].into());
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+            db.insert_account_storage(contract, U256::ZERO, U256::from(1))
+                .unwrap();
+            let mut context = Context::builder()
+                .with_db(db)
+                .modify_cfg_chained(|cfg| {
+                    cfg.spec = SpecId::CANCUN;
+                    cfg.sstore_model = Some(model);
+                })
+                .modify_tx_chained(|tx| {
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                });
+            transact(&mut context).unwrap().result.gas_used()
+        };
+
+        // Setting a cold zero slot costs 20000, Berlin adds the 2100 cold access.
+        assert_eq!(gas_used(SstoreModel::Istanbul, 1, 1), 21_006 + 20_000);
+        assert_eq!(gas_used(SstoreModel::Berlin, 1, 1), 21_006 + 22_100);
+
+        // Clearing a slot costs 5000 in both. Istanbul refunds 15000, capped to half of the
+        // gas used as before London, and London refunds 4800.
+        assert_eq!(gas_used(SstoreModel::Istanbul, 0, 0), 26_006 - 26_006 / 2);
+        assert_eq!(gas_used(SstoreModel::London, 0, 0), 26_006 - 4800);
+    }
+
+    #[test]
+    fn refund_quotient_override() {
+        let contract = Address::with_last_byte(0xc0);
+        // Clears slots 0 and 1, each refunds 4800.
+        let code = Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x01, SSTORE, STOP,
+            ]
+            .into(),
+        );
+        let gas_used = |refund_quotient| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(contract, AccountInfo::from_bytecode(code.clone()));
+            for slot in 0..2 {
+                db.insert_account_storage(contract, U256::from(slot), U256::from(1))
+                    .unwrap();
+            }
+            let mut context = Context::builder()
+                .with_db(db)
+                .modify_cfg_chained(|cfg| cfg.refund_quotient = refund_quotient)
+                .modify_tx_chained(|tx| {
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                });
+            transact(&mut context).unwrap().result.gas_used()
+        };
+
+        let spent = 21_000 + 2 * 5_006;
+        // London caps the refund to a fifth of the gas spent.
+        assert_eq!(gas_used(None), spent - spent / 5);
+        assert_eq!(gas_used(Some(1)), spent - 2 * 4800);
+        assert_eq!(gas_used(Some(0)), spent);
+    }
+
+    #[test]
+    fn base_fee_is_credited_to_recipient() {
+        let recipient = Address::with_last_byte(0x42);
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP].into(),
+            )))
+            .modify_cfg_chained(|cfg| cfg.base_fee_recipient = Some(recipient))
+            .modify_block_chained(|block| block.basefee = 10)
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+                tx.gas_price = 10;
+            });
+
+        let result = transact(&mut context).unwrap();
+        let gas_used = result.result.gas_used();
+        assert!(result.result.is_success());
+        assert_eq!(
+            result.state[&recipient].info.balance,
+            U256::from(gas_used * 10)
+        );
+    }
+
+    #[test]
+    fn coinbase_reward_excludes_prefunded_balance() {
+        let caller = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(0xc0);
+        let run = |coinbase| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(caller, AccountInfo::from_balance(U256::from(10_000_000)));
+            db.insert_account_info(
+                contract,
+                AccountInfo::from_bytecode(Bytecode::new_legacy([STOP].into())),
+            );
+            if coinbase != caller {
+                db.insert_account_info(coinbase, AccountInfo::from_balance(U256::from(1000)));
+            }
+            let mut context = Context::builder()
+                .with_db(db)
+                .modify_block_chained(|block| {
+                    block.basefee = 10;
+                    block.beneficiary = coinbase;
+                })
+                .modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                    tx.gas_price = 13;
+                });
+            transact(&mut context).unwrap()
+        };
+
+        // Priority fee of 3 per gas goes to the coinbase, the basefee is burned.
+        let coinbase = Address::with_last_byte(0xcb);
+        let result = run(coinbase);
+        assert_eq!(result.coinbase_reward, U256::from(3 * 21_000));
+        assert_eq!(
+            result.state[&coinbase].info.balance,
+            U256::from(1000 + 3 * 21_000)
+        );
+
+        // Paying the fee to itself does not change the reported reward.
+        assert_eq!(run(caller).coinbase_reward, U256::from(3 * 21_000));
+    }
+
+    #[test]
+    fn unmetered_gas_above_limit_is_not_paid_for() {
+        let caller = Address::with_last_byte(1);
+        let coinbase = Address::with_last_byte(0xcb);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(10_000_000)));
+        // Writes a new slot, costing 22100 gas on top of the intrinsic gas.
+        db.insert_account_info(
+            Address::ZERO,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP].into(),
+            )),
+        );
+        let mut context = Context::builder()
+            .with_db(db)
+            .modify_cfg_chained(|cfg| cfg.disable_gas_metering = true)
+            .modify_block_chained(|block| block.beneficiary = coinbase)
+            .modify_tx_chained(|tx| {
+                tx.caller = caller;
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 30_000;
+                tx.gas_price = 10;
+            });
+
+        let result = transact(&mut context).unwrap();
+        assert!(result.result.is_success());
+        assert_eq!(result.result.gas_used(), 30_000);
+        assert_eq!(result.coinbase_reward, U256::from(30_000 * 10));
+        assert_eq!(
+            result.state[&coinbase].info.balance,
+            U256::from(30_000 * 10)
+        );
+        assert_eq!(
+            result.state[&caller].info.balance,
+            U256::from(10_000_000 - 30_000 * 10)
+        );
+    }
+}
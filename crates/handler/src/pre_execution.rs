@@ -44,10 +44,12 @@ where
         let spec = context.cfg().spec().into();
         // Set journaling state flag.
         context.journal().set_spec_id(spec);
+        let removal = context.cfg().force_empty_account_removal();
+        context.journal().set_empty_account_removal(removal);
 
         // Load coinbase
         // EIP-3651: Warm COINBASE. Starts the `COINBASE` address warm
-        if spec.is_enabled_in(SpecId::SHANGHAI) {
+        if context.cfg().is_coinbase_warm() {
             let coinbase = context.block().beneficiary();
             context.journal().warm_account(coinbase);
         }
@@ -96,6 +98,11 @@ where
             gas_cost = gas_cost.saturating_add(blob_price.saturating_mul(blob_gas));
         }
 
+        // Gas is not paid for if the fee charge is disabled.
+        if context.cfg().is_fee_charge_disabled() {
+            gas_cost = 0;
+        }
+
         let is_call = context.tx().kind().is_call();
         let caller = context.tx().caller();
 
@@ -119,6 +126,32 @@ where
     }
 }
 
+/// EIP-7702 authorization as seen by [`apply_eip7702_auth_list_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Authorization {
+    /// Recovered authority, `None` if the signature is invalid.
+    pub authority: Option<Address>,
+    pub chain_id: U256,
+    pub nonce: u64,
+    /// Address the authority delegates to.
+    pub address: Address,
+}
+
+/// Outcome of applying a single EIP-7702 authorization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AuthResult {
+    /// Delegation was set and the authority nonce was bumped.
+    Applied,
+    /// Authority could not be recovered from the signature.
+    InvalidSignature,
+    /// Chain id is neither zero nor the current chain id.
+    SkippedChainId,
+    /// Authority has code that is not a delegation.
+    SkippedCode,
+    /// Nonce does not match the authority nonce.
+    SkippedNonce,
+}
+
 /// Apply EIP-7702 auth list and return number gas refund on already created accounts.
 #[inline]
 pub fn apply_eip7702_auth_list<
@@ -126,6 +159,18 @@ pub fn apply_eip7702_auth_list<
     ERROR: From<InvalidTransaction> + From<JournalDBError<CTX>>,
 >(
     context: &mut CTX,
+) -> Result<u64, ERROR> {
+    apply_eip7702_auth_list_with(context, |_, _, _| ())
+}
+
+/// Same as [`apply_eip7702_auth_list`] but calls `on_auth` with the outcome of every
+/// authorization, in list order.
+pub fn apply_eip7702_auth_list_with<
+    CTX: TransactionGetter + JournalGetter + CfgGetter,
+    ERROR: From<InvalidTransaction> + From<JournalDBError<CTX>>,
+>(
+    context: &mut CTX,
+    mut on_auth: impl FnMut(&mut CTX, &Authorization, AuthResult),
 ) -> Result<u64, ERROR> {
     // Return if there is no auth list.
     let tx = context.tx();
@@ -133,13 +178,6 @@ pub fn apply_eip7702_auth_list<
         return Ok(0);
     }
 
-    struct Authorization {
-        authority: Option<Address>,
-        address: Address,
-        nonce: u64,
-        chain_id: U256,
-    }
-
     let authorization_list = tx
         .authorization_list()
         .map(|a| Authorization {
@@ -153,53 +191,71 @@ pub fn apply_eip7702_auth_list<
 
     let mut refunded_accounts = 0;
     for authorization in authorization_list {
-        // 1. Recover authority and authorized addresses.
-        // authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]
-        let Some(authority) = authorization.authority else {
-            continue;
-        };
+        let result = apply_authorization::<CTX, ERROR>(
+            context,
+            &authorization,
+            chain_id,
+            &mut refunded_accounts,
+        )?;
+        on_auth(context, &authorization, result);
+    }
 
-        // 2. Verify the chain id is either 0 or the chain's current ID.
-        if authorization.chain_id.is_zero() && authorization.chain_id != U256::from(chain_id) {
-            continue;
-        }
+    let refunded_gas =
+        refunded_accounts * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST);
 
-        // Warm authority account and check nonce.
-        // 3. Add authority to accessed_addresses (as defined in EIP-2929.)
-        let mut authority_acc = context.journal().load_account_code(authority)?;
+    Ok(refunded_gas)
+}
 
-        // 4. Verify the code of authority is either empty or already delegated.
-        if let Some(bytecode) = &authority_acc.info.code {
-            // If it is not empty and it is not eip7702
-            if !bytecode.is_empty() && !bytecode.is_eip7702() {
-                continue;
-            }
-        }
+/// Applies a single authorization, counting authorities that already exist in
+/// `refunded_accounts`.
+fn apply_authorization<CTX: JournalGetter, ERROR: From<JournalDBError<CTX>>>(
+    context: &mut CTX,
+    authorization: &Authorization,
+    chain_id: u64,
+    refunded_accounts: &mut u64,
+) -> Result<AuthResult, ERROR> {
+    // 1. Recover authority and authorized addresses.
+    // authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]
+    let Some(authority) = authorization.authority else {
+        return Ok(AuthResult::InvalidSignature);
+    };
+
+    // 2. Verify the chain id is either 0 or the chain's current ID.
+    if !authorization.chain_id.is_zero() && authorization.chain_id != U256::from(chain_id) {
+        return Ok(AuthResult::SkippedChainId);
+    }
 
-        // 5. Verify the nonce of authority is equal to nonce.
-        if authorization.nonce != authority_acc.info.nonce {
-            continue;
-        }
+    // Warm authority account and check nonce.
+    // 3. Add authority to accessed_addresses (as defined in EIP-2929.)
+    let mut authority_acc = context.journal().load_account_code(authority)?;
 
-        // 6. Refund the sender PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST gas if authority exists in the trie.
-        if !authority_acc.is_empty() {
-            refunded_accounts += 1;
+    // 4. Verify the code of authority is either empty or already delegated.
+    if let Some(bytecode) = &authority_acc.info.code {
+        // If it is not empty and it is not eip7702
+        if !bytecode.is_empty() && !bytecode.is_eip7702() {
+            return Ok(AuthResult::SkippedCode);
         }
+    }
 
-        // 7. Set the code of authority to be 0xef0100 || address. This is a delegation designation.
-        let bytecode = Bytecode::new_eip7702(authorization.address);
-        authority_acc.info.code_hash = bytecode.hash_slow();
-        authority_acc.info.code = Some(bytecode);
+    // 5. Verify the nonce of authority is equal to nonce.
+    if authorization.nonce != authority_acc.info.nonce {
+        return Ok(AuthResult::SkippedNonce);
+    }
 
-        // 8. Increase the nonce of authority by one.
-        authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
-        authority_acc.mark_touch();
+    // 6. Refund the sender PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST gas if authority exists in the trie.
+    if !authority_acc.is_empty() {
+        *refunded_accounts += 1;
     }
 
-    let refunded_gas =
-        refunded_accounts * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST);
+    // 7. Set the code of authority to be 0xef0100 || address. This is a delegation designation.
+    let bytecode = Bytecode::new_eip7702(authorization.address);
+    authority_acc.info.code_hash = bytecode.hash_slow();
+    authority_acc.info.code = Some(bytecode);
 
-    Ok(refunded_gas)
+    // 8. Increase the nonce of authority by one.
+    authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
+    authority_acc.mark_touch();
+    Ok(AuthResult::Applied)
 }
 
 pub trait EthPreExecutionContext:
@@ -230,3 +286,140 @@ impl<CTX: JournalGetter, T: From<InvalidTransaction> + From<JournalDBError<CTX>>
     EthPreExecutionError<CTX> for T
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::transact;
+    use bytecode::opcode::{BALANCE, COINBASE, PUSH1, SSTORE, STOP};
+    use context::Context;
+    use context_interface::result::EVMError;
+    use database::{BenchmarkDB, InMemoryDB};
+    use primitives::TxKind;
+    use primitives::B256;
+    use specification::eip4844::GAS_PER_BLOB;
+    use state::AccountInfo;
+    use std::vec;
+
+    #[test]
+    fn deduct_caller_charges_blob_fee_at_zero_gas_price() {
+        let caller = Address::with_last_byte(1);
+        let balance = U256::from(1_000_000);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance,
+                ..Default::default()
+            },
+        );
+        let mut context = Context::builder().with_db(db).modify_tx_chained(|tx| {
+            tx.tx_type = TransactionType::Eip4844 as u8;
+            tx.caller = caller;
+            tx.gas_price = 0;
+            tx.blob_hashes = vec![B256::ZERO];
+        });
+        let blob_price = context.block().blob_gasprice().unwrap();
+        assert_ne!(blob_price, 0);
+
+        EthPreExecution::<_, EVMError<_, InvalidTransaction>>::new()
+            .deduct_caller(&mut context)
+            .unwrap();
+        assert_eq!(
+            context.journaled_state.state[&caller].info.balance,
+            balance - U256::from(blob_price * GAS_PER_BLOB as u128)
+        );
+    }
+
+    #[test]
+    fn warm_coinbase_override() {
+        let gas_used = |warm_coinbase| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    [COINBASE, BALANCE, STOP].into(),
+                )))
+                .modify_cfg_chained(|cfg| cfg.warm_coinbase = warm_coinbase)
+                .modify_block_chained(|block| block.beneficiary = Address::with_last_byte(0x42))
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = 100_000;
+                });
+            transact(&mut context).unwrap().result.gas_used()
+        };
+
+        let spec_default = gas_used(None);
+        assert_eq!(gas_used(Some(true)), spec_default);
+        // Cold account access costs 2600 instead of 100.
+        assert_eq!(gas_used(Some(false)), spec_default + 2500);
+    }
+
+    #[test]
+    fn empty_account_removal_override() {
+        let empty = Address::with_last_byte(0x42);
+        // Databases remove accounts that are marked as selfdestructed on commit.
+        let removed_after_touch = |force_empty_account_removal| {
+            let mut context = Context::builder()
+                .with_db(InMemoryDB::default())
+                .modify_cfg_chained(|cfg| {
+                    cfg.force_empty_account_removal = force_empty_account_removal
+                })
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(empty);
+                    tx.gas_limit = 100_000;
+                });
+            let result = transact(&mut context).unwrap();
+            assert!(result.result.is_success());
+            result.state[&empty].is_selfdestructed()
+        };
+
+        assert!(!removed_after_touch(None));
+        assert!(!removed_after_touch(Some(false)));
+        assert!(removed_after_touch(Some(true)));
+    }
+
+    #[test]
+    fn disabled_fee_charge_skips_gas_payment() {
+        // Account without balance.
+        let caller = Address::with_last_byte(2);
+        let beneficiary = Address::with_last_byte(0x42);
+        let context = |disable_fee_charge| {
+            Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP].into(),
+                )))
+                .modify_cfg_chained(|cfg| cfg.disable_fee_charge = disable_fee_charge)
+                .modify_block_chained(|block| block.beneficiary = beneficiary)
+                .modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = 100_000;
+                    tx.gas_price = 10;
+                })
+        };
+
+        assert!(matches!(
+            transact(&mut context(false)),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+
+        let result = transact(&mut context(true)).unwrap();
+        assert!(result.result.is_success());
+        assert!(result.result.gas_used() > 21_000);
+        assert_eq!(result.state[&caller].info.balance, U256::ZERO);
+        assert_eq!(result.state[&caller].info.nonce, 1);
+        assert!(!result.state.contains_key(&beneficiary));
+
+        // The value transfer still requires the balance.
+        let mut context = context(true).modify_tx_chained(|tx| tx.value = U256::from(1));
+        assert!(matches!(
+            transact(&mut context),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+    }
+}
@@ -1,9 +1,13 @@
-use context_interface::{Cfg, CfgGetter};
+use context_interface::{Block, BlockGetter, Cfg, CfgGetter};
+use core::ptr::fn_addr_eq;
 use handler_interface::PrecompileProvider;
 use interpreter::{Gas, InstructionResult, InterpreterResult};
-use precompile::PrecompileErrors;
-use precompile::{PrecompileSpecId, Precompiles};
+#[cfg(feature = "blst")]
+use precompile::bls12_381::pairing;
+use precompile::{kzg_point_evaluation, modexp, PrecompileSpecId, Precompiles};
+use precompile::{PrecompileError, PrecompileErrors, PrecompileFn, PrecompileResult};
 use primitives::{Address, Bytes};
+use std::vec::Vec;
 
 pub struct EthPrecompileProvider<CTX, ERROR> {
     pub precompiles: &'static Precompiles,
@@ -21,23 +25,29 @@ impl<CTX, ERROR> Clone for EthPrecompileProvider<CTX, ERROR> {
 
 impl<CTX, ERROR> PrecompileProvider for EthPrecompileProvider<CTX, ERROR>
 where
-    CTX: CfgGetter,
+    CTX: CfgGetter + BlockGetter,
     ERROR: From<PrecompileErrors>,
 {
     type Context = CTX;
     type Error = ERROR;
 
     fn new(context: &mut Self::Context) -> Self {
-        let spec = context.cfg().spec().into();
+        let spec = match context.cfg().chain_spec() {
+            Some(chain_spec) => {
+                let block = context.block();
+                chain_spec.active_precompiles(block.number(), block.timestamp())
+            }
+            None => PrecompileSpecId::from_spec_id(context.cfg().spec().into()),
+        };
         Self {
-            precompiles: Precompiles::new(PrecompileSpecId::from_spec_id(spec)),
+            precompiles: Precompiles::new(spec),
             _phantom: core::marker::PhantomData,
         }
     }
 
     fn run(
         &mut self,
-        _context: &mut Self::Context,
+        context: &mut Self::Context,
         address: &Address,
         bytes: &Bytes,
         gas_limit: u64,
@@ -46,29 +56,49 @@ where
             return Ok(None);
         };
 
-        let mut result = InterpreterResult {
-            result: InstructionResult::Return,
-            gas: Gas::new(gas_limit),
-            output: Bytes::new(),
+        if *address == kzg_point_evaluation::ADDRESS {
+            if let Some(verifier) = context.cfg().kzg_settings() {
+                let output = kzg_point_evaluation::run_with_verifier(bytes, gas_limit, verifier);
+                return into_interpreter_result(output, gas_limit).map(Some);
+            }
+        }
+
+        into_interpreter_result((*precompile)(bytes, gas_limit), gas_limit).map(Some)
+    }
+
+    /// Checks `interrupt` between exponent chunks of modexp and between pairs of BLS12-381
+    /// pairing, if those are the registered implementations.
+    ///
+    /// Other precompiles, including custom ones registered at those addresses, run unchanged.
+    fn run_interruptible(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+        interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        let Some(&precompile) = self.precompiles.get(address) else {
+            return Ok(None);
+        };
+        let check = || match interrupt() {
+            true => Err(PrecompileError::Timeout),
+            false => Ok(()),
         };
 
-        match (*precompile)(bytes, gas_limit) {
-            Ok(output) => {
-                let underflow = result.gas.record_cost(output.gas_used);
-                assert!(underflow, "Gas underflow is not possible");
-                result.result = InstructionResult::Return;
-                result.output = output.bytes;
-            }
-            Err(PrecompileErrors::Error(e)) => {
-                result.result = if e.is_oog() {
-                    InstructionResult::PrecompileOOG
-                } else {
-                    InstructionResult::PrecompileError
-                };
-            }
-            Err(err @ PrecompileErrors::Fatal { .. }) => return Err(err.into()),
+        #[cfg(feature = "blst")]
+        if fn_addr_eq(precompile, pairing::PRECOMPILE.1 as PrecompileFn) {
+            let output = pairing::pairing_with_check(bytes, gas_limit, check);
+            return into_interpreter_result(output, gas_limit).map(Some);
         }
-        Ok(Some(result))
+        let output = if fn_addr_eq(precompile, modexp::BERLIN.1 as PrecompileFn) {
+            modexp::berlin_run_with_check(bytes, gas_limit, check)
+        } else if fn_addr_eq(precompile, modexp::BYZANTIUM.1 as PrecompileFn) {
+            modexp::byzantium_run_with_check(bytes, gas_limit, check)
+        } else {
+            return self.run(context, address, bytes, gas_limit);
+        };
+        into_interpreter_result(output, gas_limit).map(Some)
     }
 
     fn warm_addresses(&self) -> impl Iterator<Item = Address> {
@@ -78,4 +108,149 @@ where
     fn contains(&self, address: &Address) -> bool {
         self.precompiles.contains(address)
     }
+
+    /// Returns active precompile addresses in ascending order.
+    fn active_addresses(&self) -> Vec<Address> {
+        let mut addresses: Vec<_> = self.precompiles.addresses().copied().collect();
+        addresses.sort_unstable();
+        addresses
+    }
+}
+
+/// Converts the output of a precompile into an [`InterpreterResult`].
+///
+/// Fatal precompile errors are returned as errors.
+pub(crate) fn into_interpreter_result<ERROR: From<PrecompileErrors>>(
+    output: PrecompileResult,
+    gas_limit: u64,
+) -> Result<InterpreterResult, ERROR> {
+    let mut result =
+        InterpreterResult::new(InstructionResult::Return, Bytes::new(), Gas::new(gas_limit));
+
+    match output {
+        Ok(output) => {
+            let underflow = result.gas.record_cost(output.gas_used);
+            assert!(underflow, "Gas underflow is not possible");
+            result.result = InstructionResult::Return;
+            result.output = output.bytes;
+        }
+        Err(PrecompileErrors::Error(e)) => {
+            result.result = if e.is_oog() {
+                InstructionResult::PrecompileOOG
+            } else {
+                InstructionResult::PrecompileError
+            };
+        }
+        Err(err @ PrecompileErrors::Fatal { .. }) => return Err(err.into()),
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::transact;
+    use context::Context;
+    use context_interface::KzgVerifier;
+    use database::BenchmarkDB;
+    use precompile::kzg_point_evaluation;
+    use primitives::TxKind;
+    use specification::{chain_spec::ChainSpec, hardfork::SpecId};
+    use std::sync::Arc;
+
+    #[test]
+    fn active_addresses_follow_spec() {
+        let mut context = Context::builder().modify_cfg_chained(|cfg| cfg.spec = SpecId::CANCUN);
+        let precompiles = EthPrecompileProvider::<_, PrecompileErrors>::new(&mut context);
+        assert_eq!(
+            precompiles.active_addresses(),
+            (1..=10).map(Address::with_last_byte).collect::<Vec<_>>()
+        );
+
+        let mut context = Context::builder().modify_cfg_chained(|cfg| cfg.spec = SpecId::BERLIN);
+        let precompiles = EthPrecompileProvider::<_, PrecompileErrors>::new(&mut context);
+        assert_eq!(precompiles.active_addresses().len(), 9);
+    }
+
+    /// Activates the Prague precompiles at timestamp 1000, on top of Cancun.
+    #[derive(Debug)]
+    struct TimestampActivation;
+
+    impl ChainSpec for TimestampActivation {
+        fn active_precompiles(&self, _block: u64, timestamp: u64) -> PrecompileSpecId {
+            if timestamp >= 1000 {
+                PrecompileSpecId::PRAGUE
+            } else {
+                PrecompileSpecId::CANCUN
+            }
+        }
+    }
+
+    #[test]
+    fn chain_spec_selects_precompiles() {
+        let bls12_g1add = Address::with_last_byte(0x0b);
+        let context = |timestamp| {
+            Context::builder()
+                .modify_cfg_chained(|cfg| {
+                    cfg.spec = SpecId::CANCUN;
+                    cfg.chain_spec = Some(Arc::new(TimestampActivation));
+                })
+                .modify_block_chained(|block| block.timestamp = timestamp)
+        };
+
+        let precompiles = EthPrecompileProvider::<_, PrecompileErrors>::new(&mut context(999));
+        assert!(!precompiles.contains(&bls12_g1add));
+        let precompiles = EthPrecompileProvider::<_, PrecompileErrors>::new(&mut context(1000));
+        assert!(precompiles.contains(&bls12_g1add));
+    }
+
+    #[test]
+    fn custom_kzg_settings_verify_point_evaluation() {
+        /// Setup under which only `proof` is valid.
+        #[derive(Debug)]
+        struct CustomSetup {
+            proof: [u8; 48],
+        }
+
+        impl KzgVerifier for CustomSetup {
+            fn verify_kzg_proof(
+                &self,
+                _commitment: &[u8; 48],
+                _z: &[u8; 32],
+                _y: &[u8; 32],
+                proof: &[u8; 48],
+            ) -> bool {
+                *proof == self.proof
+            }
+        }
+
+        let commitment = [0x11; 48];
+        let proof = [0x22; 48];
+        let input = [
+            &kzg_point_evaluation::kzg_to_versioned_hash(&commitment)[..],
+            &[0; 64],
+            &commitment,
+            &proof,
+        ]
+        .concat();
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::default())
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(kzg_point_evaluation::ADDRESS);
+                tx.gas_limit = 100_000;
+                tx.data = input.into();
+            });
+        // Not valid under the mainnet setup, which errors without a KZG backend enabled.
+        assert!(!transact(&mut context).is_ok_and(|output| output.result.is_success()));
+
+        context.modify_cfg(|cfg| {
+            cfg.kzg_settings = Some(Arc::new(CustomSetup { proof }));
+        });
+        let result = transact(&mut context).unwrap().result;
+        assert_eq!(
+            result.output().unwrap()[..],
+            kzg_point_evaluation::RETURN_VALUE[..]
+        );
+    }
 }
@@ -0,0 +1,142 @@
+use crate::precompile_provider::into_interpreter_result;
+use context_interface::{Cfg, CfgGetter};
+use handler_interface::PrecompileProvider;
+use interpreter::InterpreterResult;
+use precompile::{PrecompileError, PrecompileErrors};
+use primitives::{Address, Bytes};
+use std::vec::Vec;
+
+/// [`PrecompileProvider`] wrapper that rejects inputs longer than the configured limit.
+///
+/// The length is checked before the inner precompile runs, so oversized inputs are never
+/// decoded or allocated for. Rejected calls fail with [`PrecompileError::InputTooLarge`].
+///
+/// When created by the handler, the limit is taken from [`Cfg::precompile_max_input_len`].
+#[derive(Clone, Debug)]
+pub struct SizeLimitedPrecompiles<P> {
+    pub inner: P,
+    pub max_input_len: Option<usize>,
+}
+
+impl<P> SizeLimitedPrecompiles<P> {
+    /// Wraps the precompiles, limiting their input to `max_input_len` bytes.
+    pub fn new(inner: P, max_input_len: usize) -> Self {
+        Self {
+            inner,
+            max_input_len: Some(max_input_len),
+        }
+    }
+}
+
+impl<P> PrecompileProvider for SizeLimitedPrecompiles<P>
+where
+    P: PrecompileProvider,
+    P::Context: CfgGetter,
+    P::Error: From<PrecompileErrors>,
+{
+    type Context = P::Context;
+    type Error = P::Error;
+
+    fn new(context: &mut Self::Context) -> Self {
+        let max_input_len = context.cfg().precompile_max_input_len();
+        Self {
+            inner: P::new(context),
+            max_input_len,
+        }
+    }
+
+    fn run(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        self.run_interruptible(context, address, bytes, gas_limit, &mut || false)
+    }
+
+    fn run_interruptible(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+        interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        if self.max_input_len.is_some_and(|max| bytes.len() > max) && self.inner.contains(address) {
+            let output = Err(PrecompileError::InputTooLarge.into());
+            return into_interpreter_result(output, gas_limit).map(Some);
+        }
+        self.inner
+            .run_interruptible(context, address, bytes, gas_limit, interrupt)
+    }
+
+    fn warm_addresses(&self) -> impl Iterator<Item = Address> {
+        self.inner.warm_addresses()
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.inner.contains(address)
+    }
+
+    fn active_addresses(&self) -> Vec<Address> {
+        self.inner.active_addresses()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthPrecompileProvider;
+    use context::Context;
+    use interpreter::InstructionResult;
+    use std::vec;
+
+    type Provider<CTX> = EthPrecompileProvider<CTX, PrecompileErrors>;
+
+    #[test]
+    fn rejects_oversized_modexp_input() {
+        let modexp = Address::with_last_byte(5);
+        // Base, exponent and modulus of 1024 bytes each, would be costly to run.
+        let mut input = vec![0u8; 96 + 3 * 1024];
+        for len_offset in [30, 62, 94] {
+            input[len_offset] = 0x04;
+        }
+        input[96..].fill(0xff);
+        let input = Bytes::from(input);
+
+        let mut context = Context::builder();
+        let inner = Provider::new(&mut context);
+        let mut precompiles = SizeLimitedPrecompiles::new(inner, 1024);
+        let result = precompiles
+            .run(&mut context, &modexp, &input, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileError);
+        assert!(result.output.is_empty());
+
+        // Inputs within the limit and non precompile addresses are passed through.
+        let small = Bytes::from([0u8; 96]);
+        let result = precompiles
+            .run(&mut context, &modexp, &small, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::Return);
+        assert!(precompiles
+            .run(&mut context, &Address::ZERO, &input, 1_000_000)
+            .unwrap()
+            .is_none());
+
+        // Limit is read from the configuration when created by the handler.
+        let mut context =
+            Context::builder().modify_cfg_chained(|cfg| cfg.precompile_max_input_len = Some(1024));
+        let mut precompiles: SizeLimitedPrecompiles<Provider<_>> =
+            PrecompileProvider::new(&mut context);
+        assert_eq!(precompiles.max_input_len, Some(1024));
+        let result = precompiles
+            .run(&mut context, &modexp, &input, 1_000_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.result, InstructionResult::PrecompileError);
+    }
+}
@@ -0,0 +1,60 @@
+//! Helpers for running transactions in the handler tests.
+use crate::EthHandler;
+use context::{BlockEnv, CfgEnv, Context, TxEnv};
+use context_interface::{
+    result::{EVMError, HaltReason, InvalidTransaction, ResultAndState},
+    Database, Transaction, TransactionGetter,
+};
+use handler_interface::{
+    ExecutionHandler, FrameOrResultGen, PostExecutionHandler, PreExecutionHandler,
+    ValidationHandler,
+};
+
+/// Mainnet context of the tests.
+pub(crate) type TestContext<DB> = Context<BlockEnv, TxEnv, CfgEnv, DB>;
+
+/// Error of the [`TestContext`].
+pub(crate) type TestError<DB> = EVMError<<DB as Database>::Error, InvalidTransaction>;
+
+/// Validates and executes the transaction of the context with the [`EthHandler`], calling the
+/// stages in the same order as the EVM does.
+pub(crate) fn transact<DB: Database>(
+    context: &mut TestContext<DB>,
+) -> Result<ResultAndState<HaltReason>, TestError<DB>> {
+    let mut handler = EthHandler::<TestContext<DB>, TestError<DB>>::default();
+    let output = transact_inner(&mut handler, context);
+    let output = handler.post_execution.end(context, output);
+    handler.post_execution.clear(context);
+    output
+}
+
+fn transact_inner<DB: Database>(
+    handler: &mut EthHandler<TestContext<DB>, TestError<DB>>,
+    context: &mut TestContext<DB>,
+) -> Result<ResultAndState<HaltReason>, TestError<DB>> {
+    handler.validation.validate_env(context)?;
+    let initial_gas_spend = handler.validation.validate_initial_tx_gas(context)?;
+    handler.validation.validate_tx_against_state(context)?;
+
+    handler.pre_execution.load_accounts(context)?;
+    handler.pre_execution.deduct_caller(context)?;
+    let gas_limit = context.tx().gas_limit() - initial_gas_spend;
+    let eip7702_gas_refund = handler.pre_execution.apply_eip7702_auth_list(context)? as i64;
+
+    let frame_result = match handler.execution.init_first_frame(context, gas_limit)? {
+        FrameOrResultGen::Frame(frame) => handler.execution.run(context, frame)?,
+        FrameOrResultGen::Result(result) => result,
+    };
+    let mut exec_result = handler.execution.last_frame_result(context, frame_result)?;
+
+    handler
+        .post_execution
+        .refund(context, &mut exec_result, eip7702_gas_refund);
+    handler
+        .post_execution
+        .reimburse_caller(context, &mut exec_result)?;
+    handler
+        .post_execution
+        .reward_beneficiary(context, &mut exec_result)?;
+    handler.post_execution.output(context, exec_result)
+}
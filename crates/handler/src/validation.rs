@@ -7,10 +7,10 @@ use context_interface::{
 use core::cmp::{self, Ordering};
 use handler_interface::ValidationHandler;
 use interpreter::gas;
-use primitives::{B256, U256};
+use primitives::B256;
 use specification::{eip4844, hardfork::SpecId};
 use state::Account;
-use std::boxed::Box;
+use std::{boxed::Box, vec::Vec};
 
 pub struct EthValidation<CTX, ERROR> {
     pub _phantom: core::marker::PhantomData<fn() -> (CTX, ERROR)>,
@@ -133,6 +133,9 @@ pub fn validate_eip4844_tx(
 }
 
 /// Validate transaction against block and configuration for mainnet.
+///
+/// Runs the block and configuration checks of [`validate_tx_all`] in the same order, stopping at
+/// the first failure.
 pub fn validate_tx_env<CTX: TransactionGetter + BlockGetter + CfgGetter, Error>(
     context: CTX,
     spec_id: SpecId,
@@ -140,130 +143,166 @@ pub fn validate_tx_env<CTX: TransactionGetter + BlockGetter + CfgGetter, Error>(
 where
     Error: From<InvalidTransaction>,
 {
-    // Check if the transaction's chain id is correct
-    let tx_type = context.tx().tx_type();
+    validate_tx_type_enabled(&context, spec_id)?;
+    validate_tx_chain_id(&context)?;
+    validate_tx_fees(&context)?;
+    validate_tx_type_fields(&context)?;
+    validate_block_gas_limit(&context)?;
+    validate_tx_gas_limit_cap(&context)?;
+    validate_initcode_size(&context, spec_id)?;
+    validate_tx_input_size(&context)?;
+    Ok(())
+}
+
+/// Validate that the transaction type is enabled in the spec.
+pub fn validate_tx_type_enabled<CTX: TransactionGetter>(
+    context: &CTX,
+    spec_id: SpecId,
+) -> Result<(), InvalidTransaction> {
+    let (required, error) = match TransactionType::from(context.tx().tx_type()) {
+        TransactionType::Eip2930 => (SpecId::BERLIN, InvalidTransaction::Eip2930NotSupported),
+        TransactionType::Eip1559 => (SpecId::LONDON, InvalidTransaction::Eip1559NotSupported),
+        TransactionType::Eip4844 => (SpecId::CANCUN, InvalidTransaction::Eip4844NotSupported),
+        TransactionType::Eip7702 => (SpecId::PRAGUE, InvalidTransaction::Eip7702NotSupported),
+        TransactionType::Legacy | TransactionType::Custom => return Ok(()),
+    };
+    if !spec_id.is_enabled_in(required) {
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Validate the chain id of the transaction.
+///
+/// The chain id is optional for legacy transactions, EIP-155 replay protection applies only if
+/// it is present. Custom transaction types are not checked.
+pub fn validate_tx_chain_id<CTX: TransactionGetter + CfgGetter>(
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
     let tx = context.tx();
+    let chain_id = context.cfg().chain_id();
+    let valid = match TransactionType::from(tx.tx_type()) {
+        TransactionType::Legacy => !matches!(tx.chain_id(), Some(id) if id != chain_id),
+        TransactionType::Custom => true,
+        _ => tx.chain_id() == Some(chain_id),
+    };
+    if !valid {
+        return Err(InvalidTransaction::InvalidChainId);
+    }
+    Ok(())
+}
 
+/// Validate the fee fields of the transaction against the block basefee.
+pub fn validate_tx_fees<CTX: TransactionGetter + BlockGetter + CfgGetter>(
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    let tx = context.tx();
     let base_fee = if context.cfg().is_base_fee_check_disabled() {
         None
     } else {
         Some(context.block().basefee() as u128)
     };
 
-    match TransactionType::from(tx_type) {
-        TransactionType::Legacy => {
-            // Check chain_id only if it is present in the legacy transaction.
-            // EIP-155: Simple replay attack protection
-            if let Some(chain_id) = tx.chain_id() {
-                if chain_id != context.cfg().chain_id() {
-                    return Err(InvalidTransaction::InvalidChainId.into());
-                }
-            }
-            // Gas price must be at least the basefee.
-            if let Some(base_fee) = base_fee {
-                if tx.gas_price() < base_fee {
-                    return Err(InvalidTransaction::GasPriceLessThanBasefee.into());
-                }
-            }
-        }
-        TransactionType::Eip2930 => {
-            // Enabled in BERLIN hardfork
-            if !spec_id.is_enabled_in(SpecId::BERLIN) {
-                return Err(InvalidTransaction::Eip2930NotSupported.into());
-            }
-
-            if Some(context.cfg().chain_id()) != tx.chain_id() {
-                return Err(InvalidTransaction::InvalidChainId.into());
-            }
-
+    match TransactionType::from(tx.tx_type()) {
+        TransactionType::Legacy | TransactionType::Eip2930 => {
             // Gas price must be at least the basefee.
             if let Some(base_fee) = base_fee {
                 if tx.gas_price() < base_fee {
-                    return Err(InvalidTransaction::GasPriceLessThanBasefee.into());
+                    return Err(InvalidTransaction::GasPriceLessThanBasefee);
                 }
             }
+            Ok(())
         }
-        TransactionType::Eip1559 => {
-            if !spec_id.is_enabled_in(SpecId::LONDON) {
-                return Err(InvalidTransaction::Eip1559NotSupported.into());
-            }
-
-            if Some(context.cfg().chain_id()) != tx.chain_id() {
-                return Err(InvalidTransaction::InvalidChainId.into());
-            }
-
+        TransactionType::Eip1559 | TransactionType::Eip4844 | TransactionType::Eip7702 => {
             validate_priority_fee_tx(
                 tx.max_fee_per_gas(),
                 tx.max_priority_fee_per_gas().unwrap_or_default(),
                 base_fee,
-            )?;
+            )
         }
-        TransactionType::Eip4844 => {
-            if !spec_id.is_enabled_in(SpecId::CANCUN) {
-                return Err(InvalidTransaction::Eip4844NotSupported.into());
-            }
-
-            if Some(context.cfg().chain_id()) != tx.chain_id() {
-                return Err(InvalidTransaction::InvalidChainId.into());
-            }
-
-            validate_priority_fee_tx(
-                tx.max_fee_per_gas(),
-                tx.max_priority_fee_per_gas().unwrap_or_default(),
-                base_fee,
-            )?;
+        // Custom transaction type check is not done here.
+        TransactionType::Custom => Ok(()),
+    }
+}
 
-            validate_eip4844_tx(
-                tx.blob_versioned_hashes(),
-                tx.max_fee_per_blob_gas(),
-                context.block().blob_gasprice().unwrap_or_default(),
-            )?;
+/// Validate the fields specific to the transaction type, blobs of EIP-4844 transactions and the
+/// authorization list of EIP-7702 transactions.
+pub fn validate_tx_type_fields<CTX: TransactionGetter + BlockGetter>(
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    let tx = context.tx();
+    match TransactionType::from(tx.tx_type()) {
+        TransactionType::Eip4844 => validate_eip4844_tx(
+            tx.blob_versioned_hashes(),
+            tx.max_fee_per_blob_gas(),
+            context.block().blob_gasprice().unwrap_or_default(),
+        ),
+        // The transaction is considered invalid if the length of authorization_list is zero.
+        TransactionType::Eip7702 if tx.authorization_list_len() == 0 => {
+            Err(InvalidTransaction::EmptyAuthorizationList)
         }
-        TransactionType::Eip7702 => {
-            // Check if EIP-7702 transaction is enabled.
-            if !spec_id.is_enabled_in(SpecId::PRAGUE) {
-                return Err(InvalidTransaction::Eip7702NotSupported.into());
-            }
-
-            if Some(context.cfg().chain_id()) != tx.chain_id() {
-                return Err(InvalidTransaction::InvalidChainId.into());
-            }
+        _ => Ok(()),
+    }
+}
 
-            validate_priority_fee_tx(
-                tx.max_fee_per_gas(),
-                tx.max_priority_fee_per_gas().unwrap_or_default(),
-                base_fee,
-            )?;
+/// Validate that the transaction gas limit is not more than the block gas limit.
+pub fn validate_block_gas_limit<CTX: TransactionGetter + BlockGetter + CfgGetter>(
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    if !context.cfg().is_block_gas_limit_disabled()
+        && context.tx().gas_limit() > context.block().gas_limit()
+    {
+        return Err(InvalidTransaction::CallerGasLimitMoreThanBlock);
+    }
+    Ok(())
+}
 
-            let auth_list_len = tx.authorization_list_len();
-            // The transaction is considered invalid if the length of authorization_list is zero.
-            if auth_list_len == 0 {
-                return Err(InvalidTransaction::EmptyAuthorizationList.into());
-            }
-        }
-        TransactionType::Custom => {
-            // Custom transaction type check is not done here.
+/// Validate the transaction gas limit against [`Cfg::max_tx_gas_limit`], the EIP-7825 cap by
+/// default.
+pub fn validate_tx_gas_limit_cap<CTX: TransactionGetter + CfgGetter>(
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    let have = context.tx().gas_limit();
+    if let Some(max) = context.cfg().max_tx_gas_limit() {
+        if have > max {
+            return Err(InvalidTransaction::GasLimitTooHigh { max, have });
         }
-    };
+    }
+    Ok(())
+}
 
-    // Check if gas_limit is more than block_gas_limit
-    if !context.cfg().is_block_gas_limit_disabled() && tx.gas_limit() > context.block().gas_limit()
+/// Validate the initcode size of create transactions (EIP-3860).
+pub fn validate_initcode_size<CTX: TransactionGetter + CfgGetter>(
+    context: &CTX,
+    spec_id: SpecId,
+) -> Result<(), InvalidTransaction> {
+    let tx = context.tx();
+    if spec_id.is_enabled_in(SpecId::SHANGHAI)
+        && tx.kind().is_create()
+        && tx.input().len() > context.cfg().max_initcode_size()
     {
-        return Err(InvalidTransaction::CallerGasLimitMoreThanBlock.into());
+        return Err(InvalidTransaction::CreateInitCodeSizeLimit);
     }
+    Ok(())
+}
 
-    // EIP-3860: Limit and meter initcode
-    if spec_id.is_enabled_in(SpecId::SHANGHAI) && tx.kind().is_create() {
-        let max_initcode_size = context.cfg().max_code_size().saturating_mul(2);
-        if context.tx().input().len() > max_initcode_size {
-            return Err(InvalidTransaction::CreateInitCodeSizeLimit.into());
+/// Validate the transaction data size against [`Cfg::max_tx_input_size`].
+pub fn validate_tx_input_size<CTX: TransactionGetter + CfgGetter>(
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    let have = context.tx().input().len();
+    if let Some(max) = context.cfg().max_tx_input_size() {
+        if have > max {
+            return Err(InvalidTransaction::InputTooLarge { max, have });
         }
     }
-
     Ok(())
 }
 
 /// Validate account against the transaction.
+///
+/// Runs the caller account checks of [`validate_tx_all`] in the same order, stopping at the first
+/// failure.
 #[inline]
 pub fn validate_tx_against_account<CTX: TransactionGetter + CfgGetter, ERROR>(
     account: &Account,
@@ -272,71 +311,90 @@ pub fn validate_tx_against_account<CTX: TransactionGetter + CfgGetter, ERROR>(
 where
     ERROR: From<InvalidTransaction>,
 {
-    let tx = context.tx();
-    let tx_type = context.tx().tx_type();
-    // EIP-3607: Reject transactions from senders with deployed code
+    validate_caller_code(account, context)?;
+    validate_tx_nonce(account, context)?;
+    validate_caller_balance(account, context)?;
+    Ok(())
+}
+
+/// Validate that the caller has no deployed code (EIP-3607).
+///
+/// EOAs whose code is a valid EIP-7702 delegation designation, i.e. `0xef0100 || address`, may
+/// still originate transactions.
+pub fn validate_caller_code<CTX: CfgGetter>(
+    account: &Account,
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
     // This EIP is introduced after london but there was no collision in past
     // so we can leave it enabled always
-    if !context.cfg().is_eip3607_disabled() {
-        let bytecode = &account.info.code.as_ref().unwrap();
-        // Allow EOAs whose code is a valid delegation designation,
-        // i.e. 0xef0100 || address, to continue to originate transactions.
+    if context.cfg().is_eip3607_disabled() {
+        return Ok(());
+    }
+    if let Some(bytecode) = &account.info.code {
         if !bytecode.is_empty() && !bytecode.is_eip7702() {
-            return Err(InvalidTransaction::RejectCallerWithCode.into());
+            return Err(InvalidTransaction::RejectCallerWithCode);
         }
     }
+    Ok(())
+}
 
-    // Check that the transaction's nonce is correct
-    if !context.cfg().is_nonce_check_disabled() {
-        let tx = tx.nonce();
-        let state = account.info.nonce;
-        match tx.cmp(&state) {
-            Ordering::Greater => {
-                return Err(InvalidTransaction::NonceTooHigh { tx, state }.into());
-            }
-            Ordering::Less => {
-                return Err(InvalidTransaction::NonceTooLow { tx, state }.into());
-            }
-            _ => {}
-        }
+/// Validate that the transaction nonce equals the caller nonce.
+pub fn validate_tx_nonce<CTX: TransactionGetter + CfgGetter>(
+    account: &Account,
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    if context.cfg().is_nonce_check_disabled() {
+        return Ok(());
     }
-
-    // gas_limit * max_fee + value
-    let mut balance_check = U256::from(tx.gas_limit())
-        .checked_mul(U256::from(tx.max_fee_per_gas()))
-        .and_then(|gas_cost| gas_cost.checked_add(tx.value()))
-        .ok_or(InvalidTransaction::OverflowPaymentInTransaction)?;
-
-    if tx_type == TransactionType::Eip4844 {
-        let data_fee = tx.calc_max_data_fee();
-        balance_check = balance_check
-            .checked_add(data_fee)
-            .ok_or(InvalidTransaction::OverflowPaymentInTransaction)?;
+    let tx = context.tx().nonce();
+    let state = account.info.nonce;
+    match tx.cmp(&state) {
+        Ordering::Greater => Err(InvalidTransaction::NonceTooHigh { tx, state }),
+        Ordering::Less => Err(InvalidTransaction::NonceTooLow { tx, state }),
+        Ordering::Equal => Ok(()),
     }
+}
+
+/// Validate that the caller can pay `gas_limit * max_fee`, the blob fee and the value.
+///
+/// Only the value is required if the fee charge is disabled.
+pub fn validate_caller_balance<CTX: TransactionGetter + CfgGetter>(
+    account: &Account,
+    context: &CTX,
+) -> Result<(), InvalidTransaction> {
+    let tx = context.tx();
+    // gas_limit * max_fee + blob fee + value, only the value if gas is not charged.
+    let balance_check = if context.cfg().is_fee_charge_disabled() {
+        tx.value()
+    } else {
+        tx.max_fee_cost()
+            .checked_add(tx.value())
+            .ok_or(InvalidTransaction::OverflowPaymentInTransaction)?
+    };
 
-    // Check if account has enough balance for `gas_limit * max_fee`` and value transfer.
     // Transfer will be done inside `*_inner` functions.
     if balance_check > account.info.balance && !context.cfg().is_balance_check_disabled() {
         return Err(InvalidTransaction::LackOfFundForMaxFee {
             fee: Box::new(balance_check),
             balance: Box::new(account.info.balance),
-        }
-        .into());
+        });
     }
-
     Ok(())
 }
 
 /// Validate initial transaction gas.
+///
+/// Since Prague the gas limit must also cover the EIP-7623 calldata floor, unless it is
+/// disabled in the configuration.
 pub fn validate_initial_tx_gas<CTX, Error>(context: CTX, spec_id: SpecId) -> Result<u64, Error>
 where
-    CTX: TransactionGetter,
+    CTX: TransactionGetter + CfgGetter,
     Error: From<InvalidTransaction>,
 {
     let tx = context.tx();
     let (accounts, storages) = tx.access_list_nums().unwrap_or_default();
 
-    let initial_gas_spend = gas::validate_initial_tx_gas(
+    let gas = gas::calculate_initial_tx_gas(
         spec_id,
         tx.input(),
         tx.kind().is_create(),
@@ -346,10 +404,83 @@ where
     );
 
     // Additional check to see if limit is big enough to cover initial gas.
-    if initial_gas_spend > tx.gas_limit() {
+    if gas.initial_gas > tx.gas_limit() {
         return Err(InvalidTransaction::CallGasCostMoreThanGasLimit.into());
     }
-    Ok(initial_gas_spend)
+
+    // EIP-7623: Increase calldata cost
+    if !context.cfg().is_calldata_floor_disabled() && gas.floor_gas > tx.gas_limit() {
+        return Err(InvalidTransaction::GasFloorMoreThanGasLimit.into());
+    }
+    Ok(gas.initial_gas)
+}
+
+/// Returns the EIP-7623 floor gas of a transaction with the given calldata, zero before Prague.
+///
+/// Only the calldata is priced, at 10 gas per zero byte and 40 gas per non-zero byte on top of
+/// the 21000 base cost. Access list, initcode and authorization costs do not affect the floor.
+pub fn calldata_floor_gas(input: &[u8], spec: SpecId) -> u64 {
+    if !spec.is_enabled_in(SpecId::PRAGUE) {
+        return 0;
+    }
+    gas::calc_tx_floor_cost(gas::get_tokens_in_calldata(input, true))
+}
+
+/// Validates transaction against block, configuration and caller account, returning all failures.
+///
+/// Unlike [`validate_tx_env`], [`validate_tx_against_account`] and [`validate_initial_tx_gas`]
+/// this does not stop at the first error. Each check reports at most one failure, in this order:
+///
+/// 1. [`validate_tx_type_enabled`]
+/// 2. [`validate_tx_chain_id`]
+/// 3. [`validate_tx_fees`]
+/// 4. [`validate_tx_type_fields`]
+/// 5. [`validate_block_gas_limit`]
+/// 6. [`validate_tx_gas_limit_cap`]
+/// 7. [`validate_initcode_size`]
+/// 8. [`validate_tx_input_size`]
+/// 9. [`validate_initial_tx_gas`]
+/// 10. [`validate_caller_code`]
+/// 11. [`validate_tx_nonce`]
+/// 12. [`validate_caller_balance`]
+///
+/// Checks 1 to 11 are independent and always run. The balance check prices the transaction from
+/// its fee fields, blob fee and gas limit, so it depends on checks 3 to 6 and is skipped if any
+/// of them failed.
+pub fn validate_tx_all<CTX: TransactionGetter + BlockGetter + CfgGetter>(
+    context: &CTX,
+    spec_id: SpecId,
+    account: &Account,
+) -> Vec<InvalidTransaction> {
+    let pricing = [
+        validate_tx_fees(context),
+        validate_tx_type_fields(context),
+        validate_block_gas_limit(context),
+        validate_tx_gas_limit_cap(context),
+    ];
+    // A balance shortfall computed from invalid fees or gas limit is not meaningful.
+    let balance = if pricing.iter().all(Result::is_ok) {
+        validate_caller_balance(account, context)
+    } else {
+        Ok(())
+    };
+
+    [
+        validate_tx_type_enabled(context, spec_id),
+        validate_tx_chain_id(context),
+    ]
+    .into_iter()
+    .chain(pricing)
+    .chain([
+        validate_initcode_size(context, spec_id),
+        validate_tx_input_size(context),
+        validate_initial_tx_gas(context, spec_id).map(drop),
+        validate_caller_code(account, context),
+        validate_tx_nonce(account, context),
+        balance,
+    ])
+    .filter_map(Result::err)
+    .collect()
 }
 
 /// Helper trait that summarizes ValidationHandler requirements from Context.
@@ -372,3 +503,252 @@ impl<
     > EthValidationError<CTX> for T
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::transact;
+    use bytecode::{
+        opcode::{PUSH1, SSTORE, STOP},
+        Bytecode,
+    };
+    use context::Context;
+    use context_interface::result::EVMError;
+    use database::BenchmarkDB;
+    use primitives::{Address, Bytes, TxKind, U256};
+    use state::AccountInfo;
+    use std::vec;
+
+    #[test]
+    fn validate_tx_all_collects_errors() {
+        let context = Context::builder()
+            .modify_block_chained(|block| block.gas_limit = 1_000_000)
+            .modify_tx_chained(|tx| {
+                tx.chain_id = Some(2);
+                tx.nonce = 5;
+                tx.gas_limit = 2_000_000;
+            });
+        let account = Account::from(AccountInfo::default());
+
+        assert_eq!(
+            validate_tx_all(&context, SpecId::PRAGUE, &account),
+            vec![
+                InvalidTransaction::InvalidChainId,
+                InvalidTransaction::CallerGasLimitMoreThanBlock,
+                InvalidTransaction::NonceTooHigh { tx: 5, state: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_tx_all_matches_fail_fast_checks() {
+        let context = Context::builder()
+            .modify_cfg_chained(|cfg| {
+                cfg.max_tx_gas_limit = Some(100_000);
+                cfg.max_tx_input_size = Some(1);
+            })
+            .modify_tx_chained(|tx| {
+                tx.gas_limit = 100_001;
+                tx.data = Bytes::from_static(&[1, 2]);
+            });
+        let account = Account::from(AccountInfo::default());
+
+        let errors = validate_tx_all(&context, SpecId::PRAGUE, &account);
+        assert_eq!(
+            errors,
+            vec![
+                InvalidTransaction::GasLimitTooHigh {
+                    max: 100_000,
+                    have: 100_001
+                },
+                InvalidTransaction::InputTooLarge { max: 1, have: 2 },
+            ]
+        );
+        assert_eq!(
+            validate_tx_env::<_, InvalidTransaction>(&context, SpecId::PRAGUE),
+            Err(errors[0].clone())
+        );
+    }
+
+    #[test]
+    fn validate_tx_all_skips_balance_after_gas_limit_failure() {
+        let mut context = Context::builder()
+            .modify_block_chained(|block| block.gas_limit = 1_000_000)
+            .modify_tx_chained(|tx| {
+                tx.gas_price = 1;
+                tx.gas_limit = 2_000_000;
+            });
+        let account = Account::from(AccountInfo::default());
+
+        assert_eq!(
+            validate_tx_all(&context, SpecId::PRAGUE, &account),
+            vec![InvalidTransaction::CallerGasLimitMoreThanBlock]
+        );
+
+        context.modify_tx(|tx| tx.gas_limit = 1_000_000);
+        assert_eq!(
+            validate_tx_all(&context, SpecId::PRAGUE, &account),
+            vec![InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(U256::from(1_000_000)),
+                balance: Box::new(U256::ZERO),
+            }]
+        );
+    }
+
+    #[test]
+    fn calldata_floor_gas_prices_tokens() {
+        assert_eq!(
+            calldata_floor_gas(&[0; 100], SpecId::PRAGUE),
+            21000 + 100 * 10
+        );
+        assert_eq!(
+            calldata_floor_gas(&[1; 100], SpecId::PRAGUE),
+            21000 + 100 * 40
+        );
+        assert_eq!(calldata_floor_gas(&[], SpecId::PRAGUE), 21000);
+        assert_eq!(calldata_floor_gas(&[1; 100], SpecId::CANCUN), 0);
+    }
+
+    #[test]
+    fn max_fee_cost_near_u256_max() {
+        let mut context = Context::builder().modify_tx_chained(|tx| {
+            tx.tx_type = TransactionType::Eip4844.into();
+            tx.gas_limit = u64::MAX;
+            tx.gas_price = u128::MAX;
+            tx.max_fee_per_blob_gas = u128::MAX;
+            tx.blob_hashes = vec![B256::ZERO; 6];
+        });
+        let gas_cost = U256::from(u64::MAX) * U256::from(u128::MAX);
+        let blob_fee = U256::from(u128::MAX) * U256::from(6 * eip4844::GAS_PER_BLOB);
+        assert_eq!(context.tx().max_fee_cost(), gas_cost + blob_fee);
+
+        context.modify_tx(|tx| tx.value = U256::MAX);
+        let account = Account::from(AccountInfo::default());
+        assert_eq!(
+            validate_tx_against_account::<_, InvalidTransaction>(&account, &context),
+            Err(InvalidTransaction::OverflowPaymentInTransaction)
+        );
+    }
+
+    #[test]
+    fn max_initcode_size_override() {
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::default())
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Create;
+                tx.gas_limit = 10_000_000;
+                tx.data = vec![STOP; 60 * 1024].into();
+            });
+        assert!(matches!(
+            transact(&mut context),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CreateInitCodeSizeLimit
+            ))
+        ));
+
+        context.modify_cfg(|cfg| cfg.max_initcode_size = Some(128 * 1024));
+        assert!(transact(&mut context).unwrap().result.is_success());
+    }
+
+    #[test]
+    fn max_tx_input_size_rejects_large_call_data() {
+        let mut context = Context::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, PUSH1, 0x00, SSTORE].into(),
+            )))
+            .modify_cfg_chained(|cfg| cfg.max_tx_input_size = Some(128 * 1024))
+            .modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 10_000_000;
+                tx.data = vec![0xff; 200 * 1024].into();
+            });
+        assert!(matches!(
+            transact(&mut context),
+            Err(EVMError::Transaction(InvalidTransaction::InputTooLarge {
+                max: 131072,
+                have: 204800,
+            }))
+        ));
+
+        context.modify_tx(|tx| tx.data = vec![0xff; 128 * 1024].into());
+        assert!(transact(&mut context).unwrap().result.is_success());
+    }
+
+    #[test]
+    fn tx_gas_limit_above_cap_is_rejected() {
+        let run = |spec, max_tx_gas_limit, gas_limit| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    [STOP].into(),
+                )))
+                .modify_cfg_chained(|cfg| {
+                    cfg.spec = spec;
+                    cfg.max_tx_gas_limit = max_tx_gas_limit;
+                })
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.gas_limit = gas_limit;
+                });
+            transact(&mut context).map(|output| output.result)
+        };
+
+        assert!(matches!(
+            run(SpecId::PRAGUE, Some(50_000), 50_001),
+            Err(EVMError::Transaction(InvalidTransaction::GasLimitTooHigh {
+                max: 50_000,
+                have: 50_001
+            }))
+        ));
+        assert!(run(SpecId::PRAGUE, Some(50_000), 50_000)
+            .unwrap()
+            .is_success());
+
+        // EIP-7825 caps transactions from Osaka by default.
+        let cap = 1 << 24;
+        assert!(run(SpecId::PRAGUE, None, cap + 1).unwrap().is_success());
+        assert!(matches!(
+            run(SpecId::OSAKA, None, cap + 1),
+            Err(EVMError::Transaction(InvalidTransaction::GasLimitTooHigh { max, .. }))
+                if max == cap
+        ));
+        assert!(matches!(
+            run(SpecId::LATEST, None, cap + 1),
+            Err(EVMError::Transaction(InvalidTransaction::GasLimitTooHigh { max, .. }))
+                if max == cap
+        ));
+        assert!(run(SpecId::LATEST, None, cap).unwrap().is_success());
+    }
+
+    #[test]
+    fn calldata_floor_can_be_disabled() {
+        let run = |disable_calldata_floor, gas_limit| {
+            let mut context = Context::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    [STOP].into(),
+                )))
+                .modify_cfg_chained(|cfg| cfg.disable_calldata_floor = disable_calldata_floor)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(Address::ZERO);
+                    tx.data = vec![0xff; 1000].into();
+                    tx.gas_limit = gas_limit;
+                });
+            transact(&mut context).map(|r| r.result.gas_used())
+        };
+
+        // 16 gas per non-zero byte for execution, 40 gas per non-zero byte for the floor.
+        assert_eq!(run(false, 100_000).unwrap(), 21_000 + 40 * 1000);
+        assert_eq!(run(true, 100_000).unwrap(), 21_000 + 16 * 1000);
+
+        assert!(matches!(
+            run(false, 50_000),
+            Err(EVMError::Transaction(
+                InvalidTransaction::GasFloorMoreThanGasLimit
+            ))
+        ));
+        assert_eq!(run(true, 50_000).unwrap(), 21_000 + 16 * 1000);
+    }
+}
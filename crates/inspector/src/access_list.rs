@@ -0,0 +1,157 @@
+//! AccessListInspector. Builds the EIP-2930 access list of the accounts and slots touched by a transaction.
+use crate::Inspector;
+use revm::{
+    bytecode::opcode,
+    interpreter::{
+        interpreter_types::{InputsTrait, Jumps, StackTrait},
+        CallInputs, CallOutcome, Interpreter, InterpreterTypes,
+    },
+    primitives::{Address, B256},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+/// [Inspector] that records every account and storage slot accessed during execution, similar
+/// to the `eth_createAccessList` RPC.
+///
+/// Accounts are collected from calls and from the BALANCE, EXTCODE* and SELFDESTRUCT opcodes,
+/// storage slots from SLOAD and SSTORE. As in the RPC, the transaction sender, recipient and
+/// precompiles are not added as accounts, but storage slots of the recipient are still listed.
+#[derive(Clone, Debug, Default)]
+pub struct AccessListInspector {
+    /// Addresses that are warm regardless of the access list.
+    excluded: BTreeSet<Address>,
+    access_list: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl AccessListInspector {
+    /// Creates a new inspector that does not add `from`, `to` and `precompiles` as accounts.
+    pub fn new(
+        from: Address,
+        to: Option<Address>,
+        precompiles: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            excluded: [from].into_iter().chain(to).chain(precompiles).collect(),
+            access_list: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the access list in the transaction format, sorted by address and slot.
+    pub fn access_list(&self) -> Vec<(Address, Vec<B256>)> {
+        self.access_list
+            .iter()
+            .map(|(address, slots)| (*address, slots.iter().copied().collect()))
+            .collect()
+    }
+
+    fn add_address(&mut self, address: Address) {
+        if !self.excluded.contains(&address) {
+            self.access_list.entry(address).or_default();
+        }
+    }
+
+    fn add_slot(&mut self, address: Address, slot: B256) {
+        self.access_list.entry(address).or_default().insert(slot);
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for AccessListInspector {
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        let Some(top) = interp.stack.top().map(|value| B256::from(*value)) else {
+            return;
+        };
+        match interp.bytecode.opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                self.add_slot(interp.input.target_address(), top);
+            }
+            opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::SELFDESTRUCT => {
+                self.add_address(Address::from_word(top));
+            }
+            _ => (),
+        }
+    }
+
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.add_address(inputs.target_address);
+        self.add_address(inputs.bytecode_address);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::Bytecode,
+        database_interface::EmptyDB,
+        precompile::{PrecompileSpecId, Precompiles},
+        primitives::{address, TxKind},
+        specification::hardfork::SpecId,
+        state::AccountInfo,
+        Context,
+    };
+    use std::vec;
+
+    #[test]
+    fn access_list_of_multi_sload_contract() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let contract = address!("000000000000000000000000000000000000aaaa");
+        let other = address!("000000000000000000000000000000000000bbbb");
+
+        let mut code = Vec::new();
+        for slot in 1..=3 {
+            code.extend_from_slice(&[opcode::PUSH1, slot, opcode::SLOAD, opcode::POP]);
+        }
+        for target in [other, address!("0000000000000000000000000000000000000002")] {
+            code.push(opcode::PUSH20);
+            code.extend_from_slice(target.as_slice());
+            code.extend_from_slice(&[opcode::BALANCE, opcode::POP]);
+        }
+        code.push(opcode::STOP);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        let precompiles = Precompiles::new(PrecompileSpecId::from_spec_id(SpecId::PRAGUE))
+            .addresses()
+            .copied();
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                }),
+                AccessListInspector::new(caller, Some(contract), precompiles),
+            ),
+            inspector_handler(),
+        );
+        let gas_used = evm.transact().unwrap().result.gas_used();
+
+        let access_list = evm.context.inspector.access_list();
+        let slots = (1..=3u8).map(B256::with_last_byte).collect();
+        assert_eq!(access_list, vec![(contract, slots), (other, Vec::new())]);
+
+        // Contract is warm and pays only for listing. Slots and `other` are charged the
+        // intrinsic cost (2400 per address, 1900 per slot) instead of the cold access surcharge
+        // (2500 per account, 2000 per slot).
+        evm.context.inner.tx.access_list = access_list;
+        let gas_used_with_list = evm.transact().unwrap().result.gas_used();
+        assert_eq!(
+            gas_used_with_list,
+            gas_used + 2 * 2400 + 3 * 1900 - 2500 - 3 * 2000
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! BlockTraceInspector. Captures the call trees, results and state diffs of the transactions of a block.
+use crate::Inspector;
+use revm::{
+    context_interface::{
+        result::{ExecutionResult, HaltReason, ResultAndState},
+        CreateScheme,
+    },
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, EOFCreateInputs,
+        EOFCreateKind, InstructionResult, InterpreterResult, InterpreterTypes,
+    },
+    primitives::{Address, Bytes, B256, U256},
+};
+use std::vec::Vec;
+
+/// Execution trace of a block, assembled by [`BlockTraceInspector`].
+///
+/// Holds everything needed to render the call trees and state diffs of the block without access
+/// to the state it was executed on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockTrace {
+    /// Traces of the transactions, in execution order.
+    pub transactions: Vec<TxTrace>,
+}
+
+/// Execution trace of a single transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TxTrace {
+    /// Result of the transaction, including its gas and logs.
+    pub result: ExecutionResult<HaltReason>,
+    /// Frames of the transaction in pre-order, the tree is given by their depth.
+    pub calls: Vec<CallFrame>,
+    /// Accounts changed by the transaction, sorted by address.
+    pub state_diff: Vec<AccountDiff>,
+}
+
+/// Kind of a [`CallFrame`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameKind {
+    /// Message call.
+    Call(CallScheme),
+    /// `CREATE` or `CREATE2`.
+    Create(CreateScheme),
+    /// `EOFCREATE` or EOF creation transaction.
+    EofCreate,
+}
+
+/// Call or create frame of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallFrame {
+    /// Depth of the frame, the top level frame has depth zero.
+    pub depth: usize,
+    pub kind: FrameKind,
+    pub from: Address,
+    /// Called or created address, `None` if creation failed.
+    pub to: Option<Address>,
+    pub value: U256,
+    /// Call data or init code.
+    pub input: Bytes,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    /// Return data, or the revert reason.
+    pub output: Bytes,
+    pub result: InstructionResult,
+}
+
+/// State of an account changed by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountDiff {
+    pub address: Address,
+    /// Balance after the transaction.
+    pub balance: U256,
+    /// Nonce after the transaction.
+    pub nonce: u64,
+    pub code_hash: B256,
+    /// Code of the account, only present if it was created by the transaction.
+    pub code: Option<Bytes>,
+    /// Changed slots as `(slot, original, present)`, sorted by slot.
+    pub storage: Vec<(U256, U256, U256)>,
+    pub created: bool,
+    pub destroyed: bool,
+}
+
+/// [Inspector] that assembles a [`BlockTrace`].
+///
+/// Call frames are recorded during execution, and the result and state diff of each transaction
+/// are added with [`BlockTraceInspector::record_tx`].
+#[derive(Clone, Debug, Default)]
+pub struct BlockTraceInspector {
+    /// Indices of the frames that have not ended yet.
+    stack: Vec<usize>,
+    calls: Vec<CallFrame>,
+    trace: BlockTrace,
+}
+
+impl BlockTraceInspector {
+    /// Creates an inspector with an empty trace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the transaction executed since the last call, with the frames recorded during it.
+    pub fn record_tx(&mut self, output: &ResultAndState<HaltReason>) {
+        let mut state_diff: Vec<_> = output
+            .state
+            .iter()
+            .filter(|(_, account)| account.is_touched())
+            .map(|(address, account)| {
+                let mut storage: Vec<_> = account
+                    .changed_storage_slots()
+                    .map(|(slot, value)| (*slot, value.original_value(), value.present_value()))
+                    .collect();
+                storage.sort_unstable_by_key(|(slot, ..)| *slot);
+                AccountDiff {
+                    address: *address,
+                    balance: account.info.balance,
+                    nonce: account.info.nonce,
+                    code_hash: account.info.code_hash,
+                    code: account
+                        .info
+                        .code
+                        .as_ref()
+                        .filter(|_| account.is_created())
+                        .map(|code| code.original_bytes()),
+                    storage,
+                    created: account.is_created(),
+                    destroyed: account.is_selfdestructed(),
+                }
+            })
+            .collect();
+        state_diff.sort_unstable_by_key(|diff| diff.address);
+
+        self.stack.clear();
+        self.trace.transactions.push(TxTrace {
+            result: output.result.clone(),
+            calls: core::mem::take(&mut self.calls),
+            state_diff,
+        });
+    }
+
+    /// Returns the trace of the transactions recorded so far.
+    pub fn trace(&self) -> &BlockTrace {
+        &self.trace
+    }
+
+    /// Consumes the inspector and returns the block trace.
+    pub fn into_trace(self) -> BlockTrace {
+        self.trace
+    }
+
+    fn frame_start(
+        &mut self,
+        kind: FrameKind,
+        from: Address,
+        to: Option<Address>,
+        value: U256,
+        input: Bytes,
+        gas_limit: u64,
+    ) {
+        self.stack.push(self.calls.len());
+        self.calls.push(CallFrame {
+            depth: self.stack.len() - 1,
+            kind,
+            from,
+            to,
+            value,
+            input,
+            gas_limit,
+            gas_used: 0,
+            output: Bytes::new(),
+            result: InstructionResult::Continue,
+        });
+    }
+
+    fn frame_end(&mut self, result: &InterpreterResult, address: Option<Address>) {
+        let Some(frame) = self.stack.pop().map(|index| &mut self.calls[index]) else {
+            return;
+        };
+        frame.gas_used = result.gas.spent();
+        frame.output = result.output.clone();
+        frame.result = result.result;
+        if address.is_some() {
+            frame.to = address;
+        }
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for BlockTraceInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start(
+            FrameKind::Call(inputs.scheme),
+            inputs.caller,
+            Some(inputs.target_address),
+            inputs.call_value(),
+            inputs.input.clone(),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.frame_end(&outcome.result, None);
+    }
+
+    fn create(&mut self, _context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start(
+            FrameKind::Create(inputs.scheme),
+            inputs.caller,
+            None,
+            inputs.value,
+            inputs.init_code.clone(),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(&outcome.result, outcome.address);
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut CTX,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        let input = match &inputs.kind {
+            EOFCreateKind::Tx { initdata } => initdata.clone(),
+            EOFCreateKind::Opcode { initcode, .. } => initcode.raw.clone(),
+        };
+        self.frame_start(
+            FrameKind::EofCreate,
+            inputs.caller,
+            None,
+            inputs.value,
+            input,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &EOFCreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(&outcome.result, outcome.address);
+    }
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        context::TxEnv,
+        database_interface::{DatabaseCommit, DatabaseGetter, EmptyDB},
+        primitives::{address, TxKind},
+        state::AccountInfo,
+        Context, EvmExec,
+    };
+
+    #[test]
+    fn block_trace_round_trips_through_serde() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let contract = address!("000000000000000000000000000000000000aaaa");
+        let callee = address!("000000000000000000000000000000000000bbbb");
+
+        // Increments slot zero, logs and calls `callee`.
+        let mut code = vec![
+            opcode::PUSH1,
+            0x00,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x01,
+            opcode::ADD,
+            opcode::PUSH1,
+            0x00,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::LOG0,
+        ];
+        code.extend_from_slice(&[opcode::PUSH1, 0x00].repeat(5));
+        code.push(opcode::PUSH20);
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_info(
+            callee,
+            AccountInfo::from_bytecode(Bytecode::new_legacy([opcode::STOP].into())),
+        );
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(Context::builder().with_db(db), BlockTraceInspector::new()),
+            inspector_handler(),
+        );
+        for nonce in 0..2 {
+            let output = evm
+                .exec_with_tx(TxEnv {
+                    caller,
+                    kind: TxKind::Call(contract),
+                    nonce,
+                    gas_limit: 100_000,
+                    ..Default::default()
+                })
+                .unwrap();
+            evm.context.inspector.record_tx(&output);
+            evm.context.db().commit(output.state);
+        }
+
+        let trace = evm.context.inspector.trace().clone();
+        assert_eq!(trace.transactions.len(), 2);
+        for (index, tx) in trace.transactions.iter().enumerate() {
+            assert!(tx.result.is_success());
+            assert_eq!(tx.result.logs().len(), 1);
+            assert_eq!(
+                tx.calls
+                    .iter()
+                    .map(|call| (call.depth, call.to))
+                    .collect::<Vec<_>>(),
+                [(0, Some(contract)), (1, Some(callee))]
+            );
+            let diff = tx
+                .state_diff
+                .iter()
+                .find(|diff| diff.address == contract)
+                .unwrap();
+            let counter = U256::from(index);
+            assert_eq!(
+                diff.storage,
+                [(U256::ZERO, counter, counter + U256::from(1))]
+            );
+        }
+
+        let json = serde_json::to_string(&trace).unwrap();
+        let decoded: BlockTrace = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, trace);
+    }
+}
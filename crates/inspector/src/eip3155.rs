@@ -9,6 +9,7 @@ use revm::{
         InterpreterTypes, Stack,
     },
     primitives::{hex, HashMap, B256, U256},
+    specification::hardfork::SpecId,
 };
 use serde::Serialize;
 use std::io::Write;
@@ -19,6 +20,8 @@ pub struct TracerEip3155<CTX, INTR> {
     gas_inspector: GasInspector,
     /// Print summary of the execution.
     print_summary: bool,
+    /// Fork name of the summary, the name of the spec if not set.
+    fork_name: Option<String>,
     stack: Vec<U256>,
     pc: usize,
     opcode: u8,
@@ -136,6 +139,7 @@ where
             output,
             gas_inspector: GasInspector::new(),
             print_summary: true,
+            fork_name: None,
             include_memory: false,
             stack: Default::default(),
             memory: Default::default(),
@@ -155,6 +159,12 @@ where
         self
     }
 
+    /// Sets the fork name reported in the summary, instead of the name of the spec.
+    pub fn with_fork_name(mut self, fork_name: String) -> Self {
+        self.fork_name = Some(fork_name);
+        self
+    }
+
     /// Include a memory field for each step. This significantly increases processing time and output size.
     pub fn with_memory(mut self) -> Self {
         self.include_memory = true;
@@ -169,7 +179,7 @@ where
 
     fn print_summary(&mut self, result: &InterpreterResult, context: &mut CTX) {
         if self.print_summary {
-            let spec = context.cfg().spec().into();
+            let spec: SpecId = context.cfg().spec().into();
             let gas_limit = context.tx().gas_limit();
             let value = Summary {
                 state_root: B256::ZERO.to_string(),
@@ -177,7 +187,11 @@ where
                 gas_used: hex_number(gas_limit - self.gas_inspector.gas_remaining()),
                 pass: result.is_ok(),
                 time: None,
-                fork: Some(spec.to_string()),
+                fork: Some(
+                    self.fork_name
+                        .clone()
+                        .unwrap_or_else(|| spec.as_str().to_string()),
+                ),
             };
             let _ = self.write_value(&value);
         }
@@ -286,3 +300,66 @@ fn hex_number_u256(b: &U256) -> String {
         format!("0x{s}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{
+            opcode::{PUSH1, STOP},
+            Bytecode,
+        },
+        primitives::{Address, TxKind},
+        Context,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn summary(fork_name: Option<&str>) -> String {
+        let buffer = SharedBuffer::default();
+        let mut tracer = TracerEip3155::new(Box::new(buffer.clone()));
+        if let Some(fork_name) = fork_name {
+            tracer = tracer.with_fork_name(fork_name.to_string());
+        }
+        let code = [PUSH1, 0x01, STOP];
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder()
+                    .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+                    .modify_cfg_chained(|cfg| cfg.spec = SpecId::CANCUN)
+                    .modify_tx_chained(|tx| {
+                        tx.caller = Address::with_last_byte(1);
+                        tx.kind = TxKind::Call(Address::ZERO);
+                        tx.gas_limit = 100_000;
+                    }),
+                tracer,
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let output = String::from_utf8(buffer.0.take()).unwrap();
+        output.lines().last().unwrap().to_string()
+    }
+
+    #[test]
+    fn summary_includes_fork_name() {
+        assert!(summary(None).contains(r#""fork":"Cancun""#));
+        assert!(summary(Some("Cancun-devnet")).contains(r#""fork":"Cancun-devnet""#));
+    }
+}
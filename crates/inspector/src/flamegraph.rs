@@ -0,0 +1,214 @@
+//! FlamegraphInspector. Collects gas weighted call stacks in the folded stack format.
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InterpreterTypes,
+    },
+    primitives::Address,
+};
+use std::{collections::BTreeMap, fmt::Write, string::String, vec::Vec};
+
+/// Frame on the active call stack.
+#[derive(Clone, Debug)]
+struct Frame {
+    /// Index of the frame address in [`FlamegraphInspector::addresses`].
+    id: usize,
+    /// Gas spent by the finished child frames.
+    child_gas: u64,
+}
+
+/// [Inspector] that produces folded stacks (`addr1;addr2;addr3 gas`) weighted by gas, as
+/// consumed by `flamegraph.pl` and compatible tools.
+///
+/// Every frame is attributed its self gas, that is the gas spent by the frame minus the gas
+/// spent by its child frames. Stacks that occur more than once are merged.
+///
+/// Create frames are named after the created address, or the zero address if creation failed.
+#[derive(Clone, Debug, Default)]
+pub struct FlamegraphInspector {
+    /// Addresses of all frames seen so far, indexed by frame id.
+    addresses: Vec<Address>,
+    stack: Vec<Frame>,
+    /// Self gas of finished frames, keyed by the frame ids of their stack.
+    samples: Vec<(Vec<usize>, u64)>,
+}
+
+impl FlamegraphInspector {
+    /// Creates an inspector with no recorded samples.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns folded stacks, one stack per line, sorted by stack.
+    ///
+    /// Stacks with zero self gas are omitted.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut stacks = BTreeMap::<String, u64>::new();
+        for (ids, gas) in &self.samples {
+            let mut stack = String::new();
+            for (i, id) in ids.iter().enumerate() {
+                if i != 0 {
+                    stack.push(';');
+                }
+                let _ = write!(stack, "{}", self.addresses[*id]);
+            }
+            *stacks.entry(stack).or_default() += gas;
+        }
+
+        let mut out = String::new();
+        for (stack, gas) in stacks {
+            if gas != 0 {
+                let _ = writeln!(out, "{stack} {gas}");
+            }
+        }
+        out
+    }
+
+    fn frame_start(&mut self, address: Address) {
+        self.stack.push(Frame {
+            id: self.addresses.len(),
+            child_gas: 0,
+        });
+        self.addresses.push(address);
+    }
+
+    fn frame_end(&mut self, spent: u64) {
+        let ids = self.stack.iter().map(|frame| frame.id).collect();
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        if let Some(parent) = self.stack.last_mut() {
+            parent.child_gas += spent;
+        }
+        self.samples
+            .push((ids, spent.saturating_sub(frame.child_gas)));
+    }
+
+    fn create_end(&mut self, outcome: &CreateOutcome) {
+        if let (Some(frame), Some(address)) = (self.stack.last(), outcome.address) {
+            self.addresses[frame.id] = address;
+        }
+        self.frame_end(outcome.result.gas.spent());
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for FlamegraphInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start(inputs.target_address);
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.frame_end(outcome.result.gas.spent());
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start(Address::ZERO);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        FlamegraphInspector::create_end(self, outcome);
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start(Address::ZERO);
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &EOFCreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        FlamegraphInspector::create_end(self, outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, Bytes, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+
+    #[test]
+    fn attributes_self_gas_to_frames() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let outer = address!("000000000000000000000000000000000000aaaa");
+        let inner = address!("000000000000000000000000000000000000bbbb");
+
+        // Outer calls inner twice, inner spends 11 gas each time.
+        let mut outer_code = Vec::new();
+        for _ in 0..2 {
+            outer_code.extend([opcode::PUSH1, 0x00].repeat(5));
+            outer_code.push(opcode::PUSH20);
+            outer_code.extend_from_slice(inner.as_slice());
+            outer_code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::POP]);
+        }
+        outer_code.push(opcode::STOP);
+        let inner_code = [
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x02,
+            opcode::ADD,
+            opcode::POP,
+            opcode::STOP,
+        ];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(outer, outer_code), (inner, inner_code.to_vec())] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from(code))),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Call(outer);
+                    tx.gas_limit = 1_000_000;
+                }),
+                FlamegraphInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        let gas_used = evm.transact().unwrap().result.gas_used();
+
+        let folded = evm.context.inspector.to_folded_stacks();
+        let lines: Vec<(&str, u64)> = folded
+            .lines()
+            .map(|line| {
+                let (stack, gas) = line.rsplit_once(' ').unwrap();
+                (stack, gas.parse().unwrap())
+            })
+            .collect();
+        let outer_gas = gas_used - 21_000 - 2 * 11;
+        assert_eq!(
+            lines,
+            vec![
+                (outer.to_string().as_str(), outer_gas),
+                (format!("{outer};{inner}").as_str(), 22),
+            ]
+        );
+    }
+}
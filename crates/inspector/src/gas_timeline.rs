@@ -0,0 +1,144 @@
+//! GasTimelineInspector. Records gas remaining before every executed instruction.
+use crate::Inspector;
+use revm::{
+    context_interface::{Journal, JournalGetter},
+    interpreter::{
+        interpreter_types::{Jumps, LoopControl},
+        Interpreter, InterpreterTypes,
+    },
+};
+use std::vec::Vec;
+
+/// Gas remaining before an executed instruction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GasPoint {
+    /// Index of the instruction across the whole execution.
+    pub step: u64,
+    /// Program counter of the instruction.
+    pub pc: u32,
+    /// Call depth of the frame, the top level frame has depth 1.
+    pub depth: u32,
+    /// Gas remaining in the frame before the instruction is executed.
+    pub gas_remaining: u64,
+}
+
+/// [Inspector] that records a [`GasPoint`] for every executed instruction, for plotting how
+/// gas is depleted over a transaction.
+///
+/// Points of all frames are recorded in execution order. Gas remaining is local to the frame,
+/// so points should be filtered by depth to get a continuous series.
+#[derive(Clone, Debug, Default)]
+pub struct GasTimelineInspector {
+    points: Vec<GasPoint>,
+}
+
+impl GasTimelineInspector {
+    /// Creates an inspector with no recorded points.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns recorded points in execution order.
+    pub fn points(&self) -> &[GasPoint] {
+        &self.points
+    }
+
+    /// Consumes the inspector and returns recorded points.
+    pub fn into_points(self) -> Vec<GasPoint> {
+        self.points
+    }
+
+    /// Clears the recorded points.
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+}
+
+impl<CTX, INTR> Inspector<CTX, INTR> for GasTimelineInspector
+where
+    CTX: JournalGetter,
+    INTR: InterpreterTypes,
+{
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter<INTR>, context: &mut CTX) {
+        self.points.push(GasPoint {
+            step: self.points.len() as u64,
+            pc: interp.bytecode.pc() as u32,
+            depth: context.journal().depth() as u32,
+            gas_remaining: interp.control.gas().remaining(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, Bytes, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+    use std::vec;
+
+    #[test]
+    fn gas_decreases_within_frame() {
+        let caller = address!("000000000000000000000000000000000000aaaa");
+        let callee = address!("000000000000000000000000000000000000bbbb");
+
+        // Calls the callee with zeroed arguments and all remaining gas.
+        let mut caller_code = [opcode::PUSH1, 0x00].repeat(5);
+        caller_code.push(opcode::PUSH20);
+        caller_code.extend_from_slice(callee.as_slice());
+        caller_code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::POP, opcode::STOP]);
+        let callee_code = vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::STOP,
+        ];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(caller, caller_code), (callee, callee_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from(code))),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.kind = TxKind::Call(caller);
+                    tx.gas_limit = 1_000_000;
+                }),
+                GasTimelineInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let points = evm.context.inspector.points();
+        assert_eq!(points.len(), 14);
+        assert!(points.iter().enumerate().all(|(i, p)| p.step == i as u64));
+        assert_eq!(
+            points.iter().map(|p| p.depth).collect::<Vec<_>>(),
+            [1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1]
+        );
+        assert_eq!(points[0].gas_remaining, 1_000_000 - 21_000);
+        // Callee starts with the forwarded gas.
+        assert!(points[8].gas_remaining < points[7].gas_remaining);
+
+        for depth in [1, 2] {
+            let frame = points.iter().filter(|p| p.depth == depth);
+            let gas = frame.map(|p| p.gas_remaining).collect::<Vec<_>>();
+            assert!(gas.windows(2).all(|w| w[1] < w[0]), "{gas:?}");
+        }
+    }
+}
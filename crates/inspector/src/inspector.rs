@@ -6,26 +6,29 @@ use crate::{
 use auto_impl::auto_impl;
 use revm::{
     context_interface::{
-        BlockGetter, CfgGetter, ErrorGetter, Journal, JournalDBError, JournalGetter,
+        BlockGetter, Cfg, CfgGetter, ErrorGetter, Journal, JournalDBError, JournalGetter,
         TransactionGetter,
     },
     database_interface::{Database, EmptyDB},
     handler::{
-        EthExecution, EthFrame, EthHandler, EthPostExecution, EthPreExecution,
-        EthPrecompileProvider, EthValidation, FrameResult,
+        apply_eip7702_auth_list_with, AuthResult, Authorization, EthExecution, EthFrame,
+        EthHandler, EthPostExecution, EthPreExecution, EthPreExecutionContext,
+        EthPreExecutionError, EthPrecompileProvider, EthValidation, FrameResult,
     },
-    handler_interface::{Frame, FrameOrResultGen, PrecompileProvider},
+    handler_interface::{Frame, FrameOrResultGen, PreExecutionHandler, PrecompileProvider},
     interpreter::{
         interpreter::EthInterpreter,
         interpreter_types::{Jumps, LoopControl},
         table::CustomInstruction,
         CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, FrameInput, Host,
-        Instruction, InstructionResult, Interpreter, InterpreterTypes,
+        Instruction, InstructionResult, Interpreter, InterpreterResult, InterpreterTypes,
     },
     precompile::PrecompileErrors,
-    primitives::{Address, Log, U256},
-    Context, Error, Evm,
+    primitives::{Address, Bytes, Log, U256},
+    specification::hardfork::SpecId,
+    Context, Error, Evm, JournalEntry,
 };
+use std::vec::Vec;
 
 /// EVM [Interpreter] callbacks.
 #[auto_impl(&mut, Box)]
@@ -75,6 +78,9 @@ pub trait Inspector<CTX, INTR: InterpreterTypes> {
     /// Called whenever a call to a contract is about to start.
     ///
     /// InstructionResulting anything other than [revm::interpreter::InstructionResult::Continue] overrides the result of the call.
+    ///
+    /// If `None` is returned, the call is executed with the possibly modified `inputs`, see
+    /// [`CallInputs::redirect`] to change the callee.
     #[inline]
     fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
         let _ = context;
@@ -154,6 +160,123 @@ pub trait Inspector<CTX, INTR: InterpreterTypes> {
         let _ = target;
         let _ = value;
     }
+
+    /// Called in pre-execution for every EIP-7702 authorization of the transaction, in list order.
+    ///
+    /// `result` tells whether the delegation was applied or why it was skipped.
+    #[inline]
+    fn authorization_applied(
+        &mut self,
+        context: &mut CTX,
+        authorization: &Authorization,
+        result: AuthResult,
+    ) {
+        let _ = context;
+        let _ = authorization;
+        let _ = result;
+    }
+
+    /// Called when a checkpoint is reverted, with the undone journal entries in the order they
+    /// were journaled.
+    ///
+    /// Fired before the `*_end` hook of the reverted frame.
+    #[inline]
+    fn journal_revert(&mut self, context: &mut CTX, reverted_entries: &[JournalEntry]) {
+        let _ = context;
+        let _ = reverted_entries;
+    }
+
+    /// Called before a precompile is run.
+    ///
+    /// Fired after the `call` hook of the frame, only when precompiles are provided by
+    /// [`InspectorEthPrecompileProvider`].
+    #[inline]
+    fn precompile_call(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        input: &[u8],
+        gas_limit: u64,
+    ) {
+        let _ = context;
+        let _ = address;
+        let _ = input;
+        let _ = gas_limit;
+    }
+
+    /// Called with the result of a precompile, before it becomes the outcome of the call.
+    ///
+    /// Not called if the precompile failed with a fatal error.
+    #[inline]
+    fn precompile_result(
+        &mut self,
+        context: &mut CTX,
+        address: &Address,
+        result: &InterpreterResult,
+    ) {
+        let _ = context;
+        let _ = address;
+        let _ = result;
+    }
+
+    /// Called before `EXTCODESIZE`, `EXTCODEHASH` or `EXTCODECOPY` reads the code of `address`.
+    ///
+    /// Fired for warm and cold accounts alike, before gas is charged.
+    #[inline]
+    fn ext_code_access(&mut self, context: &mut CTX, address: Address, kind: CodeAccessKind) {
+        let _ = context;
+        let _ = address;
+        let _ = kind;
+    }
+
+    /// Called after `SLOAD` read `value` from the `key` slot of `address`.
+    ///
+    /// `is_cold` is the EIP-2929 status of the slot before the access.
+    #[inline]
+    fn sload(
+        &mut self,
+        context: &mut CTX,
+        address: Address,
+        key: U256,
+        value: U256,
+        is_cold: bool,
+    ) {
+        let _ = context;
+        let _ = address;
+        let _ = key;
+        let _ = value;
+        let _ = is_cold;
+    }
+
+    /// Called after `SSTORE` wrote `value` to the `key` slot of `address`.
+    ///
+    /// `is_cold` is the EIP-2929 status of the slot before the access.
+    #[inline]
+    fn sstore(
+        &mut self,
+        context: &mut CTX,
+        address: Address,
+        key: U256,
+        value: U256,
+        is_cold: bool,
+    ) {
+        let _ = context;
+        let _ = address;
+        let _ = key;
+        let _ = value;
+        let _ = is_cold;
+    }
+}
+
+/// Kind of access to the code of another account, see [`Inspector::ext_code_access`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CodeAccessKind {
+    /// `EXTCODESIZE`.
+    Size,
+    /// `EXTCODEHASH`.
+    Hash,
+    /// `EXTCODECOPY`.
+    Copy,
 }
 
 /// Provides access to an `Inspector` instance.
@@ -172,6 +295,17 @@ pub trait InspectorCtx {
     fn frame_end(&mut self, frame_output: &mut FrameResult);
     fn inspector_selfdestruct(&mut self, contract: Address, target: Address, value: U256);
     fn inspector_log(&mut self, interp: &mut Interpreter<Self::IT>, log: &Log);
+    fn inspector_authorization_applied(
+        &mut self,
+        authorization: &Authorization,
+        result: AuthResult,
+    );
+    fn inspector_journal_revert(&mut self);
+    fn inspector_precompile_call(&mut self, address: &Address, input: &[u8], gas_limit: u64);
+    fn inspector_precompile_result(&mut self, address: &Address, result: &InterpreterResult);
+    fn inspector_ext_code_access(&mut self, address: Address, kind: CodeAccessKind);
+    fn inspector_sload(&mut self, address: Address, key: U256, value: U256, is_cold: bool);
+    fn inspector_sstore(&mut self, address: Address, key: U256, value: U256, is_cold: bool);
 }
 
 impl<CTX, INTR: InterpreterTypes, INSP: Inspector<CTX, INTR>> GetInspector<CTX, INTR> for INSP {
@@ -270,6 +404,7 @@ where
         }
         let mut ret = EthFrame::init_first(context, frame_input)
             .map(|frame| frame.map_frame(|eth_frame| Self { eth_frame }));
+        context.inspector_journal_revert();
 
         match &mut ret {
             Ok(FrameOrResultGen::Result(res)) => {
@@ -303,6 +438,7 @@ where
             .eth_frame
             .init(context, frame_input)
             .map(|frame| frame.map_frame(|eth_frame| Self { eth_frame }));
+        context.inspector_journal_revert();
 
         if let Ok(FrameOrResultGen::Frame(frame)) = &mut ret {
             context.initialize_interp(&mut frame.eth_frame.interpreter);
@@ -314,7 +450,9 @@ where
         &mut self,
         context: &mut CTX,
     ) -> Result<FrameOrResultGen<Self::FrameInit, Self::FrameResult>, Self::Error> {
-        self.eth_frame.run(context)
+        let ret = self.eth_frame.run(context);
+        context.inspector_journal_revert();
+        ret
     }
 
     fn return_result(
@@ -327,6 +465,136 @@ where
     }
 }
 
+/// [`EthPreExecution`] that reports every EIP-7702 authorization to the inspector.
+pub struct InspectorEthPreExecution<CTX, ERROR> {
+    eth_pre_execution: EthPreExecution<CTX, ERROR>,
+}
+
+impl<CTX, ERROR> InspectorEthPreExecution<CTX, ERROR> {
+    /// Creates the pre-execution handler wrapping [`EthPreExecution`].
+    pub fn new() -> Self {
+        Self {
+            eth_pre_execution: EthPreExecution::new(),
+        }
+    }
+}
+
+impl<CTX, ERROR> Default for InspectorEthPreExecution<CTX, ERROR> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CTX, ERROR> PreExecutionHandler for InspectorEthPreExecution<CTX, ERROR>
+where
+    CTX: EthPreExecutionContext + InspectorCtx,
+    ERROR: EthPreExecutionError<CTX>,
+{
+    type Context = CTX;
+    type Error = ERROR;
+
+    fn load_accounts(&self, context: &mut Self::Context) -> Result<(), Self::Error> {
+        self.eth_pre_execution.load_accounts(context)
+    }
+
+    fn apply_eip7702_auth_list(&self, context: &mut Self::Context) -> Result<u64, Self::Error> {
+        let spec = context.cfg().spec().into();
+        if !spec.is_enabled_in(SpecId::PRAGUE) {
+            return Ok(0);
+        }
+        apply_eip7702_auth_list_with::<CTX, ERROR>(context, |context, authorization, result| {
+            context.inspector_authorization_applied(authorization, result)
+        })
+    }
+
+    fn deduct_caller(&self, context: &mut Self::Context) -> Result<(), Self::Error> {
+        self.eth_pre_execution.deduct_caller(context)
+    }
+}
+
+/// [`EthPrecompileProvider`] that reports every precompile run to the inspector.
+pub struct InspectorEthPrecompileProvider<CTX, ERROR> {
+    eth_precompiles: EthPrecompileProvider<CTX, ERROR>,
+}
+
+impl<CTX, ERROR> Clone for InspectorEthPrecompileProvider<CTX, ERROR> {
+    fn clone(&self) -> Self {
+        Self {
+            eth_precompiles: self.eth_precompiles.clone(),
+        }
+    }
+}
+
+impl<CTX, ERROR> PrecompileProvider for InspectorEthPrecompileProvider<CTX, ERROR>
+where
+    CTX: CfgGetter + BlockGetter + InspectorCtx,
+    ERROR: From<PrecompileErrors>,
+{
+    type Context = CTX;
+    type Error = ERROR;
+
+    fn new(context: &mut Self::Context) -> Self {
+        Self {
+            eth_precompiles: EthPrecompileProvider::new(context),
+        }
+    }
+
+    fn run(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        if !self.eth_precompiles.contains(address) {
+            return Ok(None);
+        }
+
+        context.inspector_precompile_call(address, bytes, gas_limit);
+        let result = self
+            .eth_precompiles
+            .run(context, address, bytes, gas_limit)?;
+        if let Some(result) = &result {
+            context.inspector_precompile_result(address, result);
+        }
+        Ok(result)
+    }
+
+    fn run_interruptible(
+        &mut self,
+        context: &mut Self::Context,
+        address: &Address,
+        bytes: &Bytes,
+        gas_limit: u64,
+        interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<InterpreterResult>, Self::Error> {
+        if !self.eth_precompiles.contains(address) {
+            return Ok(None);
+        }
+
+        context.inspector_precompile_call(address, bytes, gas_limit);
+        let result = self
+            .eth_precompiles
+            .run_interruptible(context, address, bytes, gas_limit, interrupt)?;
+        if let Some(result) = &result {
+            context.inspector_precompile_result(address, result);
+        }
+        Ok(result)
+    }
+
+    fn warm_addresses(&self) -> impl Iterator<Item = Address> {
+        self.eth_precompiles.warm_addresses()
+    }
+
+    fn contains(&self, address: &Address) -> bool {
+        self.eth_precompiles.contains(address)
+    }
+
+    fn active_addresses(&self) -> Vec<Address> {
+        self.eth_precompiles.active_addresses()
+    }
+}
+
 pub type InspCtxType<INSP, DB, CTX> = InspectorContext<INSP, DB, CTX>;
 
 pub type InspectorMainEvm<INSP, CTX, DB = EmptyDB> = Evm<
@@ -336,7 +604,7 @@ pub type InspectorMainEvm<INSP, CTX, DB = EmptyDB> = Evm<
         InspCtxType<INSP, DB, CTX>,
         Error<DB>,
         EthValidation<InspCtxType<INSP, DB, CTX>, Error<DB>>,
-        EthPreExecution<InspCtxType<INSP, DB, CTX>, Error<DB>>,
+        InspectorEthPreExecution<InspCtxType<INSP, DB, CTX>, Error<DB>>,
         InspectorEthExecution<InspCtxType<INSP, DB, CTX>, Error<DB>>,
     >,
 >;
@@ -346,21 +614,205 @@ pub fn inspector_handler<CTX: Host, ERROR, PRECOMPILE>() -> InspectorHandler<CTX
 {
     EthHandler::new(
         EthValidation::new(),
-        EthPreExecution::new(),
+        InspectorEthPreExecution::new(),
         EthExecution::<_, _, InspectorEthFrame<_, _, PRECOMPILE>>::new(),
         EthPostExecution::new(),
     )
 }
 
 /// Composed type for Inspector Execution handler.
-pub type InspectorEthExecution<CTX, ERROR, PRECOMPILE = EthPrecompileProvider<CTX, ERROR>> =
-    EthExecution<CTX, ERROR, InspectorEthFrame<CTX, ERROR, PRECOMPILE>>;
+pub type InspectorEthExecution<
+    CTX,
+    ERROR,
+    PRECOMPILE = InspectorEthPrecompileProvider<CTX, ERROR>,
+> = EthExecution<CTX, ERROR, InspectorEthFrame<CTX, ERROR, PRECOMPILE>>;
 
 /// Composed type for Inspector Handler.
 pub type InspectorHandler<CTX, ERROR, PRECOMPILE> = EthHandler<
     CTX,
     ERROR,
     EthValidation<CTX, ERROR>,
-    EthPreExecution<CTX, ERROR>,
+    InspectorEthPreExecution<CTX, ERROR>,
     InspectorEthExecution<CTX, ERROR, PRECOMPILE>,
 >;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::{BenchmarkDB, CacheDB};
+    use revm::{
+        bytecode::{
+            opcode::{PUSH1, REVERT, SSTORE},
+            Bytecode,
+        },
+        context_interface::{result::ExecutionResult, TransactionType},
+        primitives::{Bytes, TxKind, U256},
+    };
+    use std::vec;
+
+    #[derive(Default)]
+    struct AuthorizationInspector {
+        results: Vec<(Address, AuthResult)>,
+    }
+
+    impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for AuthorizationInspector {
+        fn authorization_applied(
+            &mut self,
+            _context: &mut CTX,
+            authorization: &Authorization,
+            result: AuthResult,
+        ) {
+            self.results
+                .push((authorization.authority.unwrap(), result));
+        }
+    }
+
+    #[test]
+    fn authorization_results_are_reported() {
+        let valid = Address::with_last_byte(0xaa);
+        let wrong_nonce = Address::with_last_byte(0xbb);
+        let delegate = Address::with_last_byte(0xcc);
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder()
+                    .with_db(CacheDB::new(EmptyDB::default()))
+                    .modify_tx_chained(|tx| {
+                        tx.tx_type = TransactionType::Eip7702.into();
+                        tx.caller = Address::with_last_byte(1);
+                        tx.kind = TxKind::Call(delegate);
+                        tx.gas_limit = 100_000;
+                        tx.authorization_list = vec![
+                            (Some(valid), U256::from(1), 0, delegate),
+                            (Some(wrong_nonce), U256::from(1), 5, delegate),
+                        ];
+                    }),
+                AuthorizationInspector::default(),
+            ),
+            inspector_handler(),
+        );
+        evm.transact().unwrap();
+
+        assert_eq!(
+            evm.context.inspector.results,
+            vec![
+                (valid, AuthResult::Applied),
+                (wrong_nonce, AuthResult::SkippedNonce)
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct JournalRevertInspector {
+        reverted_entries: Vec<JournalEntry>,
+    }
+
+    impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for JournalRevertInspector {
+        fn journal_revert(&mut self, _context: &mut CTX, reverted_entries: &[JournalEntry]) {
+            self.reverted_entries.extend_from_slice(reverted_entries);
+        }
+    }
+
+    #[test]
+    fn journal_revert_reports_reverted_writes() {
+        // Writes slots 0 and 1 and reverts.
+        let code = Bytes::from([
+            PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x02, PUSH1, 0x01, SSTORE, PUSH1, 0x00, PUSH1,
+            0x00, REVERT,
+        ]);
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder()
+                    .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code)))
+                    .modify_tx_chained(|tx| {
+                        tx.caller = Address::with_last_byte(1);
+                        tx.kind = TxKind::Call(Address::ZERO);
+                        tx.gas_limit = 100_000;
+                    }),
+                JournalRevertInspector::default(),
+            ),
+            inspector_handler(),
+        );
+        assert!(matches!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Revert { .. }
+        ));
+
+        let reverted_writes: Vec<_> = evm
+            .context
+            .inspector
+            .reverted_entries
+            .iter()
+            .filter_map(|entry| match entry {
+                JournalEntry::StorageChanged {
+                    address,
+                    key,
+                    had_value,
+                } => Some((*address, *key, *had_value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            reverted_writes,
+            [
+                (Address::ZERO, U256::ZERO, U256::ZERO),
+                (Address::ZERO, U256::from(1), U256::ZERO),
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct PrecompileInspector {
+        calls: Vec<(Address, Bytes, u64)>,
+        gas_used: Vec<(Address, u64)>,
+    }
+
+    impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for PrecompileInspector {
+        fn precompile_call(
+            &mut self,
+            _context: &mut CTX,
+            address: &Address,
+            input: &[u8],
+            gas_limit: u64,
+        ) {
+            self.calls
+                .push((*address, Bytes::copy_from_slice(input), gas_limit));
+        }
+
+        fn precompile_result(
+            &mut self,
+            _context: &mut CTX,
+            address: &Address,
+            result: &InterpreterResult,
+        ) {
+            self.gas_used.push((*address, result.gas.spent()));
+        }
+    }
+
+    #[test]
+    fn ecrecover_call_fires_precompile_hooks() {
+        let ecrecover = Address::with_last_byte(1);
+        let input = Bytes::from([0u8; 128]);
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder()
+                    .with_db(BenchmarkDB::new_bytecode(Bytecode::default()))
+                    .modify_tx_chained(|tx| {
+                        tx.caller = Address::with_last_byte(1);
+                        tx.kind = TxKind::Call(ecrecover);
+                        tx.data = input.clone();
+                        tx.gas_limit = 100_000;
+                    }),
+                PrecompileInspector::default(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = &evm.context.inspector;
+        assert_eq!(inspector.calls.len(), 1);
+        assert_eq!(inspector.calls[0].0, ecrecover);
+        assert_eq!(inspector.calls[0].1, input);
+        assert_eq!(inspector.gas_used, [(ecrecover, 3000)]);
+    }
+}
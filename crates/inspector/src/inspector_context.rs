@@ -7,16 +7,19 @@ use revm::{
         PerformantContextAccess, TransactionGetter,
     },
     database_interface::Database,
-    handler::FrameResult,
+    handler::{AuthResult, Authorization, FrameResult},
     interpreter::{
-        interpreter::EthInterpreter, FrameInput, Host, Interpreter, SStoreResult,
-        SelfDestructResult, StateLoad,
+        interpreter::EthInterpreter, FrameInput, Host, Interpreter, InterpreterResult,
+        SStoreResult, SelfDestructResult, StateLoad,
     },
     primitives::{Address, Bytes, Log, B256, U256},
 };
 use std::vec::Vec;
 
-use crate::{journal::JournalExtGetter, GetInspector, Inspector, InspectorCtx};
+use crate::{
+    journal::{JournalExt, JournalExtGetter},
+    CodeAccessKind, GetInspector, Inspector, InspectorCtx,
+};
 
 /// EVM context contains data that EVM needs for execution.
 #[derive(Clone, Debug)]
@@ -60,6 +63,10 @@ where
         self.inner.load_account_delegated(address)
     }
 
+    fn blob_hash(&mut self, index: usize) -> Option<B256> {
+        self.inner.blob_hash(index)
+    }
+
     fn balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
         self.inner.balance(address)
     }
@@ -109,7 +116,7 @@ where
 impl<INSP, DB, CTX> InspectorCtx for InspectorContext<INSP, DB, CTX>
 where
     INSP: GetInspector<CTX, EthInterpreter>,
-    CTX: DatabaseGetter<Database = DB>,
+    CTX: DatabaseGetter<Database = DB> + JournalGetter<Journal: JournalExt>,
 {
     type IT = EthInterpreter<()>;
 
@@ -154,6 +161,9 @@ where
                 }
             }
         }
+        if self.frame_input_stack.is_empty() {
+            self.inner.journal().track_reverted_entries();
+        }
         self.frame_input_stack.push(frame_input.clone());
         None
     }
@@ -189,6 +199,55 @@ where
             .get_inspector()
             .selfdestruct(contract, target, value)
     }
+
+    fn inspector_authorization_applied(
+        &mut self,
+        authorization: &Authorization,
+        result: AuthResult,
+    ) {
+        self.inspector
+            .get_inspector()
+            .authorization_applied(&mut self.inner, authorization, result)
+    }
+
+    fn inspector_journal_revert(&mut self) {
+        let reverted_entries = self.inner.journal().take_reverted_entries();
+        if !reverted_entries.is_empty() {
+            self.inspector
+                .get_inspector()
+                .journal_revert(&mut self.inner, &reverted_entries);
+        }
+    }
+
+    fn inspector_precompile_call(&mut self, address: &Address, input: &[u8], gas_limit: u64) {
+        self.inspector
+            .get_inspector()
+            .precompile_call(&mut self.inner, address, input, gas_limit)
+    }
+
+    fn inspector_precompile_result(&mut self, address: &Address, result: &InterpreterResult) {
+        self.inspector
+            .get_inspector()
+            .precompile_result(&mut self.inner, address, result)
+    }
+
+    fn inspector_ext_code_access(&mut self, address: Address, kind: CodeAccessKind) {
+        self.inspector
+            .get_inspector()
+            .ext_code_access(&mut self.inner, address, kind)
+    }
+
+    fn inspector_sload(&mut self, address: Address, key: U256, value: U256, is_cold: bool) {
+        self.inspector
+            .get_inspector()
+            .sload(&mut self.inner, address, key, value, is_cold)
+    }
+
+    fn inspector_sstore(&mut self, address: Address, key: U256, value: U256, is_cold: bool) {
+        self.inspector
+            .get_inspector()
+            .sstore(&mut self.inner, address, key, value, is_cold)
+    }
 }
 
 impl<INSP, DB, CTX> CfgGetter for InspectorContext<INSP, DB, CTX>
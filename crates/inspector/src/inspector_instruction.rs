@@ -3,9 +3,12 @@ use revm::{
     bytecode::opcode::OpCode,
     context_interface::JournalGetter,
     interpreter::{
-        instructions::host::{log, selfdestruct},
+        instructions::{
+            host::{extcodecopy, extcodehash, extcodesize, log, selfdestruct, sload, sstore},
+            utility::IntoAddress,
+        },
         interpreter::InstructionProvider,
-        interpreter_types::LoopControl,
+        interpreter_types::{InputsTrait, LoopControl, StackTrait},
         table::{self, CustomInstruction},
         Host, Instruction, InstructionResult, Interpreter, InterpreterTypes,
     },
@@ -15,7 +18,7 @@ use std::rc::Rc;
 
 use crate::{
     journal::{JournalExt, JournalExtGetter},
-    InspectorCtx, InspectorInstruction,
+    CodeAccessKind, InspectorCtx, InspectorInstruction,
 };
 
 pub struct InspectorInstructionProvider<WIRE: InterpreterTypes, HOST> {
@@ -125,6 +128,85 @@ where
             },
         };
 
+        // Inspector code access wrapper
+        fn inspector_ext_code<CTX: Host + InspectorCtx>(
+            interpreter: &mut Interpreter<<CTX as InspectorCtx>::IT>,
+            context: &mut CTX,
+            prev: Instruction<<CTX as InspectorCtx>::IT, CTX>,
+            kind: CodeAccessKind,
+        ) {
+            if let Some(top) = interpreter.stack.top() {
+                let address = top.into_address();
+                context.inspector_ext_code_access(address, kind);
+            }
+
+            prev(interpreter, context);
+        }
+
+        /* EXTCODESIZE, EXTCODEHASH and EXTCODECOPY instructions */
+        table[OpCode::EXTCODESIZE.as_usize()] = InspectorInstruction {
+            instruction: |interp, context| {
+                inspector_ext_code(interp, context, extcodesize, CodeAccessKind::Size);
+            },
+        };
+        table[OpCode::EXTCODEHASH.as_usize()] = InspectorInstruction {
+            instruction: |interp, context| {
+                inspector_ext_code(interp, context, extcodehash, CodeAccessKind::Hash);
+            },
+        };
+        table[OpCode::EXTCODECOPY.as_usize()] = InspectorInstruction {
+            instruction: |interp, context| {
+                inspector_ext_code(interp, context, extcodecopy, CodeAccessKind::Copy);
+            },
+        };
+
+        // Inspector storage access wrapper
+        fn inspector_storage<CTX: Host + JournalExtGetter + InspectorCtx>(
+            interpreter: &mut Interpreter<<CTX as InspectorCtx>::IT>,
+            context: &mut CTX,
+            prev: Instruction<<CTX as InspectorCtx>::IT, CTX>,
+            is_write: bool,
+        ) {
+            let Some(key) = interpreter.stack.top().copied() else {
+                prev(interpreter, context);
+                return;
+            };
+            let address = interpreter.input.target_address();
+            let slot = |context: &CTX| {
+                context
+                    .journal_ext()
+                    .evm_state()
+                    .get(&address)
+                    .and_then(|account| account.storage.get(&key))
+                    .map(|slot| (slot.present_value, slot.is_cold))
+            };
+            let is_cold = slot(context).is_none_or(|(_, is_cold)| is_cold);
+
+            prev(interpreter, context);
+
+            if interpreter.control.instruction_result() != InstructionResult::Continue {
+                return;
+            }
+            let value = slot(context).map(|(value, _)| value).unwrap_or_default();
+            if is_write {
+                context.inspector_sstore(address, key, value, is_cold);
+            } else {
+                context.inspector_sload(address, key, value, is_cold);
+            }
+        }
+
+        /* SLOAD and SSTORE instructions */
+        table[OpCode::SLOAD.as_usize()] = InspectorInstruction {
+            instruction: |interp, context| {
+                inspector_storage(interp, context, sload, false);
+            },
+        };
+        table[OpCode::SSTORE.as_usize()] = InspectorInstruction {
+            instruction: |interp, context| {
+                inspector_storage(interp, context, sstore, true);
+            },
+        };
+
         Self {
             instruction_table: Rc::new(table),
         }
@@ -134,3 +216,58 @@ where
         self.instruction_table.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inspector_context::InspectorContext, inspector_handler, Inspector, InspectorMainEvm,
+    };
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{
+            opcode::{EXTCODESIZE, POP, PUSH20, STOP},
+            Bytecode,
+        },
+        primitives::{Address, TxKind},
+        Context,
+    };
+    use std::{vec, vec::Vec};
+
+    #[derive(Default)]
+    struct CodeAccessInspector {
+        accesses: Vec<(Address, CodeAccessKind)>,
+    }
+
+    impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for CodeAccessInspector {
+        fn ext_code_access(&mut self, _context: &mut CTX, address: Address, kind: CodeAccessKind) {
+            self.accesses.push((address, kind));
+        }
+    }
+
+    #[test]
+    fn extcodesize_fires_code_access_hook() {
+        let other = Address::with_last_byte(0xaa);
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(other.as_slice());
+        code.extend_from_slice(&[EXTCODESIZE, POP, STOP]);
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder()
+                    .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+                    .modify_tx_chained(|tx| {
+                        tx.caller = Address::with_last_byte(1);
+                        tx.kind = TxKind::Call(Address::ZERO);
+                        tx.gas_limit = 100_000;
+                    }),
+                CodeAccessInspector::default(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.context.inspector.accesses,
+            [(other, CodeAccessKind::Size)]
+        );
+    }
+}
@@ -3,15 +3,26 @@ use revm::{
     context::JournaledState, database_interface::Database, primitives::Log, state::EvmState,
     JournalEntry,
 };
+use std::vec::Vec;
 
 pub trait JournalExt {
     fn logs(&self) -> &[Log];
 
+    /// Returns an iterator over the logs together with the call depth of the frame that emitted
+    /// them. Top-level call has depth one.
+    fn logs_with_context(&self) -> impl Iterator<Item = (&Log, usize)>;
+
     fn last_journal(&self) -> &[JournalEntry];
 
     fn evm_state(&self) -> &EvmState;
 
     fn evm_state_mut(&mut self) -> &mut EvmState;
+
+    /// Starts collecting journal entries undone by checkpoint reverts.
+    fn track_reverted_entries(&mut self);
+
+    /// Takes the reverted journal entries collected since the last call.
+    fn take_reverted_entries(&mut self) -> Vec<JournalEntry>;
 }
 
 impl<DB: Database> JournalExt for JournaledState<DB> {
@@ -19,6 +30,10 @@ impl<DB: Database> JournalExt for JournaledState<DB> {
         &self.logs
     }
 
+    fn logs_with_context(&self) -> impl Iterator<Item = (&Log, usize)> {
+        self.logs.iter().zip(self.log_depths.iter().copied())
+    }
+
     fn last_journal(&self) -> &[JournalEntry] {
         self.journal.last().expect("Journal is never empty")
     }
@@ -30,6 +45,17 @@ impl<DB: Database> JournalExt for JournaledState<DB> {
     fn evm_state_mut(&mut self) -> &mut EvmState {
         &mut self.state
     }
+
+    fn track_reverted_entries(&mut self) {
+        self.reverted_entries.get_or_insert_with(Vec::new);
+    }
+
+    fn take_reverted_entries(&mut self) -> Vec<JournalEntry> {
+        self.reverted_entries
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
+    }
 }
 
 #[auto_impl(&, &mut, Box, Arc)]
@@ -38,3 +64,85 @@ pub trait JournalExtGetter {
 
     fn journal_ext(&self) -> &Self::JournalExt;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inspector_context::InspectorContext, inspector_handler, Inspector, InspectorMainEvm,
+    };
+    use database::CacheDB;
+    use revm::{
+        bytecode::{
+            opcode::{CALL, GAS, LOG0, POP, PUSH1, PUSH20, STOP},
+            Bytecode,
+        },
+        database_interface::EmptyDB,
+        interpreter::{CallInputs, CallOutcome, InterpreterTypes},
+        primitives::{Address, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+
+    #[derive(Default)]
+    struct LogDepthInspector {
+        logs: Vec<(Address, usize)>,
+    }
+
+    impl<CTX: JournalExtGetter, INTR: InterpreterTypes> Inspector<CTX, INTR> for LogDepthInspector {
+        fn call_end(
+            &mut self,
+            context: &mut CTX,
+            _inputs: &CallInputs,
+            _outcome: &mut CallOutcome,
+        ) {
+            self.logs = context
+                .journal_ext()
+                .logs_with_context()
+                .map(|(log, depth)| (log.address, depth))
+                .collect();
+        }
+    }
+
+    #[test]
+    fn logs_are_reported_with_emitting_depth() {
+        let outer = Address::with_last_byte(0xaa);
+        let inner = Address::with_last_byte(0xbb);
+        let log = [PUSH1, 0x00, PUSH1, 0x00, LOG0];
+
+        // Logs, calls the inner contract and logs again.
+        let mut outer_code = log.to_vec();
+        outer_code.extend([PUSH1, 0x00].repeat(5));
+        outer_code.push(PUSH20);
+        outer_code.extend_from_slice(inner.as_slice());
+        outer_code.extend_from_slice(&[GAS, CALL, POP]);
+        outer_code.extend_from_slice(&log);
+        let mut inner_code = log.to_vec();
+        inner_code.push(STOP);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(outer, outer_code), (inner, inner_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(outer);
+                    tx.gas_limit = 100_000;
+                }),
+                LogDepthInspector::default(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.context.inspector.logs,
+            [(outer, 1), (inner, 2), (outer, 1)]
+        );
+    }
+}
@@ -5,21 +5,55 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+mod access_list;
+mod block_trace;
 #[cfg(all(feature = "std", feature = "serde-json"))]
 mod eip3155;
+#[cfg(feature = "std")]
+mod flamegraph;
 mod gas;
+mod gas_timeline;
 mod inspector;
 pub mod inspector_context;
 pub mod inspector_instruction;
 pub mod journal;
 mod noop;
+mod pc_trace;
+mod peak_usage;
+mod reentrancy;
+#[cfg(feature = "alloydb")]
+mod replay;
+mod rich_step;
+mod storage_access;
+#[cfg(feature = "std")]
+mod timing;
+#[cfg(all(feature = "std", feature = "serde-json"))]
+mod trace_compare;
 
 pub use inspector::*;
+#[cfg(feature = "alloydb")]
+pub use replay::{replay_historical_tx, ReplayError, ReplayOutput};
+#[cfg(all(feature = "std", feature = "serde-json"))]
+pub use trace_compare::{compare_traces, TraceDivergence, TraceField, TraceStep};
 
 /// [Inspector] implementations.
 pub mod inspectors {
+    pub use super::access_list::AccessListInspector;
+    pub use super::block_trace::{
+        AccountDiff, BlockTrace, BlockTraceInspector, CallFrame, FrameKind, TxTrace,
+    };
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
+    #[cfg(feature = "std")]
+    pub use super::flamegraph::FlamegraphInspector;
     pub use super::gas::GasInspector;
+    pub use super::gas_timeline::{GasPoint, GasTimelineInspector};
     pub use super::noop::NoOpInspector;
+    pub use super::pc_trace::PcTraceInspector;
+    pub use super::peak_usage::{FramePeak, PeakUsageInspector};
+    pub use super::reentrancy::{ReentrancyEvent, ReentrancyInspector};
+    pub use super::rich_step::{RichStepInspector, StackStep};
+    pub use super::storage_access::{StorageAccess, StorageAccessInspector};
+    #[cfg(feature = "std")]
+    pub use super::timing::TimingInspector;
 }
@@ -0,0 +1,229 @@
+//! PcTraceInspector. Records program counters of executed instructions for coverage maps.
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        interpreter_types::{InputsTrait, Jumps},
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Interpreter,
+        InterpreterTypes,
+    },
+    primitives::{Address, HashMap},
+};
+use std::vec::Vec;
+
+/// [Inspector] that records the program counter of every executed instruction.
+///
+/// Each step costs a single push of the `u32` program counter. Contract of the recorded
+/// program counters is tracked separately and only changes when a frame starts or returns, see
+/// [`PcTraceInspector::iter`].
+///
+/// Contracts are identified by the address of the executed code, so delegate calls are
+/// attributed to the callee code and not to the caller storage.
+#[derive(Clone, Debug, Default)]
+pub struct PcTraceInspector {
+    pcs: Vec<u32>,
+    /// Addresses of the executed contracts, indexed by contract index.
+    contracts: Vec<Address>,
+    contract_indices: HashMap<Address, u32>,
+    /// Index into `pcs` from which the program counters belong to the contract index.
+    segments: Vec<(usize, u32)>,
+    /// Active frames, `None` until the interpreter of the frame is initialized.
+    frames: Vec<Option<u32>>,
+    /// Code address of the call that is about to start.
+    pending_code_address: Option<Address>,
+}
+
+impl PcTraceInspector {
+    /// Creates an inspector with an empty trace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns program counters of all executed instructions in execution order.
+    pub fn pcs(&self) -> &[u32] {
+        &self.pcs
+    }
+
+    /// Returns addresses of the executed contracts, indexed by contract index.
+    pub fn contracts(&self) -> &[Address] {
+        &self.contracts
+    }
+
+    /// Returns an iterator over `(contract index, pc)` of all executed instructions.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.segments
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &(start, contract))| {
+                let end = self
+                    .segments
+                    .get(i + 1)
+                    .map_or(self.pcs.len(), |&(end, _)| end);
+                self.pcs[start..end].iter().map(move |&pc| (contract, pc))
+            })
+    }
+
+    /// Clears the recorded trace, contract indices are kept.
+    pub fn clear(&mut self) {
+        self.pcs.clear();
+        self.segments.clear();
+        self.frames.clear();
+        self.pending_code_address = None;
+    }
+
+    fn contract_index(&mut self, address: Address) -> u32 {
+        *self.contract_indices.entry(address).or_insert_with(|| {
+            self.contracts.push(address);
+            self.contracts.len() as u32 - 1
+        })
+    }
+
+    /// Starts a new segment for the contract, reusing the last segment if it is empty or
+    /// belongs to the same contract.
+    fn switch_contract(&mut self, contract: u32) {
+        match self.segments.last_mut() {
+            Some((start, last)) if *start == self.pcs.len() => *last = contract,
+            Some((_, last)) if *last == contract => {}
+            _ => self.segments.push((self.pcs.len(), contract)),
+        }
+    }
+
+    fn frame_end(&mut self) {
+        self.frames.pop();
+        if let Some(Some(contract)) = self.frames.last() {
+            self.switch_contract(*contract);
+        }
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for PcTraceInspector {
+    fn initialize_interp(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        let address = self
+            .pending_code_address
+            .take()
+            .unwrap_or_else(|| interp.input.target_address());
+        let contract = self.contract_index(address);
+        if let Some(frame) = self.frames.last_mut() {
+            *frame = Some(contract);
+        }
+        self.switch_contract(contract);
+    }
+
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        self.pcs.push(interp.bytecode.pc() as u32);
+    }
+
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frames.push(None);
+        self.pending_code_address = Some(inputs.bytecode_address);
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.frame_end();
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frames.push(None);
+        self.pending_code_address = None;
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end();
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frames.push(None);
+        self.pending_code_address = None;
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &EOFCreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, Bytes, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+    use std::vec;
+
+    #[test]
+    fn records_pcs_per_contract() {
+        let caller = address!("000000000000000000000000000000000000aaaa");
+        let callee = address!("000000000000000000000000000000000000bbbb");
+
+        // Calls the callee with zeroed arguments and all remaining gas.
+        let mut caller_code = [opcode::PUSH1, 0x00].repeat(5);
+        caller_code.push(opcode::PUSH20);
+        caller_code.extend_from_slice(callee.as_slice());
+        caller_code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+        let callee_code = vec![opcode::PUSH1, 0x01, opcode::POP, opcode::STOP];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(caller, caller_code), (callee, callee_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from(code))),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.kind = TxKind::Call(caller);
+                    tx.gas_limit = 1_000_000;
+                }),
+                PcTraceInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = &evm.context.inspector;
+        assert_eq!(inspector.contracts(), &[caller, callee]);
+        assert_eq!(inspector.pcs(), &[0, 2, 4, 6, 8, 10, 31, 32, 0, 2, 3, 33]);
+        assert_eq!(
+            inspector.iter().collect::<Vec<_>>(),
+            [
+                (0, 0),
+                (0, 2),
+                (0, 4),
+                (0, 6),
+                (0, 8),
+                (0, 10),
+                (0, 31),
+                (0, 32),
+                (1, 0),
+                (1, 2),
+                (1, 3),
+                (0, 33),
+            ]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! PeakUsageInspector. Records the peak stack depth and memory size of every frame.
+use crate::Inspector;
+use revm::interpreter::{
+    interpreter_types::{MemoryTrait, StackTrait},
+    CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Interpreter,
+    InterpreterTypes,
+};
+use std::vec::Vec;
+
+/// Peak resource usage of a call or create frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FramePeak {
+    /// Depth of the frame, the top level frame has depth zero.
+    pub depth: usize,
+    /// Maximum number of items on the stack.
+    pub stack: usize,
+    /// Maximum memory size in bytes.
+    pub memory: usize,
+}
+
+/// [Inspector] that records the peak stack depth and memory size reached by every frame.
+///
+/// Peaks are local to the frame, usage of sub calls is reported in their own [`FramePeak`].
+/// Usage is sampled before and after every instruction, so memory expanded by the last
+/// instruction of a frame is included.
+#[derive(Clone, Debug, Default)]
+pub struct PeakUsageInspector {
+    /// Indices of the frames that have not ended yet.
+    stack: Vec<usize>,
+    peaks: Vec<FramePeak>,
+}
+
+impl PeakUsageInspector {
+    /// Creates an inspector with no recorded frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the peaks of the frames, in the order the frames started.
+    pub fn peaks(&self) -> &[FramePeak] {
+        &self.peaks
+    }
+
+    /// Consumes the inspector and returns the peaks of the frames.
+    pub fn into_peaks(self) -> Vec<FramePeak> {
+        self.peaks
+    }
+
+    fn frame_start(&mut self) {
+        self.stack.push(self.peaks.len());
+        self.peaks.push(FramePeak {
+            depth: self.stack.len() - 1,
+            ..Default::default()
+        });
+    }
+
+    fn frame_end(&mut self) {
+        self.stack.pop();
+    }
+
+    fn sample<INTR: InterpreterTypes>(&mut self, interp: &Interpreter<INTR>) {
+        let Some(peak) = self.stack.last().map(|index| &mut self.peaks[*index]) else {
+            return;
+        };
+        peak.stack = peak.stack.max(interp.stack.len());
+        peak.memory = peak.memory.max(interp.memory.size());
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for PeakUsageInspector {
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        self.sample(interp);
+    }
+
+    #[inline]
+    fn step_end(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        self.sample(interp);
+    }
+
+    fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.frame_end();
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end();
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start();
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &EOFCreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, Bytes, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+    use std::vec;
+
+    #[test]
+    fn memory_expanded_to_1kb_is_reported() {
+        let contract = address!("000000000000000000000000000000000000aaaa");
+        let callee = address!("000000000000000000000000000000000000bbbb");
+
+        // Stores a word ending at byte 1024, then calls the callee with zeroed arguments.
+        let mut code = vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH2,
+            0x03,
+            0xe0,
+            opcode::MSTORE,
+        ];
+        code.extend_from_slice(&[opcode::PUSH1, 0x00].repeat(5));
+        code.push(opcode::PUSH20);
+        code.extend_from_slice(callee.as_slice());
+        code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+        // Stores a word at offset zero.
+        let callee_code = vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::STOP,
+        ];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(contract, code), (callee, callee_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from(code))),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 1_000_000;
+                }),
+                PeakUsageInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        // Peaks of the callee start from zero.
+        assert_eq!(
+            evm.context.inspector.peaks(),
+            [
+                FramePeak {
+                    depth: 0,
+                    stack: 7,
+                    memory: 1024,
+                },
+                FramePeak {
+                    depth: 1,
+                    stack: 2,
+                    memory: 32,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! ReentrancyInspector. Reports contracts that are called again before their first call returned.
+use crate::Inspector;
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, InterpreterTypes},
+    primitives::Address,
+};
+use std::vec::Vec;
+
+/// Contract that was re-entered while it was still on the call stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReentrancyEvent {
+    /// Address of the re-entered contract.
+    pub address: Address,
+    /// Call depth of the re-entering call. Top-level call has depth zero.
+    pub depth: usize,
+}
+
+/// [Inspector] that records every call whose target is already present in the active call stack.
+///
+/// Intended self calls (e.g. library patterns) are reported as well, it is up to the user to
+/// filter them out.
+#[derive(Clone, Debug, Default)]
+pub struct ReentrancyInspector {
+    /// Targets of active frames. Create frames are tracked as `None`.
+    stack: Vec<Option<Address>>,
+    events: Vec<ReentrancyEvent>,
+}
+
+impl ReentrancyInspector {
+    /// Creates an inspector with no recorded events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns recorded reentrancy events.
+    pub fn events(&self) -> &[ReentrancyEvent] {
+        &self.events
+    }
+
+    /// Consumes the inspector and returns recorded reentrancy events.
+    pub fn into_events(self) -> Vec<ReentrancyEvent> {
+        self.events
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for ReentrancyInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let address = inputs.target_address;
+        if self.stack.contains(&Some(address)) {
+            self.events.push(ReentrancyEvent {
+                address,
+                depth: self.stack.len(),
+            });
+        }
+        self.stack.push(Some(address));
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.stack.pop();
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.stack.push(None);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        _outcome: &mut CreateOutcome,
+    ) {
+        self.stack.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, Bytes, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+    use std::vec;
+
+    /// Pushes zeroed call arguments and calls `target` with all remaining gas.
+    fn call(target: Address) -> Vec<u8> {
+        let mut code = [opcode::PUSH1, 0x00].repeat(5);
+        code.push(opcode::PUSH20);
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::POP]);
+        code
+    }
+
+    #[test]
+    fn detects_reentrancy() {
+        let victim = address!("000000000000000000000000000000000000aaaa");
+        let attacker = address!("000000000000000000000000000000000000bbbb");
+
+        // Victim calls the attacker unless it is called by the attacker.
+        let mut victim_code = vec![opcode::CALLER, opcode::PUSH20];
+        victim_code.extend_from_slice(attacker.as_slice());
+        victim_code.extend_from_slice(&[opcode::EQ, opcode::PUSH1, 60, opcode::JUMPI]);
+        victim_code.extend(call(attacker));
+        victim_code.extend_from_slice(&[opcode::JUMPDEST, opcode::STOP]);
+        assert_eq!(victim_code[60], opcode::JUMPDEST);
+
+        // Attacker calls back into the victim.
+        let mut attacker_code = call(victim);
+        attacker_code.push(opcode::STOP);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(victim, victim_code), (attacker, attacker_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from(code))),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.kind = TxKind::Call(victim);
+                    tx.gas_limit = 1_000_000;
+                }),
+                ReentrancyInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        let result = evm.transact().unwrap();
+        assert!(result.result.is_success());
+
+        assert_eq!(
+            evm.context.inspector.events(),
+            &[ReentrancyEvent {
+                address: victim,
+                depth: 2,
+            }]
+        );
+    }
+}
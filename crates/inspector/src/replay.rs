@@ -0,0 +1,254 @@
+//! Replay of historical transactions with the state fetched from a node.
+use crate::{
+    inspector_context::InspectorContext, inspector_handler, inspectors::TracerEip3155,
+    InspectorMainEvm,
+};
+use alloy_consensus::Transaction as _;
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{
+    network::{
+        primitives::{BlockTransactions, BlockTransactionsKind},
+        Ethereum,
+    },
+    Network, Provider,
+};
+use alloy_transport::{Transport, TransportError};
+use core::{cell::RefCell, error::Error, fmt};
+use database::{AlloyDB, CacheDB, DBTransportError};
+use revm::{
+    context::{BlockEnv, TxEnv},
+    context_interface::{
+        block::BlobExcessGasAndPrice,
+        result::{EVMError, HaltReason, InvalidTransaction, ResultAndState},
+    },
+    database_interface::WrapDatabaseAsync,
+    primitives::{TxKind, B256, U256},
+    specification::hardfork::SpecId,
+    Context, EvmCommit, EvmExec,
+};
+use std::{io, rc::Rc, vec::Vec};
+
+/// Error of [`replay_historical_tx`].
+#[derive(Debug)]
+pub enum ReplayError {
+    /// Request to the node failed.
+    Transport(TransportError),
+    /// Node does not know the transaction.
+    TransactionNotFound(B256),
+    /// Transaction is not included in a block yet.
+    PendingTransaction(B256),
+    /// Block was not found or returned without full transactions.
+    BlockNotFound(u64),
+    /// Not called within a multi threaded tokio runtime.
+    Runtime,
+    /// Execution of the transaction, or one before it in the block, failed.
+    Evm(EVMError<DBTransportError, InvalidTransaction>),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(e) => write!(f, "transport error: {e}"),
+            Self::TransactionNotFound(hash) => write!(f, "transaction {hash} not found"),
+            Self::PendingTransaction(hash) => write!(f, "transaction {hash} is pending"),
+            Self::BlockNotFound(number) => write!(f, "block {number} not found"),
+            Self::Runtime => f.write_str("replay requires a multi threaded tokio runtime"),
+            Self::Evm(e) => write!(f, "execution failed: {e}"),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+impl From<TransportError> for ReplayError {
+    fn from(e: TransportError) -> Self {
+        Self::Transport(e)
+    }
+}
+
+impl From<EVMError<DBTransportError, InvalidTransaction>> for ReplayError {
+    fn from(e: EVMError<DBTransportError, InvalidTransaction>) -> Self {
+        Self::Evm(e)
+    }
+}
+
+/// Output of [`replay_historical_tx`].
+#[derive(Debug)]
+pub struct ReplayOutput {
+    /// Result of the transaction and the state it changed.
+    pub result: ResultAndState<HaltReason>,
+    /// [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) JSON lines trace of the transaction,
+    /// see [`compare_traces`][crate::compare_traces].
+    pub trace: Vec<u8>,
+}
+
+/// Re-executes a mined transaction with the state of the chain right before it.
+///
+/// The state is loaded lazily through [`AlloyDB`] at the parent block, and the transactions
+/// preceding it in the block are executed first so that their changes are visible. The block
+/// environment, including the base fee and the blob gas price, is reconstructed from the
+/// header of the block.
+///
+/// `spec` is the hardfork active at the block of the transaction.
+///
+/// Must be called within a multi threaded tokio runtime.
+pub async fn replay_historical_tx<T, P>(
+    provider: P,
+    tx_hash: B256,
+    spec: SpecId,
+) -> Result<ReplayOutput, ReplayError>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum>,
+{
+    let tx = provider
+        .get_transaction_by_hash(tx_hash)
+        .await?
+        .ok_or(ReplayError::TransactionNotFound(tx_hash))?;
+    let (Some(block_number), Some(index)) = (tx.block_number, tx.transaction_index) else {
+        return Err(ReplayError::PendingTransaction(tx_hash));
+    };
+    let block = provider
+        .get_block_by_number(
+            BlockNumberOrTag::Number(block_number),
+            BlockTransactionsKind::Full,
+        )
+        .await?
+        .ok_or(ReplayError::BlockNotFound(block_number))?;
+    let BlockTransactions::Full(transactions) = block.transactions else {
+        return Err(ReplayError::BlockNotFound(block_number));
+    };
+    let chain_id = provider.get_chain_id().await?;
+
+    let header = &block.header;
+    let block_env = BlockEnv {
+        number: header.number,
+        beneficiary: header.beneficiary,
+        timestamp: header.timestamp,
+        gas_limit: header.gas_limit,
+        basefee: header.base_fee_per_gas.unwrap_or_default(),
+        difficulty: header.difficulty,
+        prevrandao: Some(header.mix_hash),
+        blob_excess_gas_and_price: header
+            .excess_blob_gas
+            .map(|excess_blob_gas| BlobExcessGasAndPrice::new_with_spec(excess_blob_gas, spec)),
+        ..Default::default()
+    };
+
+    let db = WrapDatabaseAsync::new(AlloyDB::new(provider, (block_number - 1).into()))
+        .ok_or(ReplayError::Runtime)?;
+    let mut evm = InspectorMainEvm::new(
+        InspectorContext::new(
+            Context::builder()
+                .with_db(CacheDB::new(db))
+                .with_block(block_env)
+                .modify_cfg_chained(|cfg| {
+                    cfg.chain_id = chain_id;
+                    cfg.spec = spec;
+                }),
+            TracerEip3155::new(Box::new(io::sink())),
+        ),
+        inspector_handler(),
+    );
+
+    for preceding in transactions.iter().take(index as usize) {
+        evm.exec_commit_with_tx(tx_env(preceding))?;
+    }
+
+    let trace = SharedBuffer::default();
+    evm.context.inspector.set_writer(Box::new(trace.clone()));
+    let result = evm.exec_with_tx(tx_env(&tx))?;
+    Ok(ReplayOutput {
+        result,
+        trace: trace.0.take(),
+    })
+}
+
+fn tx_env(tx: &<Ethereum as Network>::TransactionResponse) -> TxEnv {
+    TxEnv {
+        tx_type: tx.ty(),
+        caller: tx.from,
+        gas_limit: tx.gas_limit(),
+        gas_price: tx.max_fee_per_gas(),
+        kind: match tx.to() {
+            Some(to) => TxKind::Call(to),
+            None => TxKind::Create,
+        },
+        value: tx.value(),
+        data: tx.input().clone(),
+        nonce: tx.nonce(),
+        chain_id: tx.chain_id(),
+        access_list: tx
+            .access_list()
+            .map(|access_list| {
+                access_list
+                    .iter()
+                    .map(|item| (item.address, item.storage_keys.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        gas_priority_fee: tx.max_priority_fee_per_gas(),
+        blob_hashes: tx
+            .blob_versioned_hashes()
+            .map(<[B256]>::to_vec)
+            .unwrap_or_default(),
+        max_fee_per_blob_gas: tx.max_fee_per_blob_gas().unwrap_or_default(),
+        authorization_list: tx
+            .authorization_list()
+            .map(|authorization_list| {
+                authorization_list
+                    .iter()
+                    .map(|auth| {
+                        (
+                            auth.recover_authority().ok(),
+                            U256::from(auth.chain_id),
+                            auth.nonce,
+                            auth.address,
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Writer whose output is kept after the tracer is dropped.
+#[derive(Clone, Debug, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_provider::ProviderBuilder;
+    use revm::primitives::b256;
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "flaky RPC"]
+    async fn replays_first_transfer() {
+        let client = ProviderBuilder::new().on_http(
+            "https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27"
+                .parse()
+                .unwrap(),
+        );
+        // First value transfer on mainnet, in block 46147.
+        let tx_hash = b256!("5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060");
+        let output = replay_historical_tx(client, tx_hash, SpecId::FRONTIER)
+            .await
+            .unwrap();
+
+        assert!(output.result.result.is_success());
+        assert_eq!(output.result.result.gas_used(), 21000);
+        assert!(!output.trace.is_empty());
+    }
+}
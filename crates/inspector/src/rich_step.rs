@@ -0,0 +1,173 @@
+//! RichStepInspector. Records stack values consumed and produced by each executed instruction.
+use crate::Inspector;
+use revm::{
+    bytecode::opcode::{self, OpCode},
+    interpreter::{
+        interpreter_types::{Immediates, Jumps, LoopControl},
+        Interpreter, InterpreterTypes, Stack,
+    },
+    primitives::U256,
+};
+use std::vec::Vec;
+
+/// Stack effect of a single executed instruction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StackStep {
+    /// Program counter of the instruction.
+    pub pc: usize,
+    /// Executed opcode.
+    pub opcode: u8,
+    /// Values consumed from the stack, top of the stack first.
+    pub inputs: Vec<U256>,
+    /// Values pushed onto the stack, top of the stack first.
+    ///
+    /// Empty if the instruction did not complete, e.g. it halted, or if its result is pushed
+    /// only after a sub call returns (CALL and CREATE family of opcodes).
+    pub outputs: Vec<U256>,
+}
+
+/// [Inspector] that records, for every executed instruction, the values it popped from the stack
+/// and the values it pushed onto it.
+///
+/// Number of inputs and outputs comes from the opcode stack IO table. Opcodes whose stack effect
+/// depends on the immediate (DUPN, SWAPN and EXCHANGE) are resolved from their immediate.
+#[derive(Clone, Debug, Default)]
+pub struct RichStepInspector {
+    steps: Vec<StackStep>,
+    /// Step in progress with stack length before execution and number of outputs.
+    pending: Option<(StackStep, usize, usize)>,
+}
+
+impl RichStepInspector {
+    /// Creates an inspector with no recorded steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns recorded steps.
+    pub fn steps(&self) -> &[StackStep] {
+        &self.steps
+    }
+
+    /// Consumes the inspector and returns recorded steps.
+    pub fn into_steps(self) -> Vec<StackStep> {
+        self.steps
+    }
+}
+
+/// Returns number of stack inputs and outputs of the opcode.
+fn stack_io(op: u8, immediate: u8) -> (usize, usize) {
+    let immediate = immediate as usize;
+    match op {
+        opcode::DUPN => (immediate + 1, immediate + 2),
+        opcode::SWAPN => (immediate + 2, immediate + 2),
+        opcode::EXCHANGE => {
+            let n = (immediate >> 4) + 1;
+            let m = (immediate & 0x0F) + 1;
+            (n + m + 1, n + m + 1)
+        }
+        _ => OpCode::new(op)
+            .map(|op| (op.inputs() as usize, op.outputs() as usize))
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns `n` values from the top of the stack, top first.
+fn top_values(stack: &Stack, n: usize) -> Vec<U256> {
+    stack.data().iter().rev().take(n).copied().collect()
+}
+
+impl<CTX, INTR> Inspector<CTX, INTR> for RichStepInspector
+where
+    INTR: InterpreterTypes<Stack = Stack>,
+{
+    fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        let op = interp.bytecode.opcode();
+        let immediate = match op {
+            opcode::DUPN | opcode::SWAPN | opcode::EXCHANGE => interp.bytecode.read_slice(2)[1],
+            _ => 0,
+        };
+        let (inputs, outputs) = stack_io(op, immediate);
+        let step = StackStep {
+            pc: interp.bytecode.pc(),
+            opcode: op,
+            inputs: top_values(&interp.stack, inputs),
+            outputs: Vec::new(),
+        };
+        self.pending = Some((step, interp.stack.len(), outputs));
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+        let Some((mut step, len_before, outputs)) = self.pending.take() else {
+            return;
+        };
+        if interp.control.instruction_result().is_ok()
+            && step.inputs.len() <= len_before
+            && interp.stack.len() == len_before - step.inputs.len() + outputs
+        {
+            step.outputs = top_values(&interp.stack, outputs);
+        }
+        self.steps.push(step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{Address, TxKind},
+        Context,
+    };
+    use std::vec;
+
+    #[test]
+    fn records_stack_inputs_and_outputs() {
+        let code = [
+            opcode::PUSH1,
+            0x02,
+            opcode::PUSH1,
+            0x03,
+            opcode::ADD,
+            opcode::DUP1,
+            opcode::STOP,
+        ];
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder()
+                    .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+                    .modify_tx_chained(|tx| {
+                        tx.caller = Address::with_last_byte(1);
+                        tx.kind = TxKind::Call(Address::ZERO);
+                        tx.gas_limit = 100_000;
+                    }),
+                RichStepInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        evm.transact().unwrap();
+
+        let steps = evm.context.inspector.steps();
+        assert_eq!(steps.len(), 5);
+        assert_eq!(
+            steps[2],
+            StackStep {
+                pc: 4,
+                opcode: opcode::ADD,
+                inputs: vec![U256::from(3), U256::from(2)],
+                outputs: vec![U256::from(5)],
+            }
+        );
+        assert_eq!(
+            steps[3],
+            StackStep {
+                pc: 5,
+                opcode: opcode::DUP1,
+                inputs: vec![U256::from(5)],
+                outputs: vec![U256::from(5), U256::from(5)],
+            }
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! StorageAccessInspector. Records the storage slots accessed by a transaction and their warmth.
+use crate::Inspector;
+use revm::{
+    interpreter::InterpreterTypes,
+    primitives::{Address, HashSet, U256},
+};
+use std::vec::Vec;
+
+/// Storage slot access recorded by [`StorageAccessInspector`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StorageAccess {
+    /// Account whose storage was accessed.
+    pub address: Address,
+    /// Accessed slot.
+    pub slot: U256,
+    /// `true` for `SSTORE`, `false` for `SLOAD`.
+    pub is_write: bool,
+    /// EIP-2929 status of the slot at the time of the access.
+    pub is_cold: bool,
+}
+
+/// [Inspector] that records every `SLOAD` and `SSTORE` together with whether the slot was cold
+/// or warm at the time of the access.
+///
+/// Accesses of reverted frames are kept, as their gas was still paid.
+#[derive(Clone, Debug, Default)]
+pub struct StorageAccessInspector {
+    accesses: Vec<StorageAccess>,
+}
+
+impl StorageAccessInspector {
+    /// Creates an inspector with no recorded accesses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the accesses in execution order.
+    pub fn accesses(&self) -> &[StorageAccess] {
+        &self.accesses
+    }
+
+    /// Returns the number of accesses to cold slots.
+    pub fn cold_slots(&self) -> usize {
+        self.accesses.iter().filter(|access| access.is_cold).count()
+    }
+
+    /// Returns the number of accesses to warm slots.
+    pub fn warm_slots(&self) -> usize {
+        self.accesses
+            .iter()
+            .filter(|access| !access.is_cold)
+            .count()
+    }
+
+    /// Returns the distinct `(address, slot)` pairs that were accessed.
+    pub fn slots(&self) -> HashSet<(Address, U256)> {
+        self.accesses
+            .iter()
+            .map(|access| (access.address, access.slot))
+            .collect()
+    }
+
+    fn record(&mut self, address: Address, slot: U256, is_write: bool, is_cold: bool) {
+        self.accesses.push(StorageAccess {
+            address,
+            slot,
+            is_write,
+            is_cold,
+        });
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for StorageAccessInspector {
+    fn sload(
+        &mut self,
+        _context: &mut CTX,
+        address: Address,
+        key: U256,
+        _value: U256,
+        is_cold: bool,
+    ) {
+        self.record(address, key, false, is_cold);
+    }
+
+    fn sstore(
+        &mut self,
+        _context: &mut CTX,
+        address: Address,
+        key: U256,
+        _value: U256,
+        is_cold: bool,
+    ) {
+        self.record(address, key, true, is_cold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+    use std::vec;
+
+    #[test]
+    fn second_access_to_slot_is_warm() {
+        let contract = address!("000000000000000000000000000000000000aaaa");
+        // Loads slot 1, stores to it, and loads slot 2.
+        let code = vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x01,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x02,
+            opcode::SLOAD,
+            opcode::STOP,
+        ];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_storage(contract, U256::from(2), U256::from(7))
+            .unwrap();
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                }),
+                StorageAccessInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = &evm.context.inspector;
+        let access = |slot, is_write, is_cold| StorageAccess {
+            address: contract,
+            slot: U256::from(slot),
+            is_write,
+            is_cold,
+        };
+        assert_eq!(
+            inspector.accesses(),
+            [
+                access(1, false, true),
+                access(1, true, false),
+                access(2, false, true)
+            ]
+        );
+        assert_eq!(inspector.cold_slots(), 2);
+        assert_eq!(inspector.warm_slots(), 1);
+        assert_eq!(
+            inspector.slots(),
+            HashSet::from_iter([(contract, U256::from(1)), (contract, U256::from(2))])
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! TimingInspector. Measures wall-clock time spent in each contract.
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InterpreterTypes,
+    },
+    primitives::{Address, HashMap},
+};
+use std::{
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+/// Frame on the active call stack.
+#[derive(Clone, Debug)]
+struct Frame {
+    address: Address,
+    start: Instant,
+    /// Time spent in the finished child frames.
+    child_time: Duration,
+}
+
+/// [Inspector] that accumulates the wall-clock self time of every contract.
+///
+/// Self time of a frame is the time between its start and end minus the time spent in its
+/// child frames. Frames of the same address are summed. Time spent in other inspectors is
+/// included.
+///
+/// Create frames are attributed to the created address, or the zero address if creation failed.
+#[derive(Clone, Debug, Default)]
+pub struct TimingInspector {
+    stack: Vec<Frame>,
+    self_times: HashMap<Address, Duration>,
+}
+
+impl TimingInspector {
+    /// Creates an inspector with no recorded times.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the total self time of every contract that was executed.
+    pub fn self_times(&self) -> &HashMap<Address, Duration> {
+        &self.self_times
+    }
+
+    /// Consumes the inspector and returns the self times.
+    pub fn into_self_times(self) -> HashMap<Address, Duration> {
+        self.self_times
+    }
+
+    fn frame_start(&mut self, address: Address) {
+        self.stack.push(Frame {
+            address,
+            start: Instant::now(),
+            child_time: Duration::ZERO,
+        });
+    }
+
+    fn frame_end(&mut self, address: Option<Address>) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        let elapsed = frame.start.elapsed();
+        if let Some(parent) = self.stack.last_mut() {
+            parent.child_time += elapsed;
+        }
+        *self
+            .self_times
+            .entry(address.unwrap_or(frame.address))
+            .or_default() += elapsed.saturating_sub(frame.child_time);
+    }
+}
+
+impl<CTX, INTR: InterpreterTypes> Inspector<CTX, INTR> for TimingInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.frame_start(inputs.target_address);
+        None
+    }
+
+    fn call_end(&mut self, _context: &mut CTX, _inputs: &CallInputs, _outcome: &mut CallOutcome) {
+        self.frame_end(None);
+    }
+
+    fn create(&mut self, _context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start(Address::ZERO);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(outcome.address);
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_start(Address::ZERO);
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut CTX,
+        _inputs: &EOFCreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(outcome.address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{inspector_context::InspectorContext, inspector_handler, InspectorMainEvm};
+    use database::CacheDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        database_interface::EmptyDB,
+        primitives::{address, Bytes, TxKind},
+        state::AccountInfo,
+        Context,
+    };
+
+    #[test]
+    fn loop_accumulates_self_time() {
+        let caller = address!("000000000000000000000000000000000000aaaa");
+        let looper = address!("000000000000000000000000000000000000bbbb");
+
+        // Calls the looping contract with all remaining gas.
+        let mut caller_code = [opcode::PUSH1, 0x00].repeat(5);
+        caller_code.push(opcode::PUSH20);
+        caller_code.extend_from_slice(looper.as_slice());
+        caller_code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+        // Decrements a counter from 10000 to zero.
+        let looper_code = vec![
+            opcode::PUSH2,
+            0x27,
+            0x10,
+            opcode::JUMPDEST,
+            opcode::PUSH1,
+            0x01,
+            opcode::SWAP1,
+            opcode::SUB,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x03,
+            opcode::JUMPI,
+            opcode::STOP,
+        ];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(caller, caller_code), (looper, looper_code)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from(code))),
+            );
+        }
+
+        let mut evm: InspectorMainEvm<_, _, _> = InspectorMainEvm::new(
+            InspectorContext::new(
+                Context::builder().with_db(db).modify_tx_chained(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.kind = TxKind::Call(caller);
+                    tx.gas_limit = 1_000_000;
+                }),
+                TimingInspector::new(),
+            ),
+            inspector_handler(),
+        );
+        let start = Instant::now();
+        assert!(evm.transact().unwrap().result.is_success());
+        let total = start.elapsed();
+
+        let self_times = evm.context.inspector.self_times();
+        assert_eq!(self_times.len(), 2);
+        assert!(self_times[&looper] > Duration::ZERO);
+        assert!(self_times[&caller] + self_times[&looper] <= total);
+    }
+}
@@ -0,0 +1,183 @@
+//! Comparison of [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) traces.
+use revm::primitives::{hex, U256};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read};
+
+/// Field of a trace step in which two traces diverge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TraceField {
+    Pc,
+    Opcode,
+    Gas,
+    Stack,
+    Depth,
+    Memory,
+    /// One of the traces ended before the other.
+    Length,
+}
+
+/// Step of an EIP-3155 trace, with numbers normalized so that traces produced by different
+/// clients can be compared.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TraceStep {
+    pub pc: u64,
+    pub op: u8,
+    pub gas: u64,
+    pub stack: Vec<U256>,
+    pub depth: u64,
+    /// Memory, only present if the tracer included it.
+    pub memory: Option<Vec<u8>>,
+}
+
+/// First divergence of two traces, see [`compare_traces`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TraceDivergence {
+    /// Index of the diverging step, summary lines are not counted.
+    pub step: usize,
+    /// First field that differs, in the order pc, opcode, gas, stack, depth and memory.
+    pub field: TraceField,
+    /// Step of the first trace, `None` if the trace ended.
+    pub a: Option<TraceStep>,
+    /// Step of the second trace, `None` if the trace ended.
+    pub b: Option<TraceStep>,
+}
+
+/// Compares two EIP-3155 JSON line traces and returns the first step where they diverge.
+///
+/// Numbers can be JSON numbers or hex/decimal strings. Lines that are not steps (e.g. the
+/// summary) or can't be parsed are skipped. Memory is compared only if both steps include it.
+///
+/// Returns `None` if the traces are equal.
+pub fn compare_traces(a: impl Read, b: impl Read) -> Option<TraceDivergence> {
+    let mut a = steps(a);
+    let mut b = steps(b);
+    let mut step = 0;
+    loop {
+        let (a, b) = match (a.next(), b.next()) {
+            (None, None) => return None,
+            (Some(a), Some(b)) => (a, b),
+            (a, b) => {
+                return Some(TraceDivergence {
+                    step,
+                    field: TraceField::Length,
+                    a,
+                    b,
+                })
+            }
+        };
+        if let Some(field) = diverging_field(&a, &b) {
+            return Some(TraceDivergence {
+                step,
+                field,
+                a: Some(a),
+                b: Some(b),
+            });
+        }
+        step += 1;
+    }
+}
+
+fn diverging_field(a: &TraceStep, b: &TraceStep) -> Option<TraceField> {
+    if a.pc != b.pc {
+        Some(TraceField::Pc)
+    } else if a.op != b.op {
+        Some(TraceField::Opcode)
+    } else if a.gas != b.gas {
+        Some(TraceField::Gas)
+    } else if a.stack != b.stack {
+        Some(TraceField::Stack)
+    } else if a.depth != b.depth {
+        Some(TraceField::Depth)
+    } else if matches!((&a.memory, &b.memory), (Some(a), Some(b)) if a != b) {
+        Some(TraceField::Memory)
+    } else {
+        None
+    }
+}
+
+fn steps(reader: impl Read) -> impl Iterator<Item = TraceStep> {
+    BufReader::new(reader)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| parse_step(&serde_json::from_str(&line).ok()?))
+}
+
+fn parse_step(value: &Value) -> Option<TraceStep> {
+    Some(TraceStep {
+        pc: parse_u256(value.get("pc")?)?.try_into().ok()?,
+        op: parse_u256(value.get("op")?)?.try_into().ok()?,
+        gas: parse_u256(value.get("gas")?)?.try_into().ok()?,
+        stack: value
+            .get("stack")?
+            .as_array()?
+            .iter()
+            .map(parse_u256)
+            .collect::<Option<_>>()?,
+        depth: parse_u256(value.get("depth")?)?.try_into().ok()?,
+        memory: match value.get("memory") {
+            Some(Value::String(memory)) => Some(hex::decode(memory).ok()?),
+            _ => None,
+        },
+    })
+}
+
+fn parse_u256(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE: &str = r#"{"pc":0,"op":96,"gas":"0x2710","gasCost":"0x3","stack":[],"depth":1,"returnData":"0x","refund":"0x0","memSize":"0"}
+{"pc":2,"op":96,"gas":"0x270d","gasCost":"0x3","stack":["0x1"],"depth":1,"returnData":"0x","refund":"0x0","memSize":"0"}
+{"pc":4,"op":1,"gas":"0x270a","gasCost":"0x3","stack":["0x1","0x2"],"depth":1,"returnData":"0x","refund":"0x0","memSize":"0"}
+{"stateRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","output":"0x","gasUsed":"0x9","pass":true}
+"#;
+
+    #[test]
+    fn equal_traces_with_different_formatting() {
+        // Decimal numbers, padded stack values and memory present in one trace only.
+        let other = r#"{"pc":0,"op":96,"gas":10000,"stack":[],"depth":1,"memory":"0x00"}
+{"pc":2,"op":96,"gas":"9997","stack":["0x0000000000000000000000000000000000000000000000000000000000000001"],"depth":1}
+{"pc":4,"op":"0x1","gas":9994,"stack":["0x01","0x02"],"depth":1}
+"#;
+        assert_eq!(compare_traces(TRACE.as_bytes(), other.as_bytes()), None);
+    }
+
+    #[test]
+    fn reports_first_divergence() {
+        let other = TRACE.replace(r#""stack":["0x1","0x2"]"#, r#""stack":["0x1","0x3"]"#);
+        let divergence = compare_traces(TRACE.as_bytes(), other.as_bytes()).unwrap();
+        assert_eq!(divergence.step, 2);
+        assert_eq!(divergence.field, TraceField::Stack);
+        assert_eq!(divergence.b.unwrap().stack, [U256::from(1), U256::from(3)]);
+
+        let other = TRACE.replace(r#""gas":"0x270d""#, r#""gas":"0x270c""#);
+        let divergence = compare_traces(TRACE.as_bytes(), other.as_bytes()).unwrap();
+        assert_eq!((divergence.step, divergence.field), (1, TraceField::Gas));
+
+        let truncated = TRACE.lines().take(2).collect::<Vec<_>>().join("\n");
+        let divergence = compare_traces(TRACE.as_bytes(), truncated.as_bytes()).unwrap();
+        assert_eq!((divergence.step, divergence.field), (2, TraceField::Length));
+        assert!(divergence.a.is_some() && divergence.b.is_none());
+    }
+
+    #[test]
+    fn memory_compared_when_both_present() {
+        let with_memory = |memory: &str| {
+            TRACE.replace(
+                r#""memSize":"0"}"#,
+                &format!(r#""memSize":"0","memory":"{memory}"}}"#),
+            )
+        };
+        let a = with_memory("0x00");
+        assert_eq!(compare_traces(a.as_bytes(), a.as_bytes()), None);
+        let divergence = compare_traces(a.as_bytes(), with_memory("0x01").as_bytes()).unwrap();
+        assert_eq!((divergence.step, divergence.field), (0, TraceField::Memory));
+    }
+}
@@ -18,6 +18,14 @@ pub struct Gas {
     refunded: i64,
     /// Memoisation of values for memory expansion cost.
     memory: MemoryGas,
+    /// Maximum dynamic gas a single instruction can spend.
+    opcode_gas_cap: Option<u64>,
+    /// Dynamic gas spent by the current instruction.
+    opcode_gas: u64,
+    /// Whether costs above the remaining gas are recorded instead of failing.
+    unmetered: bool,
+    /// Gas spent above the limit while unmetered.
+    exceeded: u64,
 }
 
 impl Gas {
@@ -29,6 +37,10 @@ impl Gas {
             remaining: limit,
             refunded: 0,
             memory: MemoryGas::new(),
+            opcode_gas_cap: None,
+            opcode_gas: 0,
+            unmetered: false,
+            exceeded: 0,
         }
     }
 
@@ -40,6 +52,10 @@ impl Gas {
             remaining: 0,
             refunded: 0,
             memory: MemoryGas::new(),
+            opcode_gas_cap: None,
+            opcode_gas: 0,
+            unmetered: false,
+            exceeded: 0,
         }
     }
 
@@ -49,6 +65,75 @@ impl Gas {
         self.limit
     }
 
+    /// Returns the cap on the dynamic gas of a single instruction.
+    #[inline]
+    pub const fn opcode_gas_cap(&self) -> Option<u64> {
+        self.opcode_gas_cap
+    }
+
+    /// Sets the cap on the dynamic gas of a single instruction.
+    ///
+    /// Dynamic costs, e.g. memory expansion, copy or exponent costs, above the cap halt with
+    /// [`InstructionResult::OpcodeGasCapExceeded`](crate::InstructionResult::OpcodeGasCapExceeded)
+    /// before they are charged. Static costs are not affected.
+    #[inline]
+    pub fn set_opcode_gas_cap(&mut self, cap: Option<u64>) {
+        self.opcode_gas_cap = cap;
+    }
+
+    /// Resets the dynamic gas spent by the current instruction, called before every instruction.
+    #[inline]
+    pub fn reset_opcode_gas(&mut self) {
+        self.opcode_gas = 0;
+    }
+
+    /// Adds `cost` to the dynamic gas spent by the current instruction, without recording it.
+    ///
+    /// Returns `false` if the dynamic gas exceeds the opcode gas cap, overflowing costs given as
+    /// `None` always exceed it. Always succeeds if there is no cap.
+    #[inline]
+    #[must_use = "prefer using `opcode_gas!` instead to halt the instruction on failure"]
+    pub fn record_opcode_gas(&mut self, cost: Option<u64>) -> bool {
+        let Some(cap) = self.opcode_gas_cap else {
+            return true;
+        };
+        match cost.and_then(|cost| self.opcode_gas.checked_add(cost)) {
+            Some(opcode_gas) if opcode_gas <= cap => {
+                self.opcode_gas = opcode_gas;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if running out of gas is not enforced.
+    #[inline]
+    pub const fn is_unmetered(&self) -> bool {
+        self.unmetered
+    }
+
+    /// Sets whether running out of gas is enforced.
+    ///
+    /// When unmetered, [`record_cost`](Self::record_cost) always succeeds and costs above the
+    /// remaining gas are added to [`exceeded`](Self::exceeded), which is included in
+    /// [`spent`](Self::spent).
+    #[inline]
+    pub fn set_unmetered(&mut self, unmetered: bool) {
+        self.unmetered = unmetered;
+    }
+
+    /// Returns the gas spent above the limit while unmetered.
+    #[inline]
+    pub const fn exceeded(&self) -> u64 {
+        self.exceeded
+    }
+
+    /// Records gas spent above the limit, e.g. by an unmetered child frame.
+    #[inline]
+    pub fn record_exceeded(&mut self, exceeded: u64) {
+        self.exceeded += exceeded;
+    }
+
     /// Returns the **last** memory expansion cost.
     #[inline]
     #[deprecated = "memory expansion cost is not tracked anymore; \
@@ -64,10 +149,10 @@ impl Gas {
         self.refunded
     }
 
-    /// Returns the total amount of gas spent.
+    /// Returns the total amount of gas spent, including the gas spent above the limit.
     #[inline]
     pub const fn spent(&self) -> u64 {
-        self.limit - self.remaining
+        self.limit - self.remaining + self.exceeded
     }
 
     /// Returns the amount of gas remaining.
@@ -109,8 +194,22 @@ impl Gas {
     /// Related to EIP-3529: Reduction in refunds
     #[inline]
     pub fn set_final_refund(&mut self, is_london: bool) {
-        let max_refund_quotient = if is_london { 5 } else { 2 };
-        self.refunded = (self.refunded() as u64).min(self.spent() / max_refund_quotient) as i64;
+        self.set_final_refund_with_quotient(if is_london { 5 } else { 2 });
+    }
+
+    /// Set a refund value for final refund, limited to `1 / max_refund_quotient` of gas spend.
+    ///
+    /// A quotient of zero disables refunds.
+    #[inline]
+    pub fn set_final_refund_with_quotient(&mut self, max_refund_quotient: u64) {
+        let max_refund = self.spent().checked_div(max_refund_quotient).unwrap_or(0);
+        self.refunded = (self.refunded() as u64).min(max_refund) as i64;
+    }
+
+    /// Sets the total amount of gas spent, keeping the gas limit.
+    #[inline]
+    pub fn set_spent(&mut self, spent: u64) {
+        self.remaining = self.limit.saturating_sub(spent);
     }
 
     /// Set a refund value. This overrides the current refund value.
@@ -121,7 +220,7 @@ impl Gas {
 
     /// Records an explicit cost.
     ///
-    /// Returns `false` if the gas limit is exceeded.
+    /// Returns `false` if the gas limit is exceeded, unless unmetered.
     #[inline]
     #[must_use = "prefer using `gas!` instead to return an out-of-gas error on failure"]
     pub fn record_cost(&mut self, cost: u64) -> bool {
@@ -129,6 +228,10 @@ impl Gas {
         let success = !overflow;
         if success {
             self.remaining = remaining;
+        } else if self.unmetered {
+            self.exceeded += cost - self.remaining;
+            self.remaining = 0;
+            return true;
         }
         success
     }
@@ -141,6 +244,10 @@ impl Gas {
             return MemoryExtensionResult::Same;
         };
 
+        if !self.record_opcode_gas(Some(additional_cost)) {
+            return MemoryExtensionResult::OpcodeGasCapExceeded;
+        }
+
         if !self.record_cost(additional_cost) {
             return MemoryExtensionResult::OutOfGas;
         }
@@ -156,6 +263,8 @@ pub enum MemoryExtensionResult {
     Same,
     /// Not enough gas to extend memory.s
     OutOfGas,
+    /// Expansion cost exceeds the opcode gas cap.
+    OpcodeGasCapExceeded,
 }
 
 /// Utility struct that speeds up calculation of memory expansion
@@ -56,10 +56,12 @@ pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
     }
 }
 
-/// `CREATE2` opcode cost calculation.
+/// `CREATE2` opcode cost calculation, hashing the init code costs `word_cost` gas per word.
+///
+/// Spec cost per word is [`KECCAK256WORD`].
 #[inline]
-pub const fn create2_cost(len: usize) -> Option<u64> {
-    CREATE.checked_add(tri!(cost_per_word(len, KECCAK256WORD)))
+pub const fn create2_cost(len: usize, word_cost: u64) -> Option<u64> {
+    CREATE.checked_add(tri!(cost_per_word(len, word_cost)))
 }
 
 #[inline]
@@ -138,10 +140,12 @@ pub const fn log_cost(n: u8, len: u64) -> Option<u64> {
     tri!(LOG.checked_add(tri!(LOGDATA.checked_mul(len)))).checked_add(LOGTOPIC * n as u64)
 }
 
-/// `KECCAK256` opcode cost calculation.
+/// `KECCAK256` opcode cost calculation, hashing costs `word_cost` gas per word.
+///
+/// Spec cost per word is [`KECCAK256WORD`].
 #[inline]
-pub const fn keccak256_cost(len: usize) -> Option<u64> {
-    KECCAK256.checked_add(tri!(cost_per_word(len, KECCAK256WORD)))
+pub const fn keccak256_cost(len: usize, word_cost: u64) -> Option<u64> {
+    KECCAK256.checked_add(tri!(cost_per_word(len, word_cost)))
 }
 
 /// Calculate the cost of buffer per word.
@@ -398,3 +402,62 @@ pub fn validate_initial_tx_gas(
 
     initial_gas
 }
+
+/// Initial gas of a transaction together with its EIP-7623 floor gas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InitialAndFloorGas {
+    /// Gas deducted before execution, see [`validate_initial_tx_gas`].
+    pub initial_gas: u64,
+    /// Minimum gas the transaction is charged, zero before Prague.
+    pub floor_gas: u64,
+}
+
+/// Calculates the initial gas and, since Prague, the EIP-7623 floor gas of a transaction.
+pub fn calculate_initial_tx_gas(
+    spec_id: SpecId,
+    input: &[u8],
+    is_create: bool,
+    access_list_accounts: u64,
+    access_list_storages: u64,
+    authorization_list_num: u64,
+) -> InitialAndFloorGas {
+    let initial_gas = validate_initial_tx_gas(
+        spec_id,
+        input,
+        is_create,
+        access_list_accounts,
+        access_list_storages,
+        authorization_list_num,
+    );
+    let floor_gas = if spec_id.is_enabled_in(SpecId::PRAGUE) {
+        calc_tx_floor_cost(get_tokens_in_calldata(input, true))
+    } else {
+        0
+    };
+    InitialAndFloorGas {
+        initial_gas,
+        floor_gas,
+    }
+}
+
+/// Returns the number of EIP-7623 calldata tokens.
+///
+/// Zero bytes count as one token and non-zero bytes as four tokens, or seventeen before
+/// Istanbul (EIP-2028).
+pub fn get_tokens_in_calldata(input: &[u8], is_istanbul: bool) -> u64 {
+    let zero_data_len = input.iter().filter(|v| **v == 0).count() as u64;
+    let non_zero_data_len = input.len() as u64 - zero_data_len;
+    let non_zero_data_multiplier = if is_istanbul {
+        TRANSACTION_NON_ZERO_DATA_INIT / STANDARD_TOKEN_COST
+    } else {
+        TRANSACTION_NON_ZERO_DATA_FRONTIER / STANDARD_TOKEN_COST
+    };
+    zero_data_len + non_zero_data_len * non_zero_data_multiplier
+}
+
+/// Returns the EIP-7623 floor gas for the given number of calldata tokens.
+#[inline]
+pub const fn calc_tx_floor_cost(tokens_in_calldata: u64) -> u64 {
+    tokens_in_calldata * TOTAL_COST_FLOOR_PER_TOKEN + 21000
+}
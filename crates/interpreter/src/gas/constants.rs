@@ -37,6 +37,10 @@ pub const TRANSACTION_ZERO_DATA: u64 = 4;
 pub const TRANSACTION_NON_ZERO_DATA_INIT: u64 = 16;
 pub const TRANSACTION_NON_ZERO_DATA_FRONTIER: u64 = 68;
 
+/// EIP-7623: Increase calldata cost
+pub const STANDARD_TOKEN_COST: u64 = 4;
+pub const TOTAL_COST_FLOOR_PER_TOKEN: u64 = 10;
+
 pub const EOF_CREATE_GAS: u64 = 32000;
 
 // Berlin eip2929 constants
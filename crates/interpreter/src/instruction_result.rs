@@ -98,6 +98,10 @@ pub enum InstructionResult {
     EofAuxDataTooSmall,
     /// `EXT*CALL` target address needs to be padded with 0s.
     InvalidEXTCALLTarget,
+    /// Gas spent by a single instruction exceeded the configured per opcode gas cap.
+    OpcodeGasCapExceeded,
+    /// Execution did not finish before the configured deadline.
+    Timeout,
 }
 
 impl From<TransferError> for InstructionResult {
@@ -156,6 +160,8 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::EofAuxDataTooSmall => Self::EofAuxDataTooSmall,
             HaltReason::SubRoutineStackOverflow => Self::SubRoutineStackOverflow,
             HaltReason::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+            HaltReason::OpcodeGasCapExceeded => Self::OpcodeGasCapExceeded,
+            HaltReason::Timeout => Self::Timeout,
         }
     }
 }
@@ -215,6 +221,8 @@ macro_rules! return_error {
             | $crate::InstructionResult::EofAuxDataTooSmall
             | $crate::InstructionResult::EofAuxDataOverflow
             | $crate::InstructionResult::InvalidEXTCALLTarget
+            | $crate::InstructionResult::OpcodeGasCapExceeded
+            | $crate::InstructionResult::Timeout
     };
 }
 
@@ -246,6 +254,62 @@ impl InstructionResult {
     pub const fn is_error(self) -> bool {
         matches!(self, return_error!())
     }
+
+    /// Returns whether the result is an exceptional halt, including running out of gas.
+    ///
+    /// Unlike [`is_error`][Self::is_error], fatal external errors are not halts.
+    #[inline]
+    pub const fn is_halt(self) -> bool {
+        self.is_error() && !matches!(self, InstructionResult::FatalExternalError)
+    }
+
+    /// Returns whether the result is any of the out of gas errors.
+    #[inline]
+    pub const fn is_out_of_gas(self) -> bool {
+        matches!(
+            self,
+            InstructionResult::OutOfGas
+                | InstructionResult::MemoryOOG
+                | InstructionResult::MemoryLimitOOG
+                | InstructionResult::PrecompileOOG
+                | InstructionResult::InvalidOperandOOG
+                | InstructionResult::ReentrancySentryOOG
+        )
+    }
+
+    /// Returns the category of the result.
+    #[inline]
+    pub const fn category(self) -> ResultCategory {
+        match self {
+            InstructionResult::Continue => ResultCategory::Continue,
+            InstructionResult::CallOrCreate => ResultCategory::CallOrCreate,
+            InstructionResult::FatalExternalError => ResultCategory::FatalError,
+            _ if self.is_ok() => ResultCategory::Success,
+            _ if self.is_revert() => ResultCategory::Revert,
+            _ if self.is_out_of_gas() => ResultCategory::OutOfGas,
+            _ => ResultCategory::Halt,
+        }
+    }
+}
+
+/// Coarse classification of an [`InstructionResult`], see [`InstructionResult::category`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResultCategory {
+    /// Execution continues with the next instruction.
+    Continue,
+    /// Execution is paused for a call or contract creation.
+    CallOrCreate,
+    /// Execution stopped successfully.
+    Success,
+    /// Execution reverted, the remaining gas is returned.
+    Revert,
+    /// Execution ran out of gas.
+    OutOfGas,
+    /// Execution halted exceptionally for a reason other than gas.
+    Halt,
+    /// External error, e.g. of the database, that aborts the transaction.
+    FatalError,
 }
 
 /// Internal result that are not ex
@@ -389,6 +453,10 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
             InstructionResult::InvalidEXTCALLTarget => {
                 Self::Halt(HaltReason::InvalidEXTCALLTarget.into())
             }
+            InstructionResult::OpcodeGasCapExceeded => {
+                Self::Halt(HaltReason::OpcodeGasCapExceeded.into())
+            }
+            InstructionResult::Timeout => Self::Halt(HaltReason::Timeout.into()),
             InstructionResult::InvalidExtDelegateCallTarget => {
                 Self::Internal(InternalResult::InvalidExtDelegateCallTarget)
             }
@@ -460,6 +528,8 @@ mod tests {
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::OpcodeGasCapExceeded,
+            InstructionResult::Timeout,
         ];
 
         for result in error_results {
@@ -468,4 +538,71 @@ mod tests {
             assert!(result.is_error());
         }
     }
+
+    #[test]
+    fn results_are_categorized() {
+        use crate::ResultCategory;
+
+        let cases = [
+            (InstructionResult::Continue, ResultCategory::Continue),
+            (
+                InstructionResult::CallOrCreate,
+                ResultCategory::CallOrCreate,
+            ),
+            (InstructionResult::Stop, ResultCategory::Success),
+            (InstructionResult::Return, ResultCategory::Success),
+            (InstructionResult::SelfDestruct, ResultCategory::Success),
+            (InstructionResult::ReturnContract, ResultCategory::Success),
+            (InstructionResult::Revert, ResultCategory::Revert),
+            (InstructionResult::CallTooDeep, ResultCategory::Revert),
+            (InstructionResult::OutOfFunds, ResultCategory::Revert),
+            (
+                InstructionResult::CreateInitCodeStartingEF00,
+                ResultCategory::Revert,
+            ),
+            (
+                InstructionResult::InvalidEOFInitCode,
+                ResultCategory::Revert,
+            ),
+            (
+                InstructionResult::InvalidExtDelegateCallTarget,
+                ResultCategory::Revert,
+            ),
+            (InstructionResult::OutOfGas, ResultCategory::OutOfGas),
+            (InstructionResult::MemoryOOG, ResultCategory::OutOfGas),
+            (InstructionResult::MemoryLimitOOG, ResultCategory::OutOfGas),
+            (InstructionResult::PrecompileOOG, ResultCategory::OutOfGas),
+            (
+                InstructionResult::InvalidOperandOOG,
+                ResultCategory::OutOfGas,
+            ),
+            (
+                InstructionResult::ReentrancySentryOOG,
+                ResultCategory::OutOfGas,
+            ),
+            (InstructionResult::OpcodeNotFound, ResultCategory::Halt),
+            (InstructionResult::InvalidJump, ResultCategory::Halt),
+            (InstructionResult::StackOverflow, ResultCategory::Halt),
+            (InstructionResult::CreateCollision, ResultCategory::Halt),
+            (InstructionResult::PrecompileError, ResultCategory::Halt),
+            (
+                InstructionResult::OpcodeGasCapExceeded,
+                ResultCategory::Halt,
+            ),
+            (InstructionResult::Timeout, ResultCategory::Halt),
+            (
+                InstructionResult::FatalExternalError,
+                ResultCategory::FatalError,
+            ),
+        ];
+
+        for (result, category) in cases {
+            assert_eq!(result.category(), category, "{result:?}");
+            assert_eq!(result.is_out_of_gas(), category == ResultCategory::OutOfGas);
+            assert_eq!(
+                result.is_halt(),
+                matches!(category, ResultCategory::OutOfGas | ResultCategory::Halt)
+            );
+        }
+    }
 }
@@ -98,7 +98,9 @@ pub fn exp<WIRE: InterpreterTypes, H: Host + ?Sized>(
 ) {
     let spec_id = interpreter.runtime_flag.spec_id();
     popn_top!([op1], op2, interpreter);
-    gas_or_fail!(interpreter, gas::exp_cost(spec_id, *op2));
+    let cost = gas::exp_cost(spec_id, *op2);
+    opcode_gas!(interpreter, cost.map(|cost| cost - gas::EXP));
+    gas_or_fail!(interpreter, cost);
     *op2 = op1.pow(*op2);
 }
 
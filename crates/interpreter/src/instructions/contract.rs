@@ -24,7 +24,7 @@ use std::boxed::Box;
 /// EOF Create instruction
 pub fn eofcreate<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     require_eof!(interpreter);
     require_non_staticcall!(interpreter);
@@ -58,7 +58,9 @@ pub fn eofcreate<WIRE: InterpreterTypes, H: Host + ?Sized>(
     }
 
     // Deduct gas for hash that is needed to calculate address.
-    gas_or_fail!(interpreter, cost_per_word(container.len(), KECCAK256WORD));
+    let word_cost = host.cfg().keccak_word_cost().unwrap_or(KECCAK256WORD);
+    opcode_gas!(interpreter, cost_per_word(container.len(), word_cost));
+    gas_or_fail!(interpreter, cost_per_word(container.len(), word_cost));
 
     let created_address = interpreter
         .input
@@ -148,11 +150,7 @@ pub fn return_contract<H: Host + ?Sized>(
     let gas = *interpreter.control.gas();
     interpreter.control.set_next_action(
         crate::InterpreterAction::Return {
-            result: InterpreterResult {
-                output,
-                gas,
-                result,
-            },
+            result: InterpreterResult::new(result, output, gas),
         },
         result,
     );
@@ -270,12 +268,16 @@ pub fn extcall<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let Some(gas_limit) = extcall_gas_calc(interpreter, host, target_address, has_transfer) else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining() + gas_limit;
 
     // Call host to interact with target contract
     interpreter.control.set_next_action(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded: gas_limit,
+            stipend_added: 0,
+            gas_before_call,
             target_address,
             caller: interpreter.input.target_address(),
             bytecode_address: target_address,
@@ -308,12 +310,16 @@ pub fn extdelegatecall<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let Some(gas_limit) = extcall_gas_calc(interpreter, host, target_address, false) else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining() + gas_limit;
 
     // Call host to interact with target contract
     interpreter.control.set_next_action(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded: gas_limit,
+            stipend_added: 0,
+            gas_before_call,
             target_address: interpreter.input.target_address(),
             caller: interpreter.input.caller_address(),
             bytecode_address: target_address,
@@ -346,12 +352,16 @@ pub fn extstaticcall<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let Some(gas_limit) = extcall_gas_calc(interpreter, host, target_address, false) else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining() + gas_limit;
 
     // Call host to interact with target contract
     interpreter.control.set_next_action(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded: gas_limit,
+            stipend_added: 0,
+            gas_before_call,
             target_address,
             caller: interpreter.input.target_address(),
             bytecode_address: target_address,
@@ -387,14 +397,14 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
             .spec_id()
             .is_enabled_in(SpecId::SHANGHAI)
         {
-            // Limit is set as double of max contract bytecode size
-            let max_initcode_size = host.cfg().max_code_size().saturating_mul(2);
+            let max_initcode_size = host.cfg().max_initcode_size();
             if len > max_initcode_size {
                 interpreter
                     .control
                     .set_instruction_result(InstructionResult::CreateInitCodeSizeLimit);
                 return;
             }
+            opcode_gas!(interpreter, Some(gas::initcode_cost(len)));
             gas!(interpreter, gas::initcode_cost(len));
         }
 
@@ -407,7 +417,9 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
     let scheme = if IS_CREATE2 {
         popn!([salt], interpreter);
         // SAFETY: `len` is reasonable in size as gas for it is already deducted.
-        gas_or_fail!(interpreter, gas::create2_cost(len));
+        let word_cost = host.cfg().keccak_word_cost().unwrap_or(gas::KECCAK256WORD);
+        opcode_gas!(interpreter, cost_per_word(len, word_cost));
+        gas_or_fail!(interpreter, gas::create2_cost(len, word_cost));
         CreateScheme::Create2 { salt }
     } else {
         gas!(interpreter, gas::CREATE);
@@ -467,24 +479,31 @@ pub fn call<WIRE: InterpreterTypes, H: Host + ?Sized>(
             .set_instruction_result(InstructionResult::FatalExternalError);
         return;
     };
-    let Some(mut gas_limit) =
+    let Some(gas_limit_forwarded) =
         calc_call_gas(interpreter, account_load, has_transfer, local_gas_limit)
     else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining();
 
-    gas!(interpreter, gas_limit);
+    gas!(interpreter, gas_limit_forwarded);
 
     // Add call stipend if there is value to be transferred.
-    if has_transfer {
-        gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
-    }
+    let stipend_added = if has_transfer {
+        host.cfg().call_stipend().unwrap_or(gas::CALL_STIPEND)
+    } else {
+        0
+    };
+    let gas_limit = gas_limit_forwarded.saturating_add(stipend_added);
 
     // Call host to interact with target contract
     interpreter.control.set_next_action(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded,
+            stipend_added,
+            gas_before_call,
             target_address: to,
             caller: interpreter.input.target_address(),
             bytecode_address: to,
@@ -520,23 +539,31 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
     };
     // Set `is_empty` to false as we are not creating this account.
     load.is_empty = false;
-    let Some(mut gas_limit) = calc_call_gas(interpreter, load, !value.is_zero(), local_gas_limit)
+    let Some(gas_limit_forwarded) =
+        calc_call_gas(interpreter, load, !value.is_zero(), local_gas_limit)
     else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining();
 
-    gas!(interpreter, gas_limit);
+    gas!(interpreter, gas_limit_forwarded);
 
     // Add call stipend if there is value to be transferred.
-    if !value.is_zero() {
-        gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
-    }
+    let stipend_added = if !value.is_zero() {
+        host.cfg().call_stipend().unwrap_or(gas::CALL_STIPEND)
+    } else {
+        0
+    };
+    let gas_limit = gas_limit_forwarded.saturating_add(stipend_added);
 
     // Call host to interact with target contract
     interpreter.control.set_next_action(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded,
+            stipend_added,
+            gas_before_call,
             target_address: interpreter.input.target_address(),
             caller: interpreter.input.target_address(),
             bytecode_address: to,
@@ -575,6 +602,7 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let Some(gas_limit) = calc_call_gas(interpreter, load, false, local_gas_limit) else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining();
 
     gas!(interpreter, gas_limit);
 
@@ -583,6 +611,9 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded: gas_limit,
+            stipend_added: 0,
+            gas_before_call,
             target_address: interpreter.input.target_address(),
             caller: interpreter.input.caller_address(),
             bytecode_address: to,
@@ -621,6 +652,7 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let Some(gas_limit) = calc_call_gas(interpreter, load, false, local_gas_limit) else {
         return;
     };
+    let gas_before_call = interpreter.control.gas().remaining();
     gas!(interpreter, gas_limit);
 
     // Call host to interact with target contract
@@ -628,6 +660,9 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
         InterpreterAction::NewFrame(FrameInput::Call(Box::new(CallInputs {
             input,
             gas_limit,
+            gas_limit_forwarded: gas_limit,
+            stipend_added: 0,
+            gas_before_call,
             target_address: to,
             caller: interpreter.input.target_address(),
             bytecode_address: to,
@@ -640,3 +675,126 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
         InstructionResult::CallOrCreate,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        gas,
+        interpreter::{EthInterpreter, ExtBytecode, InputsImpl, SharedMemory},
+        table::make_instruction_table,
+        FrameInput, Interpreter, InterpreterAction,
+    };
+    use bytecode::{
+        opcode::{CALL, CREATE2, PUSH1, PUSH2, PUSH20},
+        Bytecode,
+    };
+    use context::{BlockEnv, CfgEnv, TxEnv};
+    use context_interface::host::DummyHost;
+    use core::cell::RefCell;
+    use primitives::{Address, Bytes};
+    use specification::hardfork::SpecId;
+    use std::{rc::Rc, vec};
+
+    type Host = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+    /// Runs `code` until it creates a new frame, returns its input and the gas left to the caller.
+    fn run(code: &[u8], configure: impl FnOnce(&mut CfgEnv)) -> (FrameInput, u64) {
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(code))),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::LATEST,
+            1_000_000,
+        );
+        let mut host = Host::default();
+        configure(&mut host.cfg);
+
+        let table = make_instruction_table::<EthInterpreter, Host>();
+        let action = interpreter.run(&table, &mut host);
+        let InterpreterAction::NewFrame(frame_input) = action else {
+            panic!("expected a new frame, got {action:?}");
+        };
+        (frame_input, interpreter.control.gas.remaining())
+    }
+
+    #[test]
+    fn keccak_word_cost_override_applies_to_create2() {
+        // CREATE2 of 1024 zero bytes of init code.
+        let code = [PUSH1, 0, PUSH2, 0x04, 0x00, PUSH1, 0, PUSH1, 0, CREATE2];
+        // Gas left before the remaining gas is forwarded to the new frame.
+        let gas_left = |keccak_word_cost| {
+            let (FrameInput::Create(inputs), remaining) =
+                run(&code, |cfg| cfg.keccak_word_cost = keccak_word_cost)
+            else {
+                panic!("expected create");
+            };
+            remaining + inputs.gas_limit
+        };
+
+        let words = 1024 / 32;
+        assert_eq!(
+            gas_left(None) - gas_left(Some(10)),
+            (10 - gas::KECCAK256WORD) * words
+        );
+        assert_eq!(gas_left(Some(gas::KECCAK256WORD)), gas_left(None));
+    }
+
+    #[test]
+    fn call_stipend_override() {
+        // Calls with one wei and no gas.
+        let mut code = vec![
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x01,
+        ];
+        code.push(PUSH20);
+        code.extend_from_slice(Address::with_last_byte(0xbb).as_slice());
+        code.extend_from_slice(&[PUSH1, 0x00, CALL]);
+        let child_gas = |call_stipend| {
+            let (FrameInput::Call(inputs), _) = run(&code, |cfg| cfg.call_stipend = call_stipend)
+            else {
+                panic!("expected call");
+            };
+            inputs.gas_limit
+        };
+
+        assert_eq!(child_gas(None), gas::CALL_STIPEND);
+        assert_eq!(child_gas(Some(10_000)), 10_000);
+        assert_eq!(child_gas(Some(0)), 0);
+    }
+
+    #[test]
+    fn call_inputs_split_forwarded_gas_and_stipend() {
+        // Calls with `value` wei and 50_000 gas.
+        let call = |value| {
+            let mut code = vec![
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, value,
+            ];
+            code.push(PUSH20);
+            code.extend_from_slice(Address::with_last_byte(0xbb).as_slice());
+            code.extend_from_slice(&[PUSH2, 0xc3, 0x50, CALL]);
+            let (FrameInput::Call(inputs), remaining) = run(&code, |_| {}) else {
+                panic!("expected call");
+            };
+            (inputs, remaining)
+        };
+
+        let (inputs, remaining) = call(1);
+        assert_eq!(inputs.gas_limit_forwarded, 50_000);
+        assert_eq!(inputs.stipend_added, gas::CALL_STIPEND);
+        assert_eq!(inputs.gas_limit, 50_000 + gas::CALL_STIPEND);
+        assert_eq!(
+            inputs.gas_before_call - inputs.gas_limit_forwarded,
+            remaining
+        );
+
+        let (inputs, remaining) = call(0);
+        assert_eq!(inputs.gas_limit_forwarded, 50_000);
+        assert_eq!(inputs.stipend_added, 0);
+        assert_eq!(inputs.gas_limit, 50_000);
+        assert_eq!(
+            inputs.gas_before_call - inputs.gas_limit_forwarded,
+            remaining
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
     },
     Host, InstructionResult, InterpreterAction, InterpreterResult,
 };
+use context_interface::Cfg;
 use primitives::{Bytes, U256};
 
 pub fn rjump<WIRE: InterpreterTypes, H: ?Sized>(
@@ -197,12 +198,20 @@ pub fn pc<WIRE: InterpreterTypes, H: Host + ?Sized>(
 #[inline]
 fn return_inner(
     interpreter: &mut Interpreter<impl InterpreterTypes>,
+    max_return_size: Option<usize>,
     instruction_result: InstructionResult,
 ) {
     // Zero gas cost
     // gas!(interpreter, gas::ZERO)
     popn!([offset, len], interpreter);
-    let len = as_usize_or_fail!(interpreter, len);
+    let mut len = as_usize_or_fail!(interpreter, len);
+    // Init code output is the code of the new contract, only return data of calls is limited.
+    let max_return_size = max_return_size.filter(|_| !interpreter.runtime_flag.is_legacy_init());
+    // Output over the limit is cut off before memory is expanded.
+    let output_truncated = max_return_size.is_some_and(|max| len > max);
+    if let Some(max) = max_return_size {
+        len = len.min(max);
+    }
     // Important: Offset must be ignored if len is zeros
     let mut output = Bytes::default();
     if len != 0 {
@@ -218,6 +227,8 @@ fn return_inner(
                 output,
                 gas,
                 result: instruction_result,
+                output_truncated,
+                halt_context: None,
             },
         },
         instruction_result,
@@ -226,18 +237,26 @@ fn return_inner(
 
 pub fn ret<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
-    return_inner(interpreter, InstructionResult::Return);
+    return_inner(
+        interpreter,
+        host.cfg().max_return_size(),
+        InstructionResult::Return,
+    );
 }
 
 /// EIP-140: REVERT instruction
 pub fn revert<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     check!(interpreter, BYZANTIUM);
-    return_inner(interpreter, InstructionResult::Revert);
+    return_inner(
+        interpreter,
+        host.cfg().max_return_size(),
+        InstructionResult::Revert,
+    );
 }
 
 /// Stop opcode. This opcode halts the execution.
@@ -270,6 +289,75 @@ pub fn unknown<WIRE: InterpreterTypes, H: Host + ?Sized>(
         .set_instruction_result(InstructionResult::OpcodeNotFound);
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::{
+        interpreter::{EthInterpreter, ExtBytecode, InputsImpl, SharedMemory},
+        table::make_instruction_table,
+        Interpreter, InterpreterAction, InterpreterResult,
+    };
+    use bytecode::{
+        opcode::{PUSH1, PUSH3, RETURN},
+        Bytecode,
+    };
+    use context::{BlockEnv, CfgEnv, TxEnv};
+    use context_interface::host::DummyHost;
+    use core::cell::RefCell;
+    use primitives::Bytes;
+    use specification::hardfork::SpecId;
+    use std::rc::Rc;
+
+    type Host = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+    fn run(code: &[u8], is_legacy_init: bool, max_return_size: Option<usize>) -> InterpreterResult {
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(code))),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::LATEST,
+            10_000_000,
+        );
+        interpreter.runtime_flag.is_legacy_init = is_legacy_init;
+        let mut host = Host::default();
+        host.cfg.max_return_size = max_return_size;
+
+        let table = make_instruction_table::<EthInterpreter, Host>();
+        let action = interpreter.run(&table, &mut host);
+        let InterpreterAction::Return { result } = action else {
+            panic!("expected return, got {action:?}");
+        };
+        result
+    }
+
+    #[test]
+    fn return_output_truncated_to_max_return_size() {
+        // Returns 1MB of zeroed memory.
+        let code = [PUSH3, 0x10, 0x00, 0x00, PUSH1, 0x00, RETURN];
+
+        let full = run(&code, false, None);
+        assert_eq!(full.output.len(), 1 << 20);
+        assert!(!full.output_truncated);
+
+        let truncated = run(&code, false, Some(64 * 1024));
+        assert_eq!(truncated.output.len(), 64 * 1024);
+        assert!(truncated.output_truncated);
+        // Memory is only charged up to the limit.
+        assert!(truncated.gas.spent() < full.gas.spent());
+    }
+
+    #[test]
+    fn max_return_size_does_not_truncate_init_code_output() {
+        // Returns 64 bytes of zeroed memory.
+        let code = [PUSH1, 0x40, PUSH1, 0x00, RETURN];
+
+        let result = run(&code, true, Some(32));
+        assert_eq!(result.output.len(), 64);
+        assert!(!result.output_truncated);
+    }
+}
+
 // TODO : Test
 /*
 #[cfg(test)]
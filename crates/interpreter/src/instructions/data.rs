@@ -72,6 +72,7 @@ pub fn data_copy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     }
     // Fail if mem offset is big as it will spend all the gas
     let mem_offset = as_usize_or_fail!(interpreter, mem_offset);
+    opcode_gas!(interpreter, cost_per_word(size, VERYLOW));
     resize_memory!(interpreter, mem_offset, size);
 
     gas_or_fail!(interpreter, cost_per_word(size, VERYLOW));
@@ -7,6 +7,7 @@ use crate::{
     },
     Host, InstructionResult,
 };
+use context_interface::Cfg;
 use core::cmp::min;
 use primitives::{Bytes, Log, LogData, B256, U256};
 use specification::hardfork::SpecId::*;
@@ -122,6 +123,7 @@ pub fn extcodecopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     let len = as_usize_or_fail!(interpreter, len_u256);
     let (code, load) = code.into_components();
+    opcode_gas!(interpreter, gas::cost_per_word(len, gas::COPY));
     gas_or_fail!(
         interpreter,
         gas::extcodecopy_cost(interpreter.runtime_flag.spec_id(), len, load)
@@ -188,8 +190,15 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(
         return;
     };
 
+    // Gas rules follow the configured model, if any.
+    let spec_id = host
+        .cfg()
+        .sstore_model()
+        .map_or(interpreter.runtime_flag.spec_id(), |model| model.spec_id());
+
     // EIP-1706 Disable SSTORE with gasleft lower than call stipend
-    if interpreter.runtime_flag.spec_id().is_enabled_in(ISTANBUL)
+    if spec_id.is_enabled_in(ISTANBUL)
+        && !interpreter.control.gas().is_unmetered()
         && interpreter.control.gas().remaining() <= CALL_STIPEND
     {
         interpreter
@@ -199,17 +208,13 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(
     }
     gas!(
         interpreter,
-        gas::sstore_cost(
-            interpreter.runtime_flag.spec_id(),
-            &state_load.data,
-            state_load.is_cold
-        )
+        gas::sstore_cost(spec_id, &state_load.data, state_load.is_cold)
     );
 
-    interpreter.control.gas().record_refund(gas::sstore_refund(
-        interpreter.runtime_flag.spec_id(),
-        &state_load.data,
-    ));
+    interpreter
+        .control
+        .gas()
+        .record_refund(gas::sstore_refund(spec_id, &state_load.data));
 }
 
 /// EIP-1153: Transient storage opcodes
@@ -249,6 +254,7 @@ pub fn log<const N: usize, H: Host + ?Sized>(
 
     popn!([offset, len], interpreter);
     let len = as_usize_or_fail!(interpreter, len);
+    opcode_gas!(interpreter, gas::LOGDATA.checked_mul(len as u64));
     gas_or_fail!(interpreter, gas::log_cost(N as u8, len as u64));
     let data = if len == 0 {
         Bytes::new()
@@ -99,6 +99,25 @@ macro_rules! gas_or_fail {
     };
 }
 
+/// Counts the dynamic `gas` of the instruction, given as an option, against the opcode gas cap
+/// and fails the instruction if it is exceeded.
+///
+/// The gas is not recorded, it is charged separately with [`gas!`] or [`gas_or_fail!`].
+#[macro_export]
+macro_rules! opcode_gas {
+    ($interpreter:expr, $gas:expr) => {
+        $crate::opcode_gas!($interpreter, $gas, ())
+    };
+    ($interpreter:expr, $gas:expr, $ret:expr) => {
+        if !$interpreter.control.gas().record_opcode_gas($gas) {
+            $interpreter
+                .control
+                .set_instruction_result($crate::InstructionResult::OpcodeGasCapExceeded);
+            return $ret;
+        }
+    };
+}
+
 /// Resizes the interpreterreter memory if necessary. Fails the instruction if the memory or gas limit
 /// is exceeded.
 #[macro_export]
@@ -122,6 +141,12 @@ macro_rules! resize_memory {
                     .set_instruction_result($crate::InstructionResult::MemoryOOG);
                 return $ret;
             }
+            $crate::gas::MemoryExtensionResult::OpcodeGasCapExceeded => {
+                $interpreter
+                    .control
+                    .set_instruction_result($crate::InstructionResult::OpcodeGasCapExceeded);
+                return $ret;
+            }
             $crate::gas::MemoryExtensionResult::Same => (), // no action
         };
     };
@@ -59,6 +59,7 @@ pub fn mcopy<WIRE: InterpreterTypes, H: Host + ?Sized>(
     // Into usize or fail
     let len = as_usize_or_fail!(interpreter, len);
     // Deduce gas
+    opcode_gas!(interpreter, gas::cost_per_word(len, gas::COPY));
     gas_or_fail!(interpreter, gas::copy_cost_verylow(len));
     if len == 0 {
         return;
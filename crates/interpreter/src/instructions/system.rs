@@ -7,16 +7,19 @@ use crate::{
     },
     Host, InstructionResult,
 };
+use context_interface::Cfg;
 use core::ptr;
 use primitives::{B256, KECCAK_EMPTY, U256};
 
 pub fn keccak256<WIRE: InterpreterTypes, H: Host + ?Sized>(
     interpreter: &mut Interpreter<WIRE>,
-    _host: &mut H,
+    host: &mut H,
 ) {
     popn_top!([offset], top, interpreter);
     let len = as_usize_or_fail!(interpreter, top);
-    gas_or_fail!(interpreter, gas::keccak256_cost(len));
+    let word_cost = host.cfg().keccak_word_cost().unwrap_or(gas::KECCAK256WORD);
+    opcode_gas!(interpreter, gas::cost_per_word(len, word_cost));
+    gas_or_fail!(interpreter, gas::keccak256_cost(len, word_cost));
     let hash = if len == 0 {
         KECCAK_EMPTY
     } else {
@@ -224,6 +227,7 @@ pub fn memory_resize(
     len: usize,
 ) -> Option<usize> {
     // Safe to cast usize to u64
+    opcode_gas!(interpreter, gas::cost_per_word(len, gas::COPY), None);
     gas_or_fail!(interpreter, gas::copy_cost_verylow(len), None);
     if len == 0 {
         return None;
@@ -4,7 +4,7 @@ use crate::{
     interpreter_types::{InterpreterTypes, LoopControl, RuntimeFlag, StackTrait},
     Host,
 };
-use context_interface::{Block, Transaction, TransactionType};
+use context_interface::{Block, Transaction};
 use primitives::U256;
 
 pub fn gasprice<WIRE: InterpreterTypes, H: Host + ?Sized>(
@@ -36,14 +36,8 @@ pub fn blob_hash<WIRE: InterpreterTypes, H: Host + ?Sized>(
     gas!(interpreter, gas::VERYLOW);
     popn_top!([], index, interpreter);
     let i = as_usize_saturated!(index);
-    let tx = &host.tx();
-    *index = if tx.tx_type() == TransactionType::Eip4844 {
-        tx.blob_versioned_hashes()
-            .get(i)
-            .cloned()
-            .map(|b| U256::from_be_bytes(*b))
-            .unwrap_or(U256::ZERO)
-    } else {
-        U256::ZERO
-    };
+    *index = host
+        .blob_hash(i)
+        .map(|b| U256::from_be_bytes(*b))
+        .unwrap_or(U256::ZERO);
 }
@@ -3,29 +3,49 @@ mod input;
 mod loop_control;
 mod return_data;
 mod runtime_flags;
-#[cfg(feature = "serde")]
-pub mod serde;
 mod shared_memory;
+mod small_stack;
 mod stack;
 mod subroutine_stack;
 
 use crate::{
-    interpreter_types::*, table::CustomInstruction, Gas, Host, Instruction, InstructionResult,
-    InterpreterAction,
+    gas,
+    interpreter_types::*,
+    table::{CustomInstruction, InstructionTableBuilder},
+    Gas, Host, Instruction, InstructionResult, InterpreterAction,
 };
-use core::cell::RefCell;
+use bytecode::{
+    legacy::{eof_static_gas, static_gas},
+    opcode::*,
+};
+use context_interface::{result::HaltContext, Cfg};
+use core::{cell::RefCell, marker::PhantomData};
 pub use ext_bytecode::ExtBytecode;
 pub use input::InputsImpl;
 use loop_control::LoopControl as LoopControlImpl;
-use primitives::Bytes;
+use primitives::{Address, Bytes};
 use return_data::ReturnDataImpl;
 pub use runtime_flags::RuntimeFlags;
 pub use shared_memory::{num_words, MemoryGetter, SharedMemory, EMPTY_SHARED_MEMORY};
+pub use small_stack::{SmallStack, SMALL_STACK_CAPACITY};
 use specification::hardfork::SpecId;
 pub use stack::{Stack, STACK_LIMIT};
-use std::rc::Rc;
+use std::{boxed::Box, rc::Rc};
 use subroutine_stack::SubRoutineImpl;
 
+/// Number of instructions executed between two checks of the deadline in
+/// [`Interpreter::run_with_deadline`].
+///
+/// Reading the clock is much slower than an instruction, checking in batches keeps the
+/// overhead negligible while bounding the overshoot to a few microseconds.
+#[cfg(feature = "std")]
+pub const DEADLINE_CHECK_INTERVAL: usize = 1024;
+
+/// EVM bytecode interpreter.
+///
+/// With the `serde` feature the interpreter can be serialized in the middle of the execution,
+/// including its stack, memory, gas, program counter and bytecode hash. The deserialized
+/// interpreter resumes execution from the same point.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Interpreter<WIRE: InterpreterTypes> {
@@ -40,7 +60,9 @@ pub struct Interpreter<WIRE: InterpreterTypes> {
     pub extend: WIRE::Extend,
 }
 
-impl<EXT: Default, MG: MemoryGetter> Interpreter<EthInterpreter<EXT, MG>> {
+impl<EXT: Default, MG: MemoryGetter, STACK: StackTrait + Default>
+    Interpreter<EthInterpreter<EXT, MG, STACK>>
+{
     /// Create new interpreter
     pub fn new(
         memory: Rc<RefCell<MG>>,
@@ -56,11 +78,12 @@ impl<EXT: Default, MG: MemoryGetter> Interpreter<EthInterpreter<EXT, MG>> {
             is_static,
             is_eof: bytecode.is_eof(),
             is_eof_init,
+            is_legacy_init: false,
         };
 
         Self {
             bytecode,
-            stack: Stack::new(),
+            stack: STACK::default(),
             return_data: ReturnDataImpl::default(),
             memory,
             input: inputs,
@@ -72,12 +95,17 @@ impl<EXT: Default, MG: MemoryGetter> Interpreter<EthInterpreter<EXT, MG>> {
     }
 }
 
-pub struct EthInterpreter<EXT = (), MG = SharedMemory> {
+/// Ethereum [`InterpreterTypes`].
+///
+/// `STACK` selects the stack implementation, [`SmallStack`] can be used instead of the default
+/// [`Stack`] to lower the memory used by shallow frames.
+pub struct EthInterpreter<EXT = (), MG = SharedMemory, STACK = Stack> {
     _phantom: core::marker::PhantomData<fn() -> (EXT, MG)>,
+    _stack: core::marker::PhantomData<fn() -> STACK>,
 }
 
-impl<EXT, MG: MemoryGetter> InterpreterTypes for EthInterpreter<EXT, MG> {
-    type Stack = Stack;
+impl<EXT, MG: MemoryGetter, STACK: StackTrait> InterpreterTypes for EthInterpreter<EXT, MG, STACK> {
+    type Stack = STACK;
     type Memory = Rc<RefCell<MG>>;
     type Bytecode = ExtBytecode;
     type ReturnData = ReturnDataImpl;
@@ -133,6 +161,61 @@ where
     }
 }
 
+/// Instructions overriding the mainnet ones, see [`OverrideInstructionProvider`].
+pub trait InstructionOverrides<WIRE: InterpreterTypes, HOST: Host> {
+    /// Overrides instructions of the builder.
+    ///
+    /// Called once per transaction, `context` can be used to pick the overrides at runtime.
+    fn apply(
+        builder: InstructionTableBuilder<WIRE, HOST>,
+        context: &mut HOST,
+    ) -> InstructionTableBuilder<WIRE, HOST>;
+}
+
+/// Instruction provider with the mainnet instructions overridden by `OVERRIDES`.
+///
+/// Can be used in place of [`EthInstructionProvider`] in the frame of a handler.
+pub struct OverrideInstructionProvider<OVERRIDES, WIRE: InterpreterTypes, HOST> {
+    instruction_table: Rc<[Instruction<WIRE, HOST>; 256]>,
+    _phantom: PhantomData<fn() -> OVERRIDES>,
+}
+
+impl<OVERRIDES, WIRE, HOST> Clone for OverrideInstructionProvider<OVERRIDES, WIRE, HOST>
+where
+    WIRE: InterpreterTypes,
+{
+    fn clone(&self) -> Self {
+        Self {
+            instruction_table: self.instruction_table.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<OVERRIDES, WIRE, HOST> InstructionProvider
+    for OverrideInstructionProvider<OVERRIDES, WIRE, HOST>
+where
+    OVERRIDES: InstructionOverrides<WIRE, HOST>,
+    WIRE: InterpreterTypes,
+    HOST: Host,
+{
+    type WIRE = WIRE;
+    type Host = HOST;
+
+    fn new(context: &mut Self::Host) -> Self {
+        Self {
+            instruction_table: Rc::new(
+                OVERRIDES::apply(InstructionTableBuilder::new(), context).build(),
+            ),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn table(&mut self) -> &[impl CustomInstruction<Wire = Self::WIRE, Host = Self::Host>; 256] {
+        self.instruction_table.as_ref()
+    }
+}
+
 impl<IW: InterpreterTypes, H: Host> CustomInstruction for Instruction<IW, H> {
     type Wire = IW;
     type Host = H;
@@ -149,6 +232,112 @@ impl<IW: InterpreterTypes, H: Host> CustomInstruction for Instruction<IW, H> {
 }
 
 impl<IW: InterpreterTypes> Interpreter<IW> {
+    /// Returns the current return data buffer.
+    ///
+    /// It contains the output of the last sub call made by this interpreter.
+    #[inline]
+    pub fn return_data(&self) -> &[u8] {
+        self.return_data.buffer()
+    }
+
+    /// Returns the length of the current return data buffer.
+    #[inline]
+    pub fn return_data_len(&self) -> usize {
+        self.return_data.buffer().len()
+    }
+
+    /// Returns the input data of the current frame, read by `CALLDATALOAD` and `CALLDATACOPY`.
+    ///
+    /// For the top level frame it is the transaction data.
+    #[inline]
+    pub fn input_data(&self) -> &[u8] {
+        self.input.input()
+    }
+
+    /// Returns the gas the next instruction will cost, without executing it.
+    ///
+    /// The cost is the [`static_gas`] or [`eof_static_gas`] of the opcode plus the dynamic costs that follow from the
+    /// stack and memory alone: memory expansion, copied, hashed and logged words, the `EXP`
+    /// exponent and the initcode of `CREATE` and `CREATE2`. Costs that depend on the state are
+    /// not included, accounts and slots are priced as warm accesses and `SSTORE` as a no-op.
+    /// For `CALL` and the other calls only the warm access is returned, without the value
+    /// transfer, new account and memory expansion costs, or the gas forwarded to the callee.
+    ///
+    /// Returns `None` for unknown opcodes, or if the stack does not hold the operands the cost
+    /// depends on.
+    pub fn peek_next_gas_cost<H: Host + ?Sized>(&self, host: &H) -> Option<u64> {
+        let opcode = self.bytecode.opcode();
+        OpCode::new(opcode)?;
+
+        let operand = |n| usize::try_from(self.stack.peek(n)?).ok();
+        let words = |len: usize| num_words(len) as u64;
+        // Expansion cost of `len` bytes at `offset`, memory is not touched for empty ranges.
+        let memory = |offset: usize, len: usize| {
+            if len == 0 {
+                return Some(0);
+            }
+            let new_words = num_words(offset.checked_add(len)?);
+            let old_words = num_words(self.memory.size());
+            Some(gas::memory_gas(new_words).saturating_sub(gas::memory_gas(old_words)))
+        };
+        let hash_word_cost = || host.cfg().keccak_word_cost().unwrap_or(gas::KECCAK256WORD);
+
+        let dynamic = match opcode {
+            KECCAK256 => {
+                let len = operand(1)?;
+                memory(operand(0)?, len)?.checked_add(words(len).checked_mul(hash_word_cost())?)?
+            }
+            CALLDATACOPY | CODECOPY | RETURNDATACOPY => {
+                let len = operand(2)?;
+                memory(operand(0)?, len)?.checked_add(words(len) * gas::COPY)?
+            }
+            EXTCODECOPY => {
+                let len = operand(3)?;
+                memory(operand(1)?, len)?.checked_add(words(len) * gas::COPY)?
+            }
+            MCOPY => {
+                let len = operand(2)?;
+                memory(operand(0)?.max(operand(1)?), len)?.checked_add(words(len) * gas::COPY)?
+            }
+            MLOAD | MSTORE => memory(operand(0)?, 32)?,
+            MSTORE8 => memory(operand(0)?, 1)?,
+            LOG0..=LOG4 => {
+                let len = operand(1)?;
+                memory(operand(0)?, len)?.checked_add((len as u64).checked_mul(gas::LOGDATA)?)?
+            }
+            RETURN | REVERT => memory(operand(0)?, operand(1)?)?,
+            EXP => gas::exp_cost(self.runtime_flag.spec_id(), self.stack.peek(1)?)? - gas::EXP,
+            CREATE | CREATE2 => {
+                let len = operand(2)?;
+                let mut cost = memory(operand(1)?, len)?;
+                if self.runtime_flag.spec_id().is_enabled_in(SpecId::SHANGHAI) {
+                    cost = cost.checked_add(gas::initcode_cost(len))?;
+                }
+                if opcode == CREATE2 {
+                    cost = cost.checked_add(words(len).checked_mul(hash_word_cost())?)?;
+                }
+                cost
+            }
+            _ => 0,
+        };
+        let static_gas = if self.runtime_flag.is_eof() {
+            eof_static_gas(opcode)
+        } else {
+            static_gas(opcode)
+        };
+        static_gas.checked_add(dynamic)
+    }
+
+    /// Returns the address the running code was loaded from, if the target account is an
+    /// EIP-7702 delegation to it.
+    ///
+    /// The delegated account is the [target address](InputsTrait::target_address), the hash of
+    /// the running code is the hash of the delegation source's code.
+    #[inline]
+    pub fn delegation_source(&self) -> Option<Address> {
+        self.input.delegation_source()
+    }
+
     /// Executes the instruction at the current instruction pointer.
     ///
     /// Internally it will increment instruction pointer by one.
@@ -186,6 +375,87 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
             self.step(instruction_table, host);
         }
 
+        self.take_action()
+    }
+
+    /// Executes the interpreter until it returns or stops, halting with
+    /// [`InstructionResult::OpcodeGasCapExceeded`] if the dynamic gas of an instruction, e.g. for
+    /// memory expansion, copying or `EXP` exponent bytes, exceeds `opcode_gas_cap`.
+    ///
+    /// The dynamic gas is checked before it is charged and before memory is expanded. Static
+    /// costs, including account access and storage costs, and gas forwarded to a sub call or
+    /// create are not counted against the cap.
+    pub fn run_with_opcode_gas_cap<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        opcode_gas_cap: u64,
+    ) -> InterpreterAction
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.control
+            .set_next_action(InterpreterAction::None, InstructionResult::Continue);
+        self.control.gas().set_opcode_gas_cap(Some(opcode_gas_cap));
+
+        while self.control.instruction_result().is_continue() {
+            self.step_with_opcode_gas_cap(instruction_table, host);
+        }
+
+        self.take_action()
+    }
+
+    /// Executes the interpreter until it returns, stops or `deadline` passes.
+    ///
+    /// The deadline is checked before the first instruction and then every
+    /// [`DEADLINE_CHECK_INTERVAL`] instructions, execution halts with
+    /// [`InstructionResult::Timeout`] once it has passed. The opcode gas cap is enforced as in
+    /// [`Interpreter::run_with_opcode_gas_cap`].
+    #[cfg(feature = "std")]
+    pub fn run_with_deadline<FN, H: Host>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        deadline: std::time::Instant,
+        opcode_gas_cap: Option<u64>,
+    ) -> InterpreterAction
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.control
+            .set_next_action(InterpreterAction::None, InstructionResult::Continue);
+        self.control.gas().set_opcode_gas_cap(opcode_gas_cap);
+
+        'outer: while self.control.instruction_result().is_continue() {
+            if std::time::Instant::now() >= deadline {
+                self.control
+                    .set_instruction_result(InstructionResult::Timeout);
+                break;
+            }
+            for _ in 0..DEADLINE_CHECK_INTERVAL {
+                self.step_with_opcode_gas_cap(instruction_table, host);
+                if !self.control.instruction_result().is_continue() {
+                    break 'outer;
+                }
+            }
+        }
+
+        self.take_action()
+    }
+
+    /// Executes the instruction at the current instruction pointer, resetting the dynamic gas
+    /// counted against the opcode gas cap first.
+    #[inline]
+    fn step_with_opcode_gas_cap<FN, H: Host>(&mut self, instruction_table: &[FN; 256], host: &mut H)
+    where
+        FN: CustomInstruction<Wire = IW, Host = H>,
+    {
+        self.control.gas().reset_opcode_gas();
+        self.step(instruction_table, host);
+    }
+
+    /// Takes the action set by the last instruction, or a return without output for halts.
+    fn take_action(&mut self) -> InterpreterAction {
         // Return next action if it is some.
         let action = self.control.take_next_action();
         if action.is_some() {
@@ -193,18 +463,17 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
         }
         // If not, return action without output as it is a halt.
         InterpreterAction::Return {
-            result: InterpreterResult {
-                result: self.control.instruction_result(),
-                // Return empty bytecode
-                output: Bytes::new(),
-                gas: *self.control.gas(),
-            },
+            result: InterpreterResult::new(
+                self.control.instruction_result(),
+                Bytes::new(),
+                *self.control.gas(),
+            ),
         }
     }
 }
 
 /// The result of an interpreter operation.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct InterpreterResult {
     /// The result of the instruction execution.
@@ -213,6 +482,13 @@ pub struct InterpreterResult {
     pub output: Bytes,
     /// The gas usage information.
     pub gas: Gas,
+    /// Whether the output was truncated to the configured maximum return size.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub output_truncated: bool,
+    /// State of the interpreter at an exceptional halt, only captured for the top level frame
+    /// if enabled in the configuration.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub halt_context: Option<Box<HaltContext>>,
 }
 
 impl InterpreterResult {
@@ -222,6 +498,8 @@ impl InterpreterResult {
             result,
             output,
             gas,
+            output_truncated: false,
+            halt_context: None,
         }
     }
 
@@ -288,6 +566,7 @@ mod tests {
     use super::*;
     use bytecode::Bytecode;
     use primitives::{Address, Bytes, U256};
+    use std::vec::Vec;
 
     #[test]
     #[cfg(feature = "serde")]
@@ -301,6 +580,7 @@ mod tests {
                 caller_address: Address::ZERO,
                 input: Bytes::default(),
                 call_value: U256::ZERO,
+                delegation_source: None,
             },
             false,
             false,
@@ -318,4 +598,291 @@ mod tests {
             "Program counter should be preserved"
         );
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_snapshot_resumes_execution() {
+        use crate::table::make_instruction_table;
+        use bytecode::opcode::{ADD, MLOAD, MSTORE, PUSH1, RETURN};
+        use context::{BlockEnv, CfgEnv, TxEnv};
+        use context_interface::host::DummyHost;
+
+        type Host = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+        // Unassigned opcode that stops the loop without finishing the frame.
+        const PAUSE: u8 = 0x0c;
+        fn pause(interpreter: &mut Interpreter<EthInterpreter>, _host: &mut Host) {
+            interpreter
+                .control
+                .set_instruction_result(InstructionResult::Stop);
+        }
+        let mut table = make_instruction_table::<EthInterpreter, Host>();
+        table[PAUSE as usize] = pause;
+
+        // Stores 2 + 3 in memory and pushes 7, pauses, then returns the sum of both.
+        let code = [
+            PUSH1, 0x02, PUSH1, 0x03, ADD, PUSH1, 0x00, MSTORE, PUSH1, 0x07, PAUSE, PUSH1, 0x00,
+            MLOAD, ADD, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        let bytecode = Bytecode::new_raw(Bytes::copy_from_slice(&code));
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            ExtBytecode::new(bytecode.clone()),
+            InputsImpl {
+                target_address: Address::ZERO,
+                caller_address: Address::ZERO,
+                input: Bytes::default(),
+                call_value: U256::ZERO,
+                delegation_source: None,
+            },
+            false,
+            false,
+            SpecId::LATEST,
+            1_000_000,
+        );
+        let mut host = Host::default();
+        interpreter.run(&table, &mut host);
+        assert_eq!(interpreter.bytecode.pc(), 11);
+
+        let snapshot = serde_json::to_string(&interpreter).unwrap();
+        let mut resumed: Interpreter<EthInterpreter> = serde_json::from_str(&snapshot).unwrap();
+        assert_eq!(resumed.stack.data(), &[U256::from(7)]);
+        assert_eq!(resumed.control.gas, interpreter.control.gas);
+        assert_eq!(resumed.bytecode.hash(), Some(bytecode.hash_slow()));
+
+        let expected = interpreter.run(&table, &mut host);
+        let action = resumed.run(&table, &mut host);
+        assert_eq!(action, expected);
+        let InterpreterAction::Return { result } = action else {
+            panic!("expected return, got {action:?}");
+        };
+        assert_eq!(result.output[..], U256::from(12).to_be_bytes::<32>());
+        assert_eq!(resumed.control.gas, interpreter.control.gas);
+    }
+
+    #[test]
+    fn small_stack_interpreter() {
+        let memory = Rc::new(RefCell::new(SharedMemory::new()));
+        let new_interpreter = || {
+            Interpreter::<EthInterpreter<(), SharedMemory, SmallStack>>::new(
+                memory.clone(),
+                ExtBytecode::new(Bytecode::new()),
+                InputsImpl {
+                    target_address: Address::ZERO,
+                    caller_address: Address::ZERO,
+                    input: Bytes::default(),
+                    call_value: U256::ZERO,
+                    delegation_source: None,
+                },
+                false,
+                false,
+                SpecId::LATEST,
+                u64::MAX,
+            )
+        };
+
+        // Many shallow frames only hold the initial capacity.
+        let interpreters: Vec<_> = (0..1024).map(|_| new_interpreter()).collect();
+        let words: usize = interpreters
+            .iter()
+            .map(|interp| interp.stack.data().capacity())
+            .sum();
+        assert_eq!(words, 1024 * SMALL_STACK_CAPACITY);
+    }
+
+    #[test]
+    fn peek_next_gas_cost_previews_pending_instruction() {
+        use crate::table::make_instruction_table;
+        use context::{BlockEnv, CfgEnv, TxEnv};
+        use context_interface::host::DummyHost;
+
+        let code = [PUSH1, 0x02, PUSH1, 0x03, ADD, PUSH1, 0x00, MSTORE, STOP];
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(&code))),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::LATEST,
+            u64::MAX,
+        );
+        let table = make_instruction_table::<EthInterpreter, DummyHost<BlockEnv, TxEnv, CfgEnv>>();
+        let mut host = DummyHost::default();
+
+        assert_eq!(interpreter.peek_next_gas_cost(&host), Some(3));
+        interpreter.step(&table, &mut host);
+        interpreter.step(&table, &mut host);
+        // ADD is pending.
+        assert_eq!(interpreter.peek_next_gas_cost(&host), Some(3));
+        interpreter.step(&table, &mut host);
+        interpreter.step(&table, &mut host);
+        // MSTORE expands memory by one word.
+        assert_eq!(interpreter.peek_next_gas_cost(&host), Some(3 + 3));
+        interpreter.step(&table, &mut host);
+        assert_eq!(interpreter.peek_next_gas_cost(&host), Some(0));
+    }
+
+    #[test]
+    fn overridden_opcode_runs_custom_instruction() {
+        use crate::interpreter_types::StackTrait;
+        use context::{BlockEnv, CfgEnv, TxEnv};
+        use context_interface::host::DummyHost;
+
+        type Host = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+        struct AddPushesConstant;
+
+        impl InstructionOverrides<EthInterpreter, Host> for AddPushesConstant {
+            fn apply(
+                builder: InstructionTableBuilder<EthInterpreter, Host>,
+                _context: &mut Host,
+            ) -> InstructionTableBuilder<EthInterpreter, Host> {
+                builder.override_opcode(ADD, |interpreter, _host| {
+                    gas!(interpreter, gas::VERYLOW);
+                    match StackTrait::popn_top::<1>(&mut interpreter.stack) {
+                        Some((_, top)) => *top = U256::from(42),
+                        None => interpreter
+                            .control
+                            .set_instruction_result(InstructionResult::StackUnderflow),
+                    }
+                })
+            }
+        }
+
+        let code = [
+            PUSH1, 0x01, PUSH1, 0x02, ADD, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ];
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(&code))),
+            InputsImpl::default(),
+            false,
+            false,
+            SpecId::LATEST,
+            1_000_000,
+        );
+        let mut host = Host::default();
+        let mut provider =
+            OverrideInstructionProvider::<AddPushesConstant, EthInterpreter, Host>::new(&mut host);
+
+        let action = interpreter.run(provider.table(), &mut host);
+        let InterpreterAction::Return { result } = action else {
+            panic!("expected return, got {action:?}");
+        };
+        assert_eq!(result.output[..], U256::from(42).to_be_bytes::<32>());
+    }
+
+    #[test]
+    fn return_data_and_input_are_visible() {
+        use crate::table::make_instruction_table;
+        use context::{BlockEnv, CfgEnv, TxEnv};
+        use context_interface::host::DummyHost;
+
+        // Returns the whole return data buffer.
+        let code = [
+            PUSH1,
+            0x20,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            RETURNDATACOPY,
+            PUSH1,
+            0x20,
+            PUSH1,
+            0x00,
+            RETURN,
+        ];
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            Rc::new(RefCell::new(SharedMemory::new())),
+            ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(&code))),
+            InputsImpl {
+                input: Bytes::from_static(&[0x01, 0x02]),
+                ..Default::default()
+            },
+            false,
+            false,
+            SpecId::LATEST,
+            1_000_000,
+        );
+        // Output of a sub call.
+        let word = Bytes::from(U256::from(0x2a).to_be_bytes_vec());
+        *interpreter.return_data.buffer_mut() = word.clone();
+
+        assert_eq!(interpreter.input_data(), [0x01, 0x02]);
+        assert_eq!(interpreter.return_data(), &word[..]);
+        assert_eq!(interpreter.return_data_len(), 32);
+
+        let table = make_instruction_table::<EthInterpreter, DummyHost<BlockEnv, TxEnv, CfgEnv>>();
+        let action = interpreter.run(&table, &mut DummyHost::default());
+        let InterpreterAction::Return { result } = action else {
+            panic!("expected return, got {action:?}");
+        };
+        assert_eq!(result.output, word);
+    }
+
+    #[test]
+    fn opcode_gas_cap_halts_before_charging_dynamic_gas() {
+        use crate::table::make_instruction_table;
+        use context::{BlockEnv, CfgEnv, TxEnv};
+        use context_interface::host::DummyHost;
+
+        type Host = DummyHost<BlockEnv, TxEnv, CfgEnv>;
+
+        let run = |code: &[u8]| {
+            let mut interpreter = Interpreter::<EthInterpreter>::new(
+                Rc::new(RefCell::new(SharedMemory::new())),
+                ExtBytecode::new(Bytecode::new_raw(Bytes::copy_from_slice(code))),
+                InputsImpl::default(),
+                false,
+                false,
+                SpecId::LATEST,
+                10_000_000,
+            );
+            let table = make_instruction_table::<EthInterpreter, Host>();
+            let action = interpreter.run_with_opcode_gas_cap(&table, &mut Host::default(), 100_000);
+            let InterpreterAction::Return { result } = action else {
+                panic!("expected return, got {action:?}");
+            };
+            let memory_len = interpreter.memory.borrow().len();
+            (result, memory_len)
+        };
+
+        // 1MB copy of calldata, copying and memory expansion together exceed the cap so it
+        // halts before memory is expanded.
+        let (result, memory_len) = run(&[
+            PUSH3,
+            0x10,
+            0x00,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            CALLDATACOPY,
+        ]);
+        assert_eq!(result.result, InstructionResult::OpcodeGasCapExceeded);
+        assert_eq!(memory_len, 0);
+
+        // 4KB copy costs 384 for copying and 416 for memory expansion.
+        let (result, memory_len) = run(&[
+            PUSH2,
+            0x10,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            CALLDATACOPY,
+            STOP,
+        ]);
+        assert_eq!(result.result, InstructionResult::Stop);
+        assert_eq!(memory_len, 0x1000);
+
+        // EXP with a 32 byte exponent has 1600 dynamic gas.
+        let mut exp = std::vec![PUSH32];
+        exp.extend_from_slice(&[0xff; 32]);
+        exp.extend_from_slice(&[PUSH1, 0x02, EXP, STOP]);
+        assert_eq!(run(&exp).0.result, InstructionResult::Stop);
+    }
 }
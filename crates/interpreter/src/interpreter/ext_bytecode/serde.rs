@@ -18,7 +18,7 @@ impl Serialize for ExtBytecode {
         ExtBytecodeSerde {
             base: self.base.clone(),
             program_counter: self.pc(),
-            bytecode_hash: self.bytecode_hash,
+            bytecode_hash: Some(self.bytecode_hash.unwrap_or_else(|| self.base.hash_slow())),
         }
         .serialize(serializer)
     }
@@ -41,8 +41,11 @@ impl<'de> Deserialize<'de> for ExtBytecode {
             Self::new(base)
         };
 
-        if program_counter >= bytecode.base.bytecode().len() {
-            panic!("serde pc: {program_counter} is greater than or equal to bytecode len");
+        // Program counter is one past the last byte after the final `STOP` is executed.
+        if program_counter > bytecode.base.bytecode().len() {
+            return Err(serde::de::Error::custom(std::format!(
+                "program counter {program_counter} is out of bytecode bounds"
+            )));
         }
         bytecode.absolute_jump(program_counter);
         Ok(bytecode)
@@ -10,6 +10,10 @@ pub struct InputsImpl {
     pub caller_address: Address,
     pub input: Bytes,
     pub call_value: U256,
+    /// Address the executed code was loaded from, if the target account is an EIP-7702
+    /// delegation to it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub delegation_source: Option<Address>,
 }
 
 impl InputsTrait for InputsImpl {
@@ -28,4 +32,8 @@ impl InputsTrait for InputsImpl {
     fn call_value(&self) -> U256 {
         self.call_value
     }
+
+    fn delegation_source(&self) -> Option<Address> {
+        self.delegation_source
+    }
 }
@@ -4,11 +4,15 @@ use super::RuntimeFlag;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RuntimeFlags {
     pub is_static: bool,
     pub is_eof_init: bool,
     pub is_eof: bool,
+    /// Whether the frame runs legacy init code, its output is the code of the new contract.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_legacy_init: bool,
     pub spec_id: SpecId,
 }
 
@@ -25,6 +29,10 @@ impl RuntimeFlag for RuntimeFlags {
         self.is_eof_init
     }
 
+    fn is_legacy_init(&self) -> bool {
+        self.is_legacy_init
+    }
+
     fn spec_id(&self) -> SpecId {
         self.spec_id
     }
@@ -0,0 +1,278 @@
+use super::{StackTrait, STACK_LIMIT};
+use core::{fmt, ptr};
+use primitives::U256;
+use std::vec::Vec;
+
+/// Initial capacity of the [`SmallStack`] in words.
+pub const SMALL_STACK_CAPACITY: usize = 16;
+
+/// EVM stack that starts with [SMALL_STACK_CAPACITY] words and grows on demand up to
+/// [STACK_LIMIT] words.
+///
+/// [`Stack`][super::Stack] preallocates the whole [STACK_LIMIT] (32KiB) for every frame, while
+/// most frames only use a handful of slots. This stack trades an occasional reallocation for a
+/// much smaller footprint when many shallow frames are spawned, e.g. when fuzzing.
+///
+/// Overflow behaves exactly as in [`Stack`][super::Stack].
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct SmallStack {
+    /// The underlying data of the stack.
+    data: Vec<U256>,
+}
+
+impl fmt::Display for SmallStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, x) in self.data.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{x}")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl Default for SmallStack {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for SmallStack {
+    fn clone(&self) -> Self {
+        let mut new_stack = Self::with_capacity(self.data.capacity());
+        new_stack.data.extend_from_slice(&self.data);
+        new_stack
+    }
+}
+
+impl StackTrait for SmallStack {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn peek(&self, n: usize) -> Option<U256> {
+        self.data.iter().rev().nth(n).copied()
+    }
+
+    #[inline]
+    fn popn<const N: usize>(&mut self) -> Option<[U256; N]> {
+        if self.len() < N {
+            return None;
+        }
+        let mut result = [U256::ZERO; N];
+        for v in result.iter_mut() {
+            // SAFETY: Stack length is checked above.
+            *v = unsafe { self.data.pop().unwrap_unchecked() };
+        }
+        Some(result)
+    }
+
+    #[inline]
+    fn popn_top<const POPN: usize>(&mut self) -> Option<([U256; POPN], &mut U256)> {
+        if self.len() < POPN + 1 {
+            return None;
+        }
+        let result = StackTrait::popn::<POPN>(self)?;
+        let top = self.data.last_mut()?;
+        Some((result, top))
+    }
+
+    fn exchange(&mut self, n: usize, m: usize) -> bool {
+        self.exchange(n, m)
+    }
+
+    fn dup(&mut self, n: usize) -> bool {
+        self.dup(n)
+    }
+
+    fn push(&mut self, value: U256) -> bool {
+        self.push(value)
+    }
+}
+
+impl SmallStack {
+    /// Instantiate a new stack with [SMALL_STACK_CAPACITY] words of capacity.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(SMALL_STACK_CAPACITY)
+    }
+
+    /// Instantiate a new stack with the given capacity, capped at [STACK_LIMIT].
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity.min(STACK_LIMIT)),
+        }
+    }
+
+    /// Returns the length of the stack in words.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a reference to the underlying data buffer.
+    #[inline]
+    pub fn data(&self) -> &Vec<U256> {
+        &self.data
+    }
+
+    /// Consumes the stack and returns the underlying data buffer.
+    #[inline]
+    pub fn into_data(self) -> Vec<U256> {
+        self.data
+    }
+
+    /// Makes room for one more word, doubling the capacity without exceeding [STACK_LIMIT].
+    ///
+    /// Returns `false` if the stack is full.
+    #[inline]
+    fn reserve_one(&mut self) -> bool {
+        let len = self.data.len();
+        if len == STACK_LIMIT {
+            return false;
+        }
+        if len == self.data.capacity() {
+            self.grow();
+        }
+        true
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn grow(&mut self) {
+        let len = self.data.len();
+        let new_capacity = (len * 2).clamp(SMALL_STACK_CAPACITY, STACK_LIMIT);
+        self.data.reserve_exact(new_capacity - len);
+    }
+
+    /// Push a new value onto the stack.
+    ///
+    /// If it will exceed the stack limit, returns false and leaves the stack
+    /// unchanged.
+    #[inline]
+    #[must_use]
+    pub fn push(&mut self, value: U256) -> bool {
+        if !self.reserve_one() {
+            return false;
+        }
+        self.data.push(value);
+        true
+    }
+
+    /// Duplicates the `N`th value from the top of the stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    #[inline]
+    #[must_use]
+    pub fn dup(&mut self, n: usize) -> bool {
+        assume!(n > 0, "attempted to dup 0");
+        let len = self.data.len();
+        if len < n || !self.reserve_one() {
+            return false;
+        }
+        self.data.push(self.data[len - n]);
+        true
+    }
+
+    /// Exchange two values on the stack.
+    ///
+    /// `n` is the first index, and the second index is calculated as `n + m`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    #[inline]
+    pub fn exchange(&mut self, n: usize, m: usize) -> bool {
+        assume!(m > 0, "overlapping exchange");
+        let len = self.data.len();
+        let n_m_index = n + m;
+        if n_m_index >= len {
+            return false;
+        }
+        // SAFETY: `n` and `n_m` are checked to be within bounds, and they don't overlap.
+        unsafe {
+            let top = self.data.as_mut_ptr().add(len - 1);
+            ptr::swap_nonoverlapping(top.sub(n), top.sub(n_m_index), 1);
+        }
+        true
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SmallStack {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = Vec::<U256>::deserialize(deserializer)?;
+        if data.len() > STACK_LIMIT {
+            return Err(serde::de::Error::custom(std::format!(
+                "stack size exceeds limit: {} > {}",
+                data.len(),
+                STACK_LIMIT
+            )));
+        }
+        Ok(Self { data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::Stack;
+
+    #[test]
+    fn grows_up_to_stack_limit() {
+        let mut stack = SmallStack::new();
+        assert_eq!(stack.data().capacity(), SMALL_STACK_CAPACITY);
+
+        for i in 0..STACK_LIMIT {
+            assert!(stack.push(U256::from(i)));
+            assert!(stack.data().capacity() <= STACK_LIMIT);
+        }
+        assert_eq!(stack.data().capacity(), STACK_LIMIT);
+        assert!(!stack.push(U256::ZERO));
+        assert!(!stack.dup(1));
+        assert_eq!(stack.len(), STACK_LIMIT);
+        assert_eq!(stack.data().last(), Some(&U256::from(STACK_LIMIT - 1)));
+    }
+
+    #[test]
+    fn matches_stack() {
+        let mut small = SmallStack::new();
+        let mut stack = Stack::new();
+        for i in 0..40 {
+            assert_eq!(small.push(U256::from(i)), stack.push(U256::from(i)));
+        }
+        for (n, m) in [(0, 1), (3, 7), (0, 39), (1, 39)] {
+            assert_eq!(small.exchange(n, m), stack.exchange(n, m));
+        }
+        for n in [1, 16, 41, 60] {
+            assert_eq!(small.dup(n), stack.dup(n));
+        }
+        assert_eq!(small.data(), stack.data());
+
+        assert_eq!(
+            StackTrait::popn_top::<3>(&mut small).map(|(p, t)| (p, *t)),
+            StackTrait::popn_top::<3>(&mut stack).map(|(p, t)| (p, *t))
+        );
+        assert_eq!(
+            StackTrait::popn::<50>(&mut small),
+            StackTrait::popn::<50>(&mut stack)
+        );
+        assert_eq!(small.data(), stack.data());
+    }
+}
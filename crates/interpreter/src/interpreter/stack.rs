@@ -10,7 +10,7 @@ pub const STACK_LIMIT: usize = 1024;
 
 /// EVM stack with [STACK_LIMIT] capacity of words.
 #[derive(Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct Stack {
     /// The underlying data of the stack.
     data: Vec<U256>,
@@ -52,6 +52,10 @@ impl StackTrait for Stack {
         self.len()
     }
 
+    fn peek(&self, n: usize) -> Option<U256> {
+        self.peek(n).ok()
+    }
+
     #[inline]
     fn popn<const N: usize>(&mut self) -> Option<[U256; N]> {
         if self.len() < N {
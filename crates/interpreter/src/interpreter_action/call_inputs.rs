@@ -12,7 +12,17 @@ pub struct CallInputs {
     /// In EOF, this range is invalid as EOF calls do not write output to memory.
     pub return_memory_offset: Range<usize>,
     /// The gas limit of the call.
+    ///
+    /// Equal to `gas_limit_forwarded + stipend_added`.
     pub gas_limit: u64,
+    /// Gas forwarded from the caller, after the EIP-150 63/64 rule has been applied.
+    pub gas_limit_forwarded: u64,
+    /// Call stipend added on top of the forwarded gas for value transferring calls.
+    pub stipend_added: u64,
+    /// Gas remaining in the caller just before the forwarded gas was deducted.
+    ///
+    /// Memory expansion and the call cost are already charged at this point.
+    pub gas_before_call: u64,
     /// The account address of bytecode that is going to be executed.
     ///
     /// Previously `context.code_address`.
@@ -56,9 +66,20 @@ impl CallInputs {
         self.value.transfer()
     }
 
+    /// Returns the value that is moved from [`transfer_from`][Self::transfer_from] to
+    /// [`transfer_to`][Self::transfer_to].
+    ///
+    /// Zero for `DELEGATECALL`, which inherits the value of the parent frame without
+    /// transferring it. For `CALLCODE` both addresses are the caller, so balances don't change.
+    #[inline]
+    pub fn transferred_value(&self) -> U256 {
+        self.value.transfer().unwrap_or_default()
+    }
+
     /// Returns the **apparent** call value.
     ///
-    /// This value is not actually transferred, see [`CallValue`].
+    /// This value is not actually transferred, see [`CallValue`]. Use
+    /// [`call_value`][Self::call_value] for the value seen by the callee in all call schemes.
     #[inline]
     pub const fn apparent_value(&self) -> Option<U256> {
         self.value.apparent()
@@ -82,11 +103,34 @@ impl CallInputs {
 
     /// Returns the call value, regardless of the transfer value type.
     ///
+    /// This is the `msg.value` seen by the callee. For `DELEGATECALL` it is the value of the
+    /// parent frame.
+    ///
     /// **Note**: This value may not necessarily be transferred from caller to callee, see [`CallValue`].
     #[inline]
     pub const fn call_value(&self) -> U256 {
         self.value.get()
     }
+
+    /// Redirects the call to the code of `address`.
+    ///
+    /// For `CALL`, `STATICCALL` and their EOF variants the callee becomes `address`, for the
+    /// delegating schemes only the executed code changes and the storage context is kept. The
+    /// code is resolved when the frame is created, so precompiles and EIP-7702 delegations of
+    /// `address` are followed.
+    #[inline]
+    pub fn redirect(&mut self, address: Address) {
+        if matches!(
+            self.scheme,
+            CallScheme::Call
+                | CallScheme::StaticCall
+                | CallScheme::ExtCall
+                | CallScheme::ExtStaticCall
+        ) {
+            self.target_address = address;
+        }
+        self.bytecode_address = address;
+    }
 }
 
 /// Call scheme.
@@ -182,3 +226,54 @@ impl CallValue {
         matches!(self, Self::Apparent(_))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_inputs(scheme: CallScheme, value: CallValue) -> CallInputs {
+        CallInputs {
+            input: Bytes::new(),
+            return_memory_offset: 0..0,
+            gas_limit: 100_000,
+            gas_limit_forwarded: 100_000,
+            stipend_added: 0,
+            gas_before_call: 200_000,
+            bytecode_address: Address::with_last_byte(0xbb),
+            target_address: Address::with_last_byte(0xbb),
+            caller: Address::with_last_byte(0xaa),
+            value,
+            scheme,
+            is_static: false,
+            is_eof: false,
+        }
+    }
+
+    #[test]
+    fn delegatecall_inherits_apparent_value() {
+        let call = call_inputs(CallScheme::Call, CallValue::Transfer(U256::from(5)));
+        assert_eq!(call.call_value(), U256::from(5));
+        assert_eq!(call.transferred_value(), U256::from(5));
+
+        let delegate_call =
+            call_inputs(CallScheme::DelegateCall, CallValue::Apparent(U256::from(5)));
+        assert_eq!(delegate_call.call_value(), U256::from(5));
+        assert_eq!(delegate_call.transferred_value(), U256::ZERO);
+    }
+
+    #[test]
+    fn redirect_keeps_storage_context_of_delegating_schemes() {
+        let redirected = Address::with_last_byte(0xcc);
+
+        let mut call = call_inputs(CallScheme::Call, CallValue::Transfer(U256::ZERO));
+        call.redirect(redirected);
+        assert_eq!(call.target_address, redirected);
+        assert_eq!(call.bytecode_address, redirected);
+
+        let mut delegate_call =
+            call_inputs(CallScheme::DelegateCall, CallValue::Apparent(U256::ZERO));
+        delegate_call.redirect(redirected);
+        assert_eq!(delegate_call.target_address, Address::with_last_byte(0xbb));
+        assert_eq!(delegate_call.bytecode_address, redirected);
+    }
+}
@@ -11,11 +11,17 @@ use primitives::Bytes;
 ///
 /// * `result` - The result of the interpreter's execution, including output data and gas usage.
 /// * `memory_offset` - The range in memory where the output data is located.
+/// * `gas_returned` - Forwarded gas that was not used by the call and is returned to the caller.
+/// * `gas_refunded_to_caller` - Refund counter of the call that is added to the caller's refund.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallOutcome {
     pub result: InterpreterResult,
     pub memory_offset: Range<usize>,
+    /// Remaining gas of a successful or reverted call, zero if the call halted.
+    pub gas_returned: u64,
+    /// Refund counter of a successful call, zero if the call reverted or halted.
+    pub gas_refunded_to_caller: i64,
 }
 
 impl CallOutcome {
@@ -27,10 +33,24 @@ impl CallOutcome {
     ///
     /// * `result` - The result of the interpreter's execution.
     /// * `memory_offset` - The range in memory indicating where the output data is stored.
+    ///
+    /// `gas_returned` and `gas_refunded_to_caller` are derived from the result.
     pub fn new(result: InterpreterResult, memory_offset: Range<usize>) -> Self {
+        let gas_returned = if result.result.is_ok_or_revert() {
+            result.gas.remaining()
+        } else {
+            0
+        };
+        let gas_refunded_to_caller = if result.result.is_ok() {
+            result.gas.refunded()
+        } else {
+            0
+        };
         Self {
             result,
             memory_offset,
+            gas_returned,
+            gas_refunded_to_caller,
         }
     }
 
@@ -89,3 +109,30 @@ impl CallOutcome {
         self.memory_offset.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverted_call_returns_remaining_gas() {
+        // Call with 10024 gas that spent 5012 gas and accumulated a 4800 refund.
+        let mut gas = Gas::new(10024);
+        assert!(gas.record_cost(5012));
+        gas.record_refund(4800);
+        let outcome =
+            |result| CallOutcome::new(InterpreterResult::new(result, Bytes::new(), gas), 0..0);
+
+        let reverted = outcome(InstructionResult::Revert);
+        assert_eq!(reverted.gas_returned, 10024 - 5012);
+        assert_eq!(reverted.gas_refunded_to_caller, 0);
+
+        let returned = outcome(InstructionResult::Return);
+        assert_eq!(returned.gas_returned, 10024 - 5012);
+        assert_eq!(returned.gas_refunded_to_caller, 4800);
+
+        let halted = outcome(InstructionResult::OutOfGas);
+        assert_eq!(halted.gas_returned, 0);
+        assert_eq!(halted.gas_refunded_to_caller, 0);
+    }
+}
@@ -13,6 +13,11 @@ pub struct CreateOutcome {
     pub result: InterpreterResult,
     /// An optional address associated with the create operation
     pub address: Option<Address>,
+    /// Code returned by the init code that could not be deployed because the code deposit
+    /// ran out of gas.
+    ///
+    /// Useful for debugging failed deployments, it is `None` in all other cases.
+    pub deployed_code_attempt: Option<Bytes>,
 }
 
 impl CreateOutcome {
@@ -27,7 +32,11 @@ impl CreateOutcome {
     ///
     /// A new [`CreateOutcome`] instance.
     pub fn new(result: InterpreterResult, address: Option<Address>) -> Self {
-        Self { result, address }
+        Self {
+            result,
+            address,
+            deployed_code_attempt: None,
+        }
     }
 
     /// Retrieves a reference to the [`InstructionResult`] from the [`InterpreterResult`].
@@ -24,6 +24,7 @@ pub trait InputsTrait {
     fn caller_address(&self) -> Address;
     fn input(&self) -> &[u8];
     fn call_value(&self) -> U256;
+    fn delegation_source(&self) -> Option<Address>;
 }
 
 pub trait LegacyBytecode {
@@ -136,6 +137,10 @@ pub trait StackTrait {
         self.popn_top::<0>().map(|(_, top)| top)
     }
 
+    /// Returns the `n`th value from the top of the stack without removing it.
+    #[must_use]
+    fn peek(&self, n: usize) -> Option<U256>;
+
     /// Pops one value from the stack.
     #[must_use]
     fn pop(&mut self) -> Option<U256> {
@@ -195,6 +200,10 @@ pub trait RuntimeFlag {
     fn is_static(&self) -> bool;
     fn is_eof(&self) -> bool;
     fn is_eof_init(&self) -> bool;
+    /// Whether the frame runs legacy init code.
+    fn is_legacy_init(&self) -> bool {
+        false
+    }
     fn spec_id(&self) -> SpecId;
 }
 
@@ -32,9 +32,11 @@ pub use context_interface::{
 };
 pub use gas::Gas;
 pub use instruction_result::*;
+#[cfg(feature = "std")]
+pub use interpreter::DEADLINE_CHECK_INTERVAL;
 pub use interpreter::{
-    num_words, InputsImpl, Interpreter, InterpreterResult, MemoryGetter, SharedMemory, Stack,
-    EMPTY_SHARED_MEMORY, STACK_LIMIT,
+    num_words, InputsImpl, Interpreter, InterpreterResult, MemoryGetter, SharedMemory, SmallStack,
+    Stack, EMPTY_SHARED_MEMORY, SMALL_STACK_CAPACITY, STACK_LIMIT,
 };
 pub use interpreter_action::{
     CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, EOFCreateInputs,
@@ -126,6 +126,62 @@ pub const fn make_instruction_table<WIRE: InterpreterTypes, H: Host + ?Sized>(
     }
 }
 
+/// Builder of an instruction table that starts from the mainnet instructions.
+///
+/// # Example
+///
+/// ```
+/// use revm_interpreter::{
+///     gas,
+///     interpreter::EthInterpreter,
+///     interpreter_types::LoopControl,
+///     table::{InstructionTable, InstructionTableBuilder},
+///     Host, Interpreter,
+/// };
+///
+/// fn custom_opcode<H: Host>(interpreter: &mut Interpreter<EthInterpreter<()>>, _host: &mut H) {
+///     gas!(interpreter, gas::MID);
+/// }
+///
+/// fn custom_table<H: Host>() -> InstructionTable<EthInterpreter<()>, H> {
+///     InstructionTableBuilder::new()
+///         .override_opcode(0x0c, custom_opcode::<H>)
+///         .build()
+/// }
+/// ```
+pub struct InstructionTableBuilder<WIRE: InterpreterTypes, H: ?Sized> {
+    table: InstructionTable<WIRE, H>,
+}
+
+impl<WIRE: InterpreterTypes, H: Host + ?Sized> InstructionTableBuilder<WIRE, H> {
+    /// Creates a new builder with the mainnet instruction table.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            table: make_instruction_table::<WIRE, H>(),
+        }
+    }
+
+    /// Replaces the instruction of `opcode` with `instruction`.
+    #[inline]
+    pub fn override_opcode(mut self, opcode: u8, instruction: Instruction<WIRE, H>) -> Self {
+        self.table[opcode as usize] = instruction;
+        self
+    }
+
+    /// Returns the instruction table.
+    #[inline]
+    pub fn build(self) -> InstructionTable<WIRE, H> {
+        self.table
+    }
+}
+
+impl<WIRE: InterpreterTypes, H: Host + ?Sized> Default for InstructionTableBuilder<WIRE, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Make boxed instruction table that calls `f` closure for every instruction.
 #[inline]
 pub fn make_custom_instruction_table<W, H, FN, CI: CustomInstruction<Wire = W, Host = H>>(
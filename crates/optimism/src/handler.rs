@@ -443,8 +443,10 @@ where
                     result: ExecutionResult::Halt {
                         reason: OptimismHaltReason::FailedDeposit,
                         gas_used,
+                        context: None,
                     },
                     state,
+                    coinbase_reward: U256::ZERO,
                 })
             } else {
                 Err(err)
@@ -2,10 +2,13 @@ use crate::{OpSpec, OpSpecId};
 use once_cell::race::OnceBox;
 use precompile::{secp256r1, PrecompileErrors, Precompiles};
 use revm::{
-    context::Cfg, context_interface::CfgGetter, handler::EthPrecompileProvider,
-    handler_interface::PrecompileProvider, specification::hardfork::SpecId,
+    context::Cfg,
+    context_interface::{BlockGetter, CfgGetter},
+    handler::EthPrecompileProvider,
+    handler_interface::PrecompileProvider,
+    specification::hardfork::SpecId,
 };
-use std::boxed::Box;
+use std::{boxed::Box, vec::Vec};
 
 pub struct OpPrecompileProvider<CTX, ERROR> {
     precompile_provider: EthPrecompileProvider<CTX, ERROR>,
@@ -54,7 +57,7 @@ pub fn granite() -> &'static Precompiles {
 
 impl<CTX, ERROR> PrecompileProvider for OpPrecompileProvider<CTX, ERROR>
 where
-    CTX: CfgGetter,
+    CTX: CfgGetter + BlockGetter,
     <CTX as CfgGetter>::Cfg: Cfg<Spec = OpSpec>,
     ERROR: From<PrecompileErrors>,
 {
@@ -112,6 +115,19 @@ where
             .run(context, address, bytes, gas_limit)
     }
 
+    #[inline]
+    fn run_interruptible(
+        &mut self,
+        context: &mut Self::Context,
+        address: &precompile::Address,
+        bytes: &precompile::Bytes,
+        gas_limit: u64,
+        interrupt: &mut dyn FnMut() -> bool,
+    ) -> Result<Option<revm::interpreter::InterpreterResult>, Self::Error> {
+        self.precompile_provider
+            .run_interruptible(context, address, bytes, gas_limit, interrupt)
+    }
+
     #[inline]
     fn warm_addresses(&self) -> impl Iterator<Item = precompile::Address> {
         self.precompile_provider.warm_addresses()
@@ -121,4 +137,9 @@ where
     fn contains(&self, address: &precompile::Address) -> bool {
         self.precompile_provider.contains(address)
     }
+
+    #[inline]
+    fn active_addresses(&self) -> Vec<precompile::Address> {
+        self.precompile_provider.active_addresses()
+    }
 }
@@ -42,7 +42,7 @@ mod test {
     use super::map_fp_to_g1;
     use super::msm::msm_required_gas;
     use super::pairing;
-    use crate::PrecompileResult;
+    use crate::{PrecompileError, PrecompileResult};
     use eyre::Result;
     use primitives::{hex::FromHex, Bytes};
     use rstest::rstest;
@@ -130,6 +130,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn pairing_with_check_runs_between_pairs() {
+        // Three pairs of points at infinity.
+        let input = Bytes::from(vec![0u8; 3 * 384]);
+        let mut checks = 0;
+        let output = pairing::pairing_with_check(&input, 30_000_000, || {
+            checks += 1;
+            Ok(())
+        });
+        assert_eq!(output, pairing::pairing(&input, 30_000_000));
+        assert_eq!(checks, 2);
+
+        let interrupted =
+            pairing::pairing_with_check(&input, 30_000_000, || Err(PrecompileError::Timeout));
+        assert_eq!(interrupted, Err(PrecompileError::Timeout.into()));
+    }
+
     #[rstest]
     #[case::g1_empty(0, g1_mul::BASE_GAS_FEE, 0)]
     #[case::g1_one_item(160, g1_mul::BASE_GAS_FEE, 14400)]
@@ -34,6 +34,16 @@ const INPUT_LENGTH: usize = 384;
 ///
 /// See also: <https://eips.ethereum.org/EIPS/eip-2537#abi-for-pairing>
 pub(super) fn pairing(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    pairing_with_check(input, gas_limit, || Ok(()))
+}
+
+/// Runs [`PRECOMPILE`] calling `check` before every pair but the first one, its
+/// error aborts the execution.
+pub fn pairing_with_check(
+    input: &Bytes,
+    gas_limit: u64,
+    mut check: impl FnMut() -> Result<(), PrecompileError>,
+) -> PrecompileResult {
     let input_len = input.len();
     if input_len == 0 || input_len % INPUT_LENGTH != 0 {
         return Err(PrecompileError::Other(format!(
@@ -51,6 +61,9 @@ pub(super) fn pairing(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     // Accumulator for the fp12 multiplications of the miller loops.
     let mut acc = blst_fp12::default();
     for i in 0..k {
+        if i > 0 {
+            check()?;
+        }
         // NB: Scalar multiplications, MSMs and pairings MUST perform a subgroup check.
         //
         // So we set the subgroup_check flag to `true`
@@ -1,3 +1,4 @@
+use crate::calc_linear_cost_u32;
 use context_interface::result::EVMError;
 use core::fmt;
 use primitives::Bytes;
@@ -22,10 +23,49 @@ impl PrecompileOutput {
     pub fn new(gas_used: u64, bytes: Bytes) -> Self {
         Self { gas_used, bytes }
     }
+
+    /// Returns new precompile output that charges `base` gas plus `per_word_out` gas for every
+    /// 32-byte word of the output bytes.
+    pub fn with_gas_for_output(base: u64, per_word_out: u64, bytes: Bytes) -> Self {
+        let gas_used = calc_linear_cost_u32(bytes.len(), base, per_word_out);
+        Self { gas_used, bytes }
+    }
 }
 
 pub type PrecompileFn = fn(&Bytes, u64) -> PrecompileResult;
 
+/// Runs a precompile whose cost depends on the length of its output.
+///
+/// `run` computes the output bytes, the gas is then charged with
+/// [`PrecompileOutput::with_gas_for_output`] and checked against the `gas_limit`. If the
+/// `base` cost alone exceeds the limit, `run` is not executed.
+///
+/// Non-capturing closures coerce to [`PrecompileFn`], so it can be used to register a precompile:
+///
+/// ```
+/// use revm_precompile::{run_with_output_gas, u64_to_address, PrecompileWithAddress};
+///
+/// let echo = PrecompileWithAddress(u64_to_address(0x100), |input, gas_limit| {
+///     run_with_output_gas(input, gas_limit, 15, 3, |input| Ok(input.clone()))
+/// });
+/// ```
+pub fn run_with_output_gas(
+    input: &Bytes,
+    gas_limit: u64,
+    base: u64,
+    per_word_out: u64,
+    run: impl FnOnce(&Bytes) -> Result<Bytes, PrecompileErrors>,
+) -> PrecompileResult {
+    if base > gas_limit {
+        return Err(PrecompileError::OutOfGas.into());
+    }
+    let output = PrecompileOutput::with_gas_for_output(base, per_word_out, run(input)?);
+    if output.gas_used > gas_limit {
+        return Err(PrecompileError::OutOfGas.into());
+    }
+    Ok(output)
+}
+
 /// Precompile errors.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum PrecompileErrors {
@@ -72,6 +112,10 @@ pub enum PrecompileError {
     BlobMismatchedVersion,
     /// The proof verification failed
     BlobVerifyKzgProofFailed,
+    /// Execution deadline was reached before the precompile finished
+    Timeout,
+    /// Input is longer than the configured limit
+    InputTooLarge,
     /// Catch-all variant for other errors
     Other(String),
 }
@@ -111,8 +155,33 @@ impl fmt::Display for PrecompileError {
             Self::BlobInvalidInputLength => "invalid blob input length",
             Self::BlobMismatchedVersion => "mismatched blob version",
             Self::BlobVerifyKzgProofFailed => "verifying blob kzg proof failed",
+            Self::Timeout => "precompile execution deadline exceeded",
+            Self::InputTooLarge => "precompile input too large",
             Self::Other(s) => s,
         };
         f.write_str(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+        run_with_output_gas(input, gas_limit, 10, 4, |input| Ok(input.clone()))
+    }
+
+    #[test]
+    fn output_priced_precompile() {
+        let input = Bytes::from(vec![1; 33]);
+        let output = echo(&input, 18).unwrap();
+        assert_eq!(output, PrecompileOutput::new(10 + 2 * 4, input.clone()));
+
+        assert_eq!(echo(&Bytes::new(), 10).unwrap().gas_used, 10);
+        assert_eq!(echo(&input, 17), Err(PrecompileError::OutOfGas.into()));
+        assert_eq!(
+            run_with_output_gas(&input, 9, 10, 4, |_| unreachable!()),
+            Err(PrecompileError::OutOfGas.into())
+        );
+    }
+}
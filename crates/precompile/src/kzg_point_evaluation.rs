@@ -1,14 +1,24 @@
-use crate::{Address, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress};
+//! KZG point evaluation precompile.
+//!
+//! Verification against the mainnet trusted setup requires the `c-kzg` or `kzg-rs` feature,
+//! [`run_with_verifier`] is always available.
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+use crate::PrecompileWithAddress;
+use crate::{Address, PrecompileError, PrecompileOutput, PrecompileResult};
 cfg_if::cfg_if! {
     if #[cfg(feature = "c-kzg")] {
+        pub use c_kzg::KzgSettings;
         use c_kzg::{Bytes32, Bytes48, KzgProof};
     } else if #[cfg(feature = "kzg-rs")] {
+        pub use kzg_rs::KzgSettings;
         use kzg_rs::{Bytes32, Bytes48, KzgProof};
     }
 }
+use context_interface::KzgVerifier;
 use primitives::{hex_literal::hex, Bytes};
 use sha2::{Digest, Sha256};
 
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub const POINT_EVALUATION: PrecompileWithAddress = PrecompileWithAddress(ADDRESS, run);
 
 pub const ADDRESS: Address = crate::u64_to_address(0x0A);
@@ -21,15 +31,42 @@ pub const RETURN_VALUE: &[u8; 64] = &hex!(
     "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
 );
 
-/// Run kzg point evaluation precompile.
-///
-/// The Env has the KZGSettings that is needed for evaluation.
+/// Run kzg point evaluation precompile with the mainnet trusted setup.
 ///
 /// The input is encoded as follows:
 /// | versioned_hash |  z  |  y  | commitment | proof |
 /// |     32         | 32  | 32  |     48     |   48  |
 /// with z and y being padded 32 byte big endian values
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    run_with(input, gas_limit, |commitment, z, y, proof| {
+        verify_kzg_proof(
+            as_bytes48(commitment),
+            as_bytes32(z),
+            as_bytes32(y),
+            as_bytes48(proof),
+        )
+    })
+}
+
+/// Run kzg point evaluation precompile, verifying the proof with the given verifier.
+///
+/// Used for chains with a custom trusted setup, see [`run`] for the input encoding.
+pub fn run_with_verifier(
+    input: &Bytes,
+    gas_limit: u64,
+    verifier: &dyn KzgVerifier,
+) -> PrecompileResult {
+    run_with(input, gas_limit, |commitment, z, y, proof| {
+        verifier.verify_kzg_proof(commitment, z, y, proof)
+    })
+}
+
+fn run_with(
+    input: &Bytes,
+    gas_limit: u64,
+    verify: impl FnOnce(&[u8; 48], &[u8; 32], &[u8; 32], &[u8; 48]) -> bool,
+) -> PrecompileResult {
     if gas_limit < GAS_COST {
         return Err(PrecompileError::OutOfGas.into());
     }
@@ -47,11 +84,11 @@ pub fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     }
 
     // Verify KZG proof with z and y in big endian format
-    let commitment = as_bytes48(commitment);
-    let z = as_bytes32(&input[32..64]);
-    let y = as_bytes32(&input[64..96]);
-    let proof = as_bytes48(&input[144..192]);
-    if !verify_kzg_proof(commitment, z, y, proof) {
+    let commitment = as_array::<48>(commitment);
+    let z = as_array::<32>(&input[32..64]);
+    let y = as_array::<32>(&input[64..96]);
+    let proof = as_array::<48>(&input[144..192]);
+    if !verify(commitment, z, y, proof) {
         return Err(PrecompileError::BlobVerifyKzgProofFailed.into());
     }
 
@@ -67,6 +104,7 @@ pub fn kzg_to_versioned_hash(commitment: &[u8]) -> [u8; 32] {
     hash
 }
 
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 #[inline]
 pub fn verify_kzg_proof(commitment: &Bytes48, z: &Bytes32, y: &Bytes32, proof: &Bytes48) -> bool {
     cfg_if::cfg_if! {
@@ -80,12 +118,44 @@ pub fn verify_kzg_proof(commitment: &Bytes48, z: &Bytes32, y: &Bytes32, proof: &
     KzgProof::verify_kzg_proof(commitment, z, y, proof, kzg_settings).unwrap_or(false)
 }
 
+/// [`KzgVerifier`] checking proofs against the trusted setup of the settings.
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+pub struct KzgSetup(pub KzgSettings);
+
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+impl core::fmt::Debug for KzgSetup {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KzgSetup").finish_non_exhaustive()
+    }
+}
+
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+impl KzgVerifier for KzgSetup {
+    fn verify_kzg_proof(
+        &self,
+        commitment: &[u8; 48],
+        z: &[u8; 32],
+        y: &[u8; 32],
+        proof: &[u8; 48],
+    ) -> bool {
+        KzgProof::verify_kzg_proof(
+            as_bytes48(commitment),
+            as_bytes32(z),
+            as_bytes32(y),
+            as_bytes48(proof),
+            &self.0,
+        )
+        .unwrap_or(false)
+    }
+}
+
 #[inline]
 #[track_caller]
 pub fn as_array<const N: usize>(bytes: &[u8]) -> &[u8; N] {
     bytes.try_into().expect("slice with incorrect length")
 }
 
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 #[inline]
 #[track_caller]
 pub fn as_bytes32(bytes: &[u8]) -> &Bytes32 {
@@ -93,6 +163,7 @@ pub fn as_bytes32(bytes: &[u8]) -> &Bytes32 {
     unsafe { &*as_array::<32>(bytes).as_ptr().cast() }
 }
 
+#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 #[inline]
 #[track_caller]
 pub fn as_bytes48(bytes: &[u8]) -> &Bytes48 {
@@ -100,7 +171,7 @@ pub fn as_bytes48(bytes: &[u8]) -> &Bytes48 {
     unsafe { &*as_array::<48>(bytes).as_ptr().cast() }
 }
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "c-kzg", feature = "kzg-rs")))]
 mod tests {
     use super::*;
 
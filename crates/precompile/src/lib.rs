@@ -15,7 +15,6 @@ pub mod bn128;
 pub mod hash;
 pub mod identity;
 pub mod interface;
-#[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub mod kzg_point_evaluation;
 pub mod modexp;
 pub mod secp256k1;
@@ -30,11 +29,10 @@ use kzg_rs as _;
 pub use primitives::{Address, Bytes, HashMap, HashSet, Log, B256};
 
 pub use primitives;
+pub use specification::hardfork::PrecompileSpecId;
 
 use cfg_if::cfg_if;
-use core::hash::Hash;
 use once_cell::race::OnceBox;
-use specification::hardfork::SpecId;
 use std::{boxed::Box, vec::Vec};
 
 pub fn calc_linear_cost_u32(len: usize, base: u64, word: u64) -> u64 {
@@ -266,41 +264,6 @@ impl PrecompileWithAddress {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
-pub enum PrecompileSpecId {
-    HOMESTEAD,
-    BYZANTIUM,
-    ISTANBUL,
-    BERLIN,
-    CANCUN,
-    PRAGUE,
-    LATEST,
-}
-
-impl From<SpecId> for PrecompileSpecId {
-    fn from(spec_id: SpecId) -> Self {
-        Self::from_spec_id(spec_id)
-    }
-}
-
-impl PrecompileSpecId {
-    /// Returns the appropriate precompile Spec for the primitive [SpecId].
-    pub const fn from_spec_id(spec_id: specification::hardfork::SpecId) -> Self {
-        use specification::hardfork::SpecId::*;
-        match spec_id {
-            FRONTIER | FRONTIER_THAWING | HOMESTEAD | DAO_FORK | TANGERINE | SPURIOUS_DRAGON => {
-                Self::HOMESTEAD
-            }
-            BYZANTIUM | CONSTANTINOPLE | PETERSBURG => Self::BYZANTIUM,
-            ISTANBUL | MUIR_GLACIER => Self::ISTANBUL,
-            BERLIN | LONDON | ARROW_GLACIER | GRAY_GLACIER | MERGE | SHANGHAI => Self::BERLIN,
-            CANCUN => Self::CANCUN,
-            PRAGUE | OSAKA => Self::PRAGUE,
-            LATEST => Self::LATEST,
-        }
-    }
-}
-
 /// Const function for making an address by concatenating the bytes from two given numbers.
 ///
 /// Note that 32 + 128 = 160 = 20 bytes (the length of an address).
@@ -4,8 +4,9 @@ use crate::{
     PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
 };
 use aurora_engine_modexp::modexp;
-use core::cmp::{max, min};
+use core::cmp::{max, min, Ordering};
 use primitives::Bytes;
+use std::vec::Vec;
 
 pub const BYZANTIUM: PrecompileWithAddress =
     PrecompileWithAddress(crate::u64_to_address(5), byzantium_run);
@@ -27,6 +28,28 @@ pub fn berlin_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     })
 }
 
+/// Same as [`byzantium_run`] but calls `check` between exponentiation chunks.
+///
+/// See [`run_inner_with_check`].
+pub fn byzantium_run_with_check(
+    input: &Bytes,
+    gas_limit: u64,
+    check: impl FnMut() -> Result<(), PrecompileError>,
+) -> PrecompileResult {
+    run_inner_with_check(input, gas_limit, 0, byzantium_gas_calc, check)
+}
+
+/// Same as [`berlin_run`] but calls `check` between exponentiation chunks.
+///
+/// See [`run_inner_with_check`].
+pub fn berlin_run_with_check(
+    input: &Bytes,
+    gas_limit: u64,
+    check: impl FnMut() -> Result<(), PrecompileError>,
+) -> PrecompileResult {
+    run_inner_with_check(input, gas_limit, 200, berlin_gas_calc, check)
+}
+
 pub fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> u64 {
     let mut iteration_count: u64 = 0;
 
@@ -45,6 +68,50 @@ pub fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> u64 {
 pub fn run_inner<F>(input: &[u8], gas_limit: u64, min_gas: u64, calc_gas: F) -> PrecompileResult
 where
     F: FnOnce(u64, u64, u64, &U256) -> u64,
+{
+    run_inner_with(
+        input,
+        gas_limit,
+        min_gas,
+        calc_gas,
+        |base, exponent, modulus| Ok(modexp(base, exponent, modulus)),
+    )
+}
+
+/// Runs modexp splitting the exponent in chunks of [`EXP_CHUNK_LEN`] bytes.
+///
+/// `check` is called before every chunk but the first one, its error aborts the execution.
+/// Used to interrupt long running exponentiations, it is slower than [`run_inner`].
+pub fn run_inner_with_check<F, C>(
+    input: &[u8],
+    gas_limit: u64,
+    min_gas: u64,
+    calc_gas: F,
+    check: C,
+) -> PrecompileResult
+where
+    F: FnOnce(u64, u64, u64, &U256) -> u64,
+    C: FnMut() -> Result<(), PrecompileError>,
+{
+    run_inner_with(
+        input,
+        gas_limit,
+        min_gas,
+        calc_gas,
+        |base, exponent, modulus| chunked_modexp(base, exponent, modulus, check),
+    )
+}
+
+fn run_inner_with<F, M>(
+    input: &[u8],
+    gas_limit: u64,
+    min_gas: u64,
+    calc_gas: F,
+    modexp: M,
+) -> PrecompileResult
+where
+    F: FnOnce(u64, u64, u64, &U256) -> u64,
+    M: FnOnce(&[u8], &[u8], &[u8]) -> Result<Vec<u8>, PrecompileError>,
 {
     // If there is no minimum gas, return error.
     if min_gas > gas_limit {
@@ -108,7 +175,7 @@ where
     debug_assert_eq!(modulus.len(), mod_len);
 
     // Call the modexp.
-    let output = modexp(base, exponent, modulus);
+    let output = modexp(base, exponent, modulus)?;
 
     // Left pad the result to modulus length. bytes will always by less or equal to modulus length.
     Ok(PrecompileOutput::new(
@@ -117,6 +184,122 @@ where
     ))
 }
 
+/// Number of exponent bytes processed between two checks of [`run_inner_with_check`].
+pub const EXP_CHUNK_LEN: usize = 32;
+
+/// Computes `base ^ exponent % modulus` one exponent chunk at a time.
+///
+/// For `exponent = high * 256^len(chunk) + chunk` the accumulated `base ^ high` is raised
+/// to `256^len(chunk)` and multiplied with `base ^ chunk`.
+fn chunked_modexp(
+    base: &[u8],
+    exponent: &[u8],
+    modulus: &[u8],
+    mut check: impl FnMut() -> Result<(), PrecompileError>,
+) -> Result<Vec<u8>, PrecompileError> {
+    let mut chunks = exponent.chunks(EXP_CHUNK_LEN);
+    let Some(first) = chunks.next() else {
+        return Ok(modexp(base, exponent, modulus));
+    };
+    if modulus.iter().all(|byte| *byte == 0) {
+        return Ok(Vec::new());
+    }
+
+    let mut acc = modexp(base, first, modulus);
+    for chunk in chunks {
+        check()?;
+        // 256^len(chunk)
+        let mut shift = vec![0; chunk.len() + 1];
+        shift[0] = 1;
+        acc = modexp(&acc, &shift, modulus);
+        acc = mul_mod(&acc, &modexp(base, chunk, modulus), modulus);
+    }
+    Ok(acc)
+}
+
+/// Computes `a * b % modulus` for `a, b < modulus` using only modular squaring.
+///
+/// `(a + b)^2 - (a - b)^2 = 4ab`, squares are reduced modulo `4 * modulus` so that the
+/// difference is exactly `4 * (ab % modulus)`.
+fn mul_mod(a: &[u8], b: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let modulus4 = shl2(modulus);
+    let sum_sq = modexp(&add(a, b), &[2], &modulus4);
+    let diff = match cmp(a, b) {
+        Ordering::Less => sub(b, a),
+        _ => sub(a, b),
+    };
+    let diff_sq = modexp(&diff, &[2], &modulus4);
+    let product4 = match cmp(&sum_sq, &diff_sq) {
+        Ordering::Less => sub(&add(&sum_sq, &modulus4), &diff_sq),
+        _ => sub(&sum_sq, &diff_sq),
+    };
+    let mut product = shr2(&product4);
+    let leading_zeros = product.iter().take_while(|byte| **byte == 0).count();
+    product.drain(..leading_zeros);
+    product
+}
+
+/// Big endian addition.
+fn add(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let len = max(a.len(), b.len()) + 1;
+    let mut out = vec![0; len];
+    let mut carry = 0u16;
+    for i in 0..len {
+        let x = a.len().checked_sub(i + 1).map_or(0, |j| a[j]) as u16;
+        let y = b.len().checked_sub(i + 1).map_or(0, |j| b[j]) as u16;
+        let sum = x + y + carry;
+        out[len - i - 1] = sum as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+/// Big endian subtraction, `a` must not be less than `b`.
+fn sub(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = a.to_vec();
+    let mut borrow = 0i16;
+    for i in 0..a.len() {
+        let y = b.len().checked_sub(i + 1).map_or(0, |j| b[j]) as i16;
+        let mut diff = a[a.len() - i - 1] as i16 - y - borrow;
+        borrow = (diff < 0) as i16;
+        if diff < 0 {
+            diff += 256;
+        }
+        out[a.len() - i - 1] = diff as u8;
+    }
+    debug_assert_eq!(borrow, 0);
+    out
+}
+
+/// Big endian comparison.
+fn cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let strip = |x: &[u8]| -> usize { x.iter().position(|byte| *byte != 0).unwrap_or(x.len()) };
+    let (a, b) = (&a[strip(a)..], &b[strip(b)..]);
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Big endian multiplication by four.
+fn shl2(a: &[u8]) -> Vec<u8> {
+    let mut out = vec![0; a.len() + 1];
+    for (i, byte) in a.iter().enumerate() {
+        out[i] |= byte >> 6;
+        out[i + 1] = byte << 2;
+    }
+    out
+}
+
+/// Big endian division by four.
+fn shr2(a: &[u8]) -> Vec<u8> {
+    let mut out = vec![0; a.len()];
+    for (i, byte) in a.iter().enumerate() {
+        out[i] |= byte >> 2;
+        if i + 1 < a.len() {
+            out[i + 1] = byte << 6;
+        }
+    }
+    out
+}
+
 pub fn byzantium_gas_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &U256) -> u64 {
     // Output of this function is bounded by 2^128
     fn mul_complexity(x: u64) -> U256 {
@@ -380,4 +563,32 @@ mod tests {
         let expected: Vec<u8> = Vec::new();
         assert_eq!(res.bytes, expected)
     }
+
+    #[test]
+    fn test_modexp_with_check() {
+        for test in TESTS.iter() {
+            let input = hex::decode(test.input).unwrap().into();
+            let res = berlin_run_with_check(&input, 100_000_000, || Ok(())).unwrap();
+            let expected = hex::decode(test.expected).unwrap();
+            assert_eq!(res.bytes, expected, "{}", test.name);
+        }
+    }
+
+    #[test]
+    fn test_chunked_modexp_matches_modexp() {
+        let base = [0xff; 40];
+        let modulus = [0xfe; 33];
+        let exponent: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(37)).collect();
+        let mut checks = 0;
+        let chunked = chunked_modexp(&base, &exponent, &modulus, || {
+            checks += 1;
+            Ok(())
+        });
+        assert_eq!(chunked, Ok(modexp(&base, &exponent, &modulus)));
+        assert_eq!(checks, 3);
+
+        let interrupted =
+            chunked_modexp(&base, &exponent, &modulus, || Err(PrecompileError::Timeout));
+        assert_eq!(interrupted, Err(PrecompileError::Timeout));
+    }
 }
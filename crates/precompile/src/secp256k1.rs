@@ -31,16 +31,23 @@ pub fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     let recid = input[63] - 27;
     let sig = <&B512>::try_from(&input[64..128]).unwrap();
 
+    let out = ecrecover(sig, recid, msg)
+        .map(|o| o.to_vec().into())
+        .unwrap_or_default();
+    Ok(PrecompileOutput::new(ECRECOVER_BASE, out))
+}
+
+/// Recovers the address that signed the message hash, using the enabled secp256k1 backend.
+///
+/// Returns the address left padded to 32 bytes, or `None` if the signature is invalid.
+pub fn ecrecover(sig: &B512, recid: u8, msg: &B256) -> Option<B256> {
     cfg_if::cfg_if! {
         if #[cfg(feature = "secp256k1")] {
-            let res = bitcoin_secp256k1::ecrecover(sig, recid, msg);
+            bitcoin_secp256k1::ecrecover(sig, recid, msg).ok()
         } else if #[cfg(feature = "libsecp256k1")] {
-            let res = parity_libsecp256k1::ecrecover(sig, recid, msg);
+            parity_libsecp256k1::ecrecover(sig, recid, msg).ok()
         } else {
-            let res = k256::ecrecover(sig, recid, msg);
+            k256::ecrecover(sig, recid, msg).ok()
         }
-    };
-
-    let out = res.map(|o| o.to_vec().into()).unwrap_or_default();
-    Ok(PrecompileOutput::new(ECRECOVER_BASE, out))
+    }
 }
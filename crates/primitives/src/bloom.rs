@@ -0,0 +1,58 @@
+//! Logs bloom filter of receipts and block headers.
+use crate::{Bloom, Log};
+
+/// Computes the bloom filter of the logs, as in the `logsBloom` field of receipts and headers.
+///
+/// For each log the address and every topic are hashed with keccak256. Three 11-bit indices
+/// taken from the first six bytes of every hash each set one bit of the 2048-bit filter, the
+/// `M3:2048` function of the yellow paper. Blooms of several receipts are combined with
+/// [`Bloom::accrue_bloom`].
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bloom {
+    let mut bloom = Bloom::ZERO;
+    for log in logs {
+        bloom.accrue_log(log);
+    }
+    bloom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, b256, hex, Bytes};
+    extern crate alloc;
+    use alloc::vec;
+
+    #[test]
+    fn matches_reference_bloom() {
+        let log = Log::new_unchecked(
+            address!("ef2d6d194084c2de36e0dabfce45d046b37d1106"),
+            vec![b256!(
+                "02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc"
+            )],
+            Bytes::from_static(b"data is not part of the bloom"),
+        );
+        let expected = Bloom::new(hex!(
+            "00000000000000000000000000000000
+             00000000100000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000002020000000000000000000000
+             00000000000000000000000800000000
+             10000000000000000000000000000000
+             00000000000000000000001000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000"
+        ));
+        assert_eq!(logs_bloom([&log]), expected);
+        assert_eq!(logs_bloom(&[]), Bloom::ZERO);
+
+        // Duplicated logs set the same bits.
+        assert_eq!(logs_bloom([&log, &log]), expected);
+    }
+}
@@ -0,0 +1,147 @@
+//! [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blob gas pricing.
+//!
+//! Used by the EVM and when building blocks.
+
+/// Gas consumption of a single data blob (== blob byte size).
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+
+/// Target consumable blob gas per block in Cancun, 3 blobs.
+pub const TARGET_BLOB_GAS_PER_BLOCK_CANCUN: u64 = 3 * GAS_PER_BLOB;
+
+/// Target consumable blob gas per block since Prague, 6 blobs
+/// ([EIP-7691](https://eips.ethereum.org/EIPS/eip-7691)).
+pub const TARGET_BLOB_GAS_PER_BLOCK_PRAGUE: u64 = 6 * GAS_PER_BLOB;
+
+/// Maximum consumable blob gas per block in Cancun, 6 blobs.
+pub const MAX_BLOB_GAS_PER_BLOCK_CANCUN: u64 = 6 * GAS_PER_BLOB;
+
+/// Maximum consumable blob gas per block since Prague, 9 blobs
+/// ([EIP-7691](https://eips.ethereum.org/EIPS/eip-7691)).
+pub const MAX_BLOB_GAS_PER_BLOCK_PRAGUE: u64 = 9 * GAS_PER_BLOB;
+
+/// Minimum base fee per blob gas.
+pub const MIN_BLOB_BASE_FEE: u64 = 1;
+
+/// Controls the maximum rate of change of the blob base fee in Cancun.
+pub const BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN: u64 = 3338477;
+
+/// Controls the maximum rate of change of the blob base fee since Prague
+/// ([EIP-7691](https://eips.ethereum.org/EIPS/eip-7691)).
+pub const BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE: u64 = 5007716;
+
+/// Calculates the `excess_blob_gas` of a block from its parent's `excess_blob_gas` and
+/// `blob_gas_used`.
+///
+/// `is_prague` selects the Prague blob target.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`calc_excess_blob_gas`).
+#[inline]
+pub const fn calc_excess_blob_gas(
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+    is_prague: bool,
+) -> u64 {
+    let target = if is_prague {
+        TARGET_BLOB_GAS_PER_BLOCK_PRAGUE
+    } else {
+        TARGET_BLOB_GAS_PER_BLOCK_CANCUN
+    };
+    (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(target)
+}
+
+/// Calculates the base fee per blob gas from the block's `excess_blob_gas`.
+///
+/// `is_prague` selects the Prague update fraction.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`get_base_fee_per_blob_gas`).
+#[inline]
+pub fn get_base_fee_per_blob_gas(excess_blob_gas: u64, is_prague: bool) -> u128 {
+    let update_fraction = if is_prague {
+        BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE
+    } else {
+        BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN
+    };
+    fake_exponential(MIN_BLOB_BASE_FEE, excess_blob_gas, update_fraction)
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
+///
+/// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
+/// (`fake_exponential`).
+///
+/// # Panics
+///
+/// This function panics if `denominator` is zero.
+#[inline]
+pub fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u128 {
+    assert_ne!(denominator, 0, "attempt to divide by zero");
+    let factor = factor as u128;
+    let numerator = numerator as u128;
+    let denominator = denominator as u128;
+
+    let mut i = 1;
+    let mut output = 0;
+    let mut numerator_accum = factor * denominator;
+    while numerator_accum > 0 {
+        output += numerator_accum;
+
+        // Denominator is asserted as not zero at the start of the function.
+        numerator_accum = (numerator_accum * numerator) / (denominator * i);
+        i += 1;
+    }
+    output / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excess_blob_gas_per_fork() {
+        let blobs = |n| n * GAS_PER_BLOB;
+        for (parent_excess, parent_used, is_prague, expected) in [
+            (0, blobs(3), false, 0),
+            (0, blobs(4), false, blobs(1)),
+            (blobs(1), blobs(1), false, 0),
+            (blobs(2), blobs(2), false, blobs(1)),
+            // Prague doubles the target.
+            (0, blobs(4), true, 0),
+            (0, blobs(6), true, 0),
+            (0, blobs(9), true, blobs(3)),
+            (blobs(10), blobs(0), true, blobs(4)),
+        ] {
+            assert_eq!(
+                calc_excess_blob_gas(parent_excess, parent_used, is_prague),
+                expected,
+                "{parent_excess} {parent_used} {is_prague}"
+            );
+        }
+    }
+
+    // Cancun vectors are from https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L60
+    #[test]
+    fn base_fee_per_blob_gas_per_fork() {
+        for (excess, is_prague, expected) in [
+            (0, false, 1),
+            (2314057, false, 1),
+            (2314058, false, 2),
+            (10 * 1024 * 1024, false, 23),
+            (148099578, false, 18446739238971471609),
+            (161087488, false, 902580055246494526580),
+            // Prague has a larger update fraction, so the fee rises slower.
+            (0, true, 1),
+            (3471086, true, 1),
+            (3471087, true, 2),
+            (10 * 1024 * 1024, true, 8),
+            (161087488, true, 93394741990580),
+        ] {
+            assert_eq!(
+                get_base_fee_per_blob_gas(excess, is_prague),
+                expected,
+                "{excess} {is_prague}"
+            );
+        }
+    }
+}
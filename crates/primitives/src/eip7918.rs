@@ -0,0 +1,77 @@
+//! [EIP-7918](https://eips.ethereum.org/EIPS/eip-7918) blob base fee bounded by execution cost.
+use crate::eip4844::{
+    get_base_fee_per_blob_gas, GAS_PER_BLOB, MAX_BLOB_GAS_PER_BLOCK_PRAGUE,
+    TARGET_BLOB_GAS_PER_BLOCK_PRAGUE,
+};
+
+/// Execution gas that a blob costs at least, priced at the block base fee.
+pub const BLOB_BASE_COST: u64 = 1 << 13;
+
+/// Returns whether a blob at `blob_base_fee` costs less than [`BLOB_BASE_COST`] gas at the
+/// execution base fee.
+#[inline]
+pub const fn is_reserve_price_active(base_fee_per_gas: u64, blob_base_fee: u128) -> bool {
+    BLOB_BASE_COST as u128 * base_fee_per_gas as u128 > GAS_PER_BLOB as u128 * blob_base_fee
+}
+
+/// Calculates the `excess_blob_gas` of a block since Osaka from its parent's `excess_blob_gas`,
+/// `blob_gas_used` and `base_fee_per_gas`.
+///
+/// While the reserve price is active, the excess only grows by the blob gas used above the
+/// target, scaled by `(max - target) / max`, and does not fall.
+///
+/// See also [the EIP-7918 specification](https://eips.ethereum.org/EIPS/eip-7918#specification)
+/// (`calc_excess_blob_gas`).
+#[inline]
+pub fn calc_excess_blob_gas(
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+    parent_base_fee_per_gas: u64,
+) -> u64 {
+    let target = TARGET_BLOB_GAS_PER_BLOCK_PRAGUE;
+    let max = MAX_BLOB_GAS_PER_BLOCK_PRAGUE;
+    if parent_excess_blob_gas + parent_blob_gas_used < target {
+        return 0;
+    }
+    let blob_base_fee = get_base_fee_per_blob_gas(parent_excess_blob_gas, true);
+    if is_reserve_price_active(parent_base_fee_per_gas, blob_base_fee) {
+        parent_excess_blob_gas + parent_blob_gas_used * (max - target) / max
+    } else {
+        parent_excess_blob_gas + parent_blob_gas_used - target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vectors follow the pseudocode of the EIP with the Prague blob schedule, 6 target and 9
+    // max blobs.
+    #[test]
+    fn excess_blob_gas_with_reserve_price() {
+        let blobs = |n| n * GAS_PER_BLOB;
+        for (parent_excess, parent_used, parent_base_fee, expected) in [
+            // Below the target the excess is zero either way.
+            (0, blobs(5), 0, 0),
+            (0, blobs(5), 1_000_000_000, 0),
+            // At the minimum blob base fee the reserve price is active above 16 wei.
+            (0, blobs(6), 16, 0),
+            (0, blobs(6), 17, blobs(2)),
+            (0, blobs(9), 0, blobs(3)),
+            (0, blobs(9), 1_000_000_000, blobs(3)),
+            // The excess does not fall while the reserve price is active.
+            (blobs(10), 0, 0, blobs(4)),
+            (blobs(10), 0, 1_000_000_000, blobs(10)),
+            (blobs(10), blobs(3), 1_000_000_000, blobs(11)),
+            // Blob base fee of 8 wei at this excess, the reserve price needs more than 128 wei.
+            (10 * 1024 * 1024, blobs(6), 128, 10 * 1024 * 1024),
+            (10 * 1024 * 1024, blobs(6), 129, 10 * 1024 * 1024 + blobs(2)),
+        ] {
+            assert_eq!(
+                calc_excess_blob_gas(parent_excess, parent_used, parent_base_fee),
+                expected,
+                "{parent_excess} {parent_used} {parent_base_fee}"
+            );
+        }
+    }
+}
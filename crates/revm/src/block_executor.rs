@@ -0,0 +1,195 @@
+// Lives in `revm` rather than `handler`: it drives the EVM through `EvmCommit`, which is
+// defined here, and `handler` can't depend on this crate.
+use crate::exec::EvmCommit;
+use context_interface::{
+    result::{ExecutionResult, HaltReasonTrait, InvalidTransaction},
+    Block, Transaction,
+};
+use primitives::{logs_bloom, Bloom, Log};
+use std::vec::Vec;
+
+/// Receipt of a transaction executed by [`BlockExecutor`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Receipt {
+    /// `true` if the transaction was successful.
+    pub success: bool,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+    /// Gas used by the block up to and including this transaction.
+    pub cumulative_gas_used: u64,
+    /// Block wide index of the first log of this transaction.
+    pub first_log_index: u64,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+    /// Bloom filter of the emitted logs.
+    pub logs_bloom: Bloom,
+}
+
+impl Receipt {
+    /// Returns logs paired with their block wide log index.
+    pub fn indexed_logs(&self) -> impl Iterator<Item = (u64, &Log)> {
+        (self.first_log_index..).zip(self.logs.iter())
+    }
+}
+
+/// Output of the block execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockOutput {
+    /// Receipts of executed transactions, in execution order.
+    pub receipts: Vec<Receipt>,
+    /// Total gas used by the block.
+    pub gas_used: u64,
+    /// Bloom filter of all logs emitted in the block.
+    pub logs_bloom: Bloom,
+}
+
+/// Executes transactions of a single block and assembles their receipts.
+///
+/// Each transaction is committed to the database. Transactions whose gas limit is greater
+/// than the gas that is left in the block are refused.
+#[derive(Debug)]
+pub struct BlockExecutor<EVM> {
+    evm: EVM,
+    block_gas_limit: u64,
+    output: BlockOutput,
+    log_count: u64,
+}
+
+impl<EVM: EvmCommit> BlockExecutor<EVM> {
+    /// Creates a new block executor and sets the block environment of the EVM.
+    pub fn new(mut evm: EVM, block: EVM::Block) -> Self {
+        let block_gas_limit = block.gas_limit();
+        evm.set_block(block);
+        Self {
+            evm,
+            block_gas_limit,
+            output: BlockOutput::default(),
+            log_count: 0,
+        }
+    }
+
+    /// Returns the gas used by the transactions executed so far.
+    pub fn cumulative_gas_used(&self) -> u64 {
+        self.output.gas_used
+    }
+
+    /// Returns the receipts of the transactions executed so far.
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.output.receipts
+    }
+
+    /// Returns a mutable reference to the wrapped EVM.
+    pub fn evm_mut(&mut self) -> &mut EVM {
+        &mut self.evm
+    }
+
+    /// Executes and commits the transaction, returning its receipt.
+    ///
+    /// Fails with [`InvalidTransaction::CallerGasLimitMoreThanBlock`] if the transaction gas limit
+    /// exceeds the gas left in the block. Refused or failed transactions do not produce a receipt.
+    pub fn execute_tx<HALT, ERROR>(&mut self, tx: EVM::Transaction) -> Result<&Receipt, ERROR>
+    where
+        EVM: EvmCommit<CommitOutput = Result<ExecutionResult<HALT>, ERROR>>,
+        HALT: HaltReasonTrait,
+        ERROR: From<InvalidTransaction>,
+    {
+        let gas_left = self.block_gas_limit - self.output.gas_used;
+        if tx.gas_limit() > gas_left {
+            return Err(InvalidTransaction::CallerGasLimitMoreThanBlock.into());
+        }
+
+        let result = self.evm.exec_commit_with_tx(tx)?;
+        let gas_used = result.gas_used();
+        self.output.gas_used += gas_used;
+
+        let logs = result.logs().to_vec();
+        let logs_bloom = logs_bloom(&logs);
+        self.output.logs_bloom.accrue_bloom(&logs_bloom);
+
+        let first_log_index = self.log_count;
+        self.log_count += logs.len() as u64;
+
+        self.output.receipts.push(Receipt {
+            success: result.is_success(),
+            gas_used,
+            cumulative_gas_used: self.output.gas_used,
+            first_log_index,
+            logs,
+            logs_bloom,
+        });
+        Ok(self.output.receipts.last().unwrap())
+    }
+
+    /// Consumes the executor and returns the block output.
+    pub fn finish(self) -> BlockOutput {
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context, MainEvm};
+    use bytecode::{
+        opcode::{LOG0, PUSH1, STOP},
+        Bytecode,
+    };
+    use context::{block::BlockEnv, tx::TxEnv};
+    use context_interface::result::EVMError;
+    use database::CacheDB;
+    use database_interface::EmptyDB;
+    use handler::EthHandler;
+    use primitives::{Address, TxKind};
+    use state::AccountInfo;
+
+    #[test]
+    fn accumulates_gas_and_receipts() {
+        let contract = Address::with_last_byte(0x42);
+        let caller = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x00, PUSH1, 0x00, LOG0, STOP].into(),
+            )),
+        );
+
+        let evm = MainEvm::new(Context::builder().with_db(db), EthHandler::default());
+        let block = BlockEnv {
+            gas_limit: 250_000,
+            ..Default::default()
+        };
+        let mut executor = BlockExecutor::new(evm, block);
+        let tx = |nonce, gas_limit| TxEnv {
+            caller,
+            kind: TxKind::Call(contract),
+            nonce,
+            gas_limit,
+            ..Default::default()
+        };
+
+        for nonce in 0..2 {
+            let receipt = executor.execute_tx(tx(nonce, 100_000)).unwrap();
+            assert!(receipt.success);
+            assert_eq!(receipt.first_log_index, nonce);
+            assert!(receipt.logs_bloom.contains_log(&receipt.logs[0]));
+        }
+
+        // Fits the block gas limit but not the gas left in the block.
+        assert!(matches!(
+            executor.execute_tx(tx(2, 230_000)),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CallerGasLimitMoreThanBlock
+            ))
+        ));
+
+        let output = executor.finish();
+        assert_eq!(output.receipts.len(), 2);
+        assert_eq!(output.gas_used, output.receipts[1].cumulative_gas_used);
+        assert_eq!(
+            output.gas_used,
+            output.receipts.iter().map(|r| r.gas_used).sum::<u64>()
+        );
+        assert!(output.logs_bloom.contains_log(&output.receipts[1].logs[0]));
+    }
+}
@@ -3,7 +3,7 @@ use context::{block::BlockEnv, tx::TxEnv, CfgEnv, Context, JournaledState};
 use context_interface::{
     block::BlockSetter,
     context::PerformantContextAccess,
-    journaled_state::Journal,
+    journaled_state::{Journal, JournalCheckpoint},
     result::{
         EVMError, ExecutionResult, HaltReasonTrait, InvalidHeader, InvalidTransaction,
         ResultAndState,
@@ -12,6 +12,8 @@ use context_interface::{
     BlockGetter, CfgGetter, DatabaseGetter, ErrorGetter, JournalDBError, JournalGetter,
     Transaction, TransactionGetter,
 };
+#[cfg(any(test, feature = "database"))]
+use database::{states::bundle_state::BundleRetention, BundleState, State};
 use database_interface::{Database, DatabaseCommit};
 use handler::{EthHandler, FrameResult};
 use handler_interface::{
@@ -41,6 +43,107 @@ impl<ERROR, CTX, HANDLER> Evm<ERROR, CTX, HANDLER> {
     }
 }
 
+impl<ERROR, BLOCK, TX, CFG, DB, JOURNAL, CHAIN, HANDLER>
+    Evm<ERROR, Context<BLOCK, TX, CFG, DB, JOURNAL, CHAIN>, HANDLER>
+where
+    DB: Database,
+    JOURNAL: Journal<Database = DB>,
+{
+    /// Sets a new transaction and block and clears the per transaction state of the journal,
+    /// including warm addresses, transient storage, logs and journal entries.
+    ///
+    /// The database, and with it any state cached by it, is kept. This allows executing many
+    /// transactions against the same state without rebuilding the EVM.
+    pub fn reset_context(&mut self, tx: TX, block: BLOCK) {
+        self.context.tx = tx;
+        self.context.block = block;
+        self.context.journaled_state.clear();
+        self.context.error = Ok(());
+    }
+}
+
+#[cfg(any(test, feature = "database"))]
+impl<ERROR, BLOCK, TX, CFG, DB, JOURNAL, CHAIN, HANDLER>
+    Evm<ERROR, Context<BLOCK, TX, CFG, State<DB>, JOURNAL, CHAIN>, HANDLER>
+where
+    DB: Database,
+    JOURNAL: Journal<Database = State<DB>>,
+{
+    /// Takes the changes of all transactions committed since the last call as a bundle and
+    /// clears the journal, preparing the EVM for the next block.
+    ///
+    /// Transitions are merged with reverts retained. The journal is cleared of warm addresses,
+    /// transient storage, logs and journal entries. The cache of the [`State`] is kept, so the
+    /// next block continues from the committed state.
+    pub fn take_bundle(&mut self) -> BundleState {
+        self.context.journaled_state.clear();
+        self.context.error = Ok(());
+        let state = self.context.journaled_state.db();
+        state.merge_transitions(BundleRetention::Reverts);
+        state.take_bundle()
+    }
+}
+
+/// Snapshot of the EVM journal, see [`Evm::snapshot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvmSnapshot {
+    checkpoint: JournalCheckpoint,
+    /// Depth of the journal when the snapshot was taken.
+    depth: usize,
+}
+
+/// Error of [`Evm::restore`] for a snapshot that was discarded, by restoring an older snapshot,
+/// leaving the frame it was taken in or finalizing the journal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaleSnapshotError;
+
+impl core::fmt::Display for StaleSnapshotError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("snapshot was discarded")
+    }
+}
+
+impl core::error::Error for StaleSnapshotError {}
+
+impl<ERROR, BLOCK, TX, CFG, DB, CHAIN, HANDLER>
+    Evm<ERROR, Context<BLOCK, TX, CFG, DB, JournaledState<DB>, CHAIN>, HANDLER>
+where
+    DB: Database,
+{
+    /// Takes a snapshot of the journaled state, transient storage and logs.
+    ///
+    /// Snapshot is a journal checkpoint, changes made after it are reverted by
+    /// [`restore`][Self::restore]. Snapshots can be nested, restoring a snapshot discards all
+    /// snapshots taken after it.
+    ///
+    /// Snapshots are only valid until the journal is finalized, e.g. by executing a transaction.
+    pub fn snapshot(&mut self) -> EvmSnapshot {
+        let journal = &mut self.context.journaled_state;
+        let depth = journal.depth;
+        let checkpoint = journal.checkpoint();
+        // Snapshots do not open a call frame.
+        journal.checkpoint_commit();
+        EvmSnapshot { checkpoint, depth }
+    }
+
+    /// Reverts all changes made after the snapshot was taken.
+    ///
+    /// Returns an error and leaves the journal untouched if the snapshot was discarded.
+    pub fn restore(&mut self, snapshot: EvmSnapshot) -> Result<(), StaleSnapshotError> {
+        let journal = &mut self.context.journaled_state;
+        if snapshot.checkpoint.generation != journal.generation
+            || snapshot.depth > journal.depth
+            || snapshot.checkpoint.journal_i >= journal.journal.len()
+        {
+            return Err(StaleSnapshotError);
+        }
+        // Reverting closes the frame the snapshot was taken in, it ends at the snapshot depth.
+        journal.depth = snapshot.depth + 1;
+        journal.checkpoint_revert(snapshot.checkpoint);
+        Ok(())
+    }
+}
+
 impl<ERROR, CTX, VAL, PREEXEC, EXEC, POSTEXEC, HALT> EvmCommit
     for Evm<ERROR, CTX, EthHandler<CTX, ERROR, VAL, PREEXEC, EXEC, POSTEXEC>>
 where
@@ -286,6 +389,182 @@ where
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{LOG0, PUSH1, SSTORE, STOP, TLOAD, TSTORE},
+        Bytecode,
+    };
+    use database::{BenchmarkDB, CacheDB};
+    use database_interface::EmptyDB;
+    use handler::EthHandler;
+    use primitives::{Address, TxKind, U256};
+    use state::AccountInfo;
+    use std::vec;
+
+    #[test]
+    fn take_bundle_matches_independent_blocks() {
+        let caller = |n: u8| Address::with_last_byte(0xa0 + n);
+        let contract = |n: u8| Address::with_last_byte(0xc0 + n);
+        // Sets transient and persistent slot 1 and emits a log.
+        let code = Bytecode::new_legacy(
+            [
+                PUSH1, 0x01, PUSH1, 0x01, TSTORE, PUSH1, 0x01, PUSH1, 0x01, SSTORE, PUSH1, 0x00,
+                PUSH1, 0x00, LOG0, STOP,
+            ]
+            .into(),
+        );
+        let evm = || {
+            let mut db = CacheDB::new(EmptyDB::default());
+            for n in 0..2 {
+                db.insert_account_info(contract(n), AccountInfo::from_bytecode(code.clone()));
+                db.insert_account_info(caller(n), AccountInfo::from_balance(U256::from(1)));
+            }
+            let state = State::builder()
+                .with_database(db)
+                .with_bundle_update()
+                .build();
+            MainEvm::new(Context::builder().with_db(state), EthHandler::default())
+        };
+        let block = |n| {
+            vec![TxEnv {
+                caller: caller(n),
+                kind: TxKind::Call(contract(n)),
+                gas_limit: 100_000,
+                ..Default::default()
+            }]
+        };
+
+        let mut sequential = evm();
+        for n in 0..2 {
+            let results = sequential.transact_block(block(n));
+            assert!(results
+                .iter()
+                .all(|result| result.as_ref().unwrap().is_success()));
+            let bundle = sequential.take_bundle();
+            let journal = &sequential.context.journaled_state;
+            assert!(journal.transient_storage.is_empty());
+            assert!(journal.logs.is_empty());
+            assert!(journal.state.is_empty());
+            assert_eq!(journal.journal, vec![vec![]]);
+
+            let mut independent = evm();
+            independent.transact_block(block(n));
+            assert_eq!(bundle, independent.take_bundle());
+            assert_eq!(
+                bundle
+                    .account(&contract(n))
+                    .unwrap()
+                    .storage_slot(U256::from(1)),
+                Some(U256::from(1))
+            );
+        }
+    }
+
+    #[test]
+    fn reset_context_matches_fresh_evm() {
+        // Copies transient slot zero to storage slot zero.
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x00, TLOAD, PUSH1, 0x00, SSTORE, STOP].into());
+        let envs = [1u64, 2].map(|n| {
+            let tx = TxEnv {
+                caller: Address::with_last_byte(1),
+                kind: TxKind::Call(Address::ZERO),
+                gas_limit: 100_000 * n,
+                ..Default::default()
+            };
+            let block = BlockEnv {
+                number: n,
+                ..Default::default()
+            };
+            (tx, block)
+        });
+        let context = || Context::builder().with_db(BenchmarkDB::new_bytecode(bytecode.clone()));
+
+        let fresh = envs.clone().map(|(tx, block)| {
+            let mut evm = MainEvm::new(
+                context().with_tx(tx).with_block(block),
+                EthHandler::default(),
+            );
+            evm.transact().unwrap()
+        });
+
+        let mut evm = MainEvm::new(context(), EthHandler::default());
+        for ((tx, block), expected) in envs.into_iter().zip(fresh) {
+            // Leftovers of an aborted transaction.
+            let journal = &mut evm.context.journaled_state;
+            journal.tstore(Address::ZERO, U256::ZERO, U256::from(1));
+            journal.warm_account(Address::with_last_byte(0x42));
+            journal.checkpoint();
+
+            evm.reset_context(tx, block);
+            assert_eq!(evm.transact().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn nested_snapshots_restore_in_order() {
+        let address = Address::with_last_byte(0x42);
+        let key = U256::from(1);
+        let log = |n| Log::new_unchecked(address, Vec::new(), vec![n].into());
+        let mut evm = MainEvm::new(Context::builder(), EthHandler::default());
+        evm.context.journaled_state.load_account(address).unwrap();
+
+        let outer = evm.snapshot();
+        let journal = &mut evm.context.journaled_state;
+        journal.tstore(address, key, U256::from(1));
+        journal.log(log(1));
+
+        let inner = evm.snapshot();
+        let journal = &mut evm.context.journaled_state;
+        journal.tstore(address, key, U256::from(2));
+        journal.sstore(address, key, U256::from(5)).unwrap();
+        journal.log(log(2));
+
+        evm.restore(inner.clone()).unwrap();
+        let journal = &mut evm.context.journaled_state;
+        assert_eq!(journal.tload(address, key), U256::from(1));
+        assert_eq!(journal.sload(address, key).unwrap().data, U256::ZERO);
+        assert_eq!(journal.logs, vec![log(1)]);
+
+        journal.tstore(address, key, U256::from(3));
+        let empty = evm.snapshot();
+        evm.restore(empty).unwrap();
+        assert_eq!(
+            evm.context.journaled_state.tload(address, key),
+            U256::from(3)
+        );
+
+        evm.restore(outer).unwrap();
+        let journal = &mut evm.context.journaled_state;
+        assert_eq!(journal.tload(address, key), U256::ZERO);
+        assert!(journal.logs.is_empty());
+        assert_eq!(journal.depth, 0);
+        // Restoring the outer snapshot discarded the inner one.
+        assert_eq!(evm.restore(inner), Err(StaleSnapshotError));
+    }
+
+    #[test]
+    fn finalize_discards_snapshots() {
+        let address = Address::with_last_byte(0x42);
+        let key = U256::from(1);
+        let mut evm = MainEvm::new(Context::builder(), EthHandler::default());
+
+        let snapshot = evm.snapshot();
+        evm.context.journaled_state.finalize().unwrap();
+        // Same checkpoint position in the new journal.
+        let _ = evm.snapshot();
+        evm.context
+            .journaled_state
+            .tstore(address, key, U256::from(1));
+
+        assert_eq!(evm.restore(snapshot), Err(StaleSnapshotError));
+        let journal = &mut evm.context.journaled_state;
+        assert_eq!(journal.tload(address, key), U256::from(1));
+        assert_eq!(journal.depth, 0);
+    }
+}
+
 /*
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use context_interface::{Block, Transaction};
+use std::vec::Vec;
 
 pub trait EvmExec {
     type Transaction: Transaction;
@@ -26,4 +27,77 @@ pub trait EvmCommit: EvmExec {
         self.set_tx(tx);
         self.exec_commit()
     }
+
+    /// Executes and commits the transactions in order, returning their outputs.
+    ///
+    /// Per transaction state (warm accounts and storage, transient storage, logs) is reset
+    /// before each transaction, while state committed to the database by earlier transactions
+    /// is seen by the later ones, as in a block. Failed transactions are returned as errors and
+    /// do not stop the execution of the remaining ones.
+    fn transact_block(
+        &mut self,
+        txs: impl IntoIterator<Item = Self::Transaction>,
+    ) -> Vec<Self::CommitOutput> {
+        txs.into_iter()
+            .map(|tx| self.exec_commit_with_tx(tx))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context, MainEvm};
+    use bytecode::{
+        opcode::{BALANCE, PUSH20, STOP},
+        Bytecode,
+    };
+    use context::TxEnv;
+    use database::CacheDB;
+    use database_interface::EmptyDB;
+    use handler::EthHandler;
+    use primitives::{Address, TxKind, U256};
+    use state::AccountInfo;
+    use std::vec;
+
+    #[test]
+    fn transact_block_resets_warm_accounts_per_tx() {
+        let caller = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(0xc0);
+        let target = Address::with_last_byte(0xee);
+        // Reads the balance of the target account.
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[BALANCE, STOP]);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+
+        let mut evm = MainEvm::new(Context::builder().with_db(db), EthHandler::default());
+        let txs = (0..2).map(|nonce| TxEnv {
+            caller,
+            kind: TxKind::Call(contract),
+            gas_limit: 100_000,
+            nonce,
+            ..Default::default()
+        });
+        let results = evm.transact_block(txs);
+
+        // Second transaction sees the nonce committed by the first one and pays the cold
+        // account access again.
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!(result.unwrap().gas_used(), 21_000 + 3 + 2600);
+        }
+        assert_eq!(
+            evm.context.journaled_state.database.accounts[&caller]
+                .info
+                .nonce,
+            2
+        );
+    }
 }
@@ -20,13 +20,19 @@ pub use state;
 
 // Modules.
 
+mod block_executor;
 mod evm;
 mod exec;
+mod state_override;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 // Export items.
 
+pub use block_executor::{BlockExecutor, BlockOutput, Receipt};
 pub use context::journaled_state::{JournalEntry, JournaledState};
 pub use context::Context;
 pub use database_interface::{Database, DatabaseCommit, DatabaseRef};
-pub use evm::{Error, EthContext, Evm, MainEvm};
+pub use evm::{Error, EthContext, Evm, EvmSnapshot, MainEvm, StaleSnapshotError};
 pub use exec::{EvmCommit, EvmExec};
+pub use state_override::{AccountOverride, StateOverride, StorageOverride};
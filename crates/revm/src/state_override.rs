@@ -0,0 +1,264 @@
+use crate::Evm;
+use bytecode::Bytecode;
+use context::{Context, JournaledState};
+use database_interface::Database;
+use primitives::{Address, HashMap, U256};
+use state::{AccountStatus, EvmStorageSlot};
+
+/// Storage override of a single account, see [`AccountOverride::storage`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageOverride {
+    /// Replaces the whole account storage, slots that are not listed read as zero.
+    ///
+    /// Equivalent of the `state` field of the `eth_call` state override.
+    Replace(HashMap<U256, U256>),
+    /// Overrides only the listed slots, other slots are read from the database.
+    ///
+    /// Equivalent of the `stateDiff` field of the `eth_call` state override.
+    Diff(HashMap<U256, U256>),
+}
+
+/// Override of a single account, fields that are `None` are not overridden.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Bytecode>,
+    pub storage: Option<StorageOverride>,
+}
+
+/// Account overrides applied by [`Evm::with_state_overrides`], mirroring the `eth_call` state
+/// override set.
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+impl<ERROR, BLOCK, TX, CFG, DB, CHAIN, HANDLER>
+    Evm<ERROR, Context<BLOCK, TX, CFG, DB, JournaledState<DB>, CHAIN>, HANDLER>
+where
+    DB: Database,
+{
+    /// Applies the state overrides to the journal.
+    ///
+    /// Overrides behave as if they were the database values: accounts and slots stay cold and
+    /// the original value of the overridden slots is the overridden value. Nothing is written to
+    /// the database, overrides are part of the state of the next executed transaction only.
+    ///
+    /// Overrides are meant for [`exec`][crate::EvmExec::exec] and its variants, whose result is
+    /// not committed. Committing the result, e.g. with
+    /// [`exec_commit`][crate::EvmCommit::exec_commit], writes the overridden values of touched
+    /// accounts to the database, and databases ignore the storage cleared status of replaced
+    /// storage.
+    ///
+    /// Accounts with replaced storage are marked as storage cleared, so unlisted slots are not
+    /// read from the database. They are not marked as created, an existing contract still can't
+    /// be destroyed by `SELFDESTRUCT` after Cancun.
+    pub fn with_state_overrides(mut self, overrides: StateOverride) -> Result<Self, DB::Error> {
+        let journal = &mut self.context.journaled_state;
+        for (address, account_override) in overrides {
+            let account = journal.initial_account_load(address, [])?;
+            account.status -= AccountStatus::LoadedAsNotExisting;
+            account.mark_cold();
+
+            if let Some(balance) = account_override.balance {
+                account.info.balance = balance;
+            }
+            if let Some(nonce) = account_override.nonce {
+                account.info.nonce = nonce;
+            }
+            if let Some(code) = account_override.code {
+                account.info.code_hash = code.hash_slow();
+                account.info.code = Some(code);
+            }
+
+            let slots = match account_override.storage {
+                Some(StorageOverride::Replace(slots)) => {
+                    account.storage.clear();
+                    account.mark_storage_cleared();
+                    slots
+                }
+                Some(StorageOverride::Diff(slots)) => slots,
+                None => continue,
+            };
+            for (key, value) in slots {
+                let mut slot = EvmStorageSlot::new(value);
+                slot.mark_cold();
+                account.storage.insert(key, slot);
+            }
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exec::EvmExec, MainEvm};
+    use bytecode::opcode::{
+        ADD, BALANCE, CALLER, MSTORE, PUSH1, PUSH20, RETURN, SELFDESTRUCT, SLOAD,
+    };
+    use database::CacheDB;
+    use database_interface::EmptyDB;
+    use handler::EthHandler;
+    use primitives::{Bytes, TxKind};
+    use specification::hardfork::SpecId;
+    use state::AccountInfo;
+    use std::vec;
+
+    /// Returns the sum of storage slots 0 and 1.
+    fn sum_slots_code() -> Bytecode {
+        Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, SLOAD, PUSH1, 0x01, SLOAD, ADD, PUSH1, 0x00, MSTORE, PUSH1, 0x20,
+                PUSH1, 0x00, RETURN,
+            ]
+            .into(),
+        )
+    }
+
+    fn call_with_storage(storage: Option<StorageOverride>) -> U256 {
+        let contract = Address::with_last_byte(0x42);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(contract, AccountInfo::from_bytecode(sum_slots_code()));
+        db.insert_account_storage(contract, U256::ZERO, U256::from(1))
+            .unwrap();
+        db.insert_account_storage(contract, U256::from(1), U256::from(10))
+            .unwrap();
+
+        let overrides = StateOverride::from_iter([(
+            contract,
+            AccountOverride {
+                storage,
+                ..Default::default()
+            },
+        )]);
+        let mut evm = MainEvm::new(
+            Context::builder().with_db(db).modify_tx_chained(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.kind = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            }),
+            EthHandler::default(),
+        )
+        .with_state_overrides(overrides)
+        .unwrap();
+
+        let output = evm.exec().unwrap().result.into_output().unwrap();
+        let db = &mut evm.context.journaled_state.database;
+        assert_eq!(db.storage(contract, U256::ZERO), Ok(U256::from(1)));
+        U256::from_be_slice(&output)
+    }
+
+    #[test]
+    fn storage_overrides() {
+        assert_eq!(call_with_storage(None), U256::from(11));
+
+        let slots = HashMap::from_iter([(U256::ZERO, U256::from(5))]);
+        assert_eq!(
+            call_with_storage(Some(StorageOverride::Diff(slots.clone()))),
+            U256::from(15)
+        );
+        assert_eq!(
+            call_with_storage(Some(StorageOverride::Replace(slots))),
+            U256::from(5)
+        );
+    }
+
+    #[test]
+    fn replaced_storage_does_not_allow_selfdestruct_after_cancun() {
+        let contract = Address::with_last_byte(0x42);
+        let beneficiary = Address::with_last_byte(0x43);
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(beneficiary.as_slice());
+        code.push(SELFDESTRUCT);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                balance: U256::from(100),
+                ..AccountInfo::from_bytecode(Bytecode::new_legacy(code.into()))
+            },
+        );
+        db.insert_account_storage(contract, U256::ZERO, U256::from(1))
+            .unwrap();
+
+        let overrides = StateOverride::from_iter([(
+            contract,
+            AccountOverride {
+                storage: Some(StorageOverride::Replace(HashMap::from_iter([(
+                    U256::from(1),
+                    U256::from(5),
+                )]))),
+                ..Default::default()
+            },
+        )]);
+        let mut evm = MainEvm::new(
+            Context::builder()
+                .with_db(db)
+                .with_spec(SpecId::CANCUN)
+                .modify_tx_chained(|tx| {
+                    tx.caller = Address::with_last_byte(1);
+                    tx.kind = TxKind::Call(contract);
+                }),
+            EthHandler::default(),
+        )
+        .with_state_overrides(overrides)
+        .unwrap();
+        let result = evm.exec().unwrap();
+        assert!(result.result.is_success());
+
+        // EIP-6780: only the balance is transferred, the contract is kept.
+        let account = &result.state[&contract];
+        assert!(!account.is_selfdestructed());
+        assert_eq!(account.info.balance, U256::ZERO);
+        assert!(!account.info.is_empty_code_hash());
+        assert_eq!(result.state[&beneficiary].info.balance, U256::from(100));
+    }
+
+    #[test]
+    fn account_overrides() {
+        let caller = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(0x42);
+        // Returns the balance of the caller.
+        let code = Bytecode::new_legacy(Bytes::from_static(&[
+            CALLER, BALANCE, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+        ]));
+        let overrides = StateOverride::from_iter([
+            (
+                caller,
+                AccountOverride {
+                    balance: Some(U256::from(1_000_000_000)),
+                    nonce: Some(7),
+                    ..Default::default()
+                },
+            ),
+            (
+                contract,
+                AccountOverride {
+                    code: Some(code),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let mut evm = MainEvm::new(
+            Context::builder()
+                .with_db(CacheDB::new(EmptyDB::default()))
+                .modify_tx_chained(|tx| {
+                    tx.caller = caller;
+                    tx.kind = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                    tx.nonce = 7;
+                }),
+            EthHandler::default(),
+        )
+        .with_state_overrides(overrides)
+        .unwrap();
+
+        let output = evm.exec().unwrap().result.into_output().unwrap();
+        assert_eq!(U256::from_be_slice(&output), U256::from(1_000_000_000));
+        let db = &mut evm.context.journaled_state.database;
+        assert_eq!(
+            db.basic(caller).unwrap().unwrap_or_default().balance,
+            U256::ZERO
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for testing precompiles against a ready [`Context`].
+use crate::{Context, Error};
+use database::InMemoryDB;
+use handler_interface::PrecompileProvider;
+use interpreter::InterpreterResult;
+use primitives::{address, Address, Bytes, U256};
+use specification::hardfork::SpecId;
+use state::AccountInfo;
+
+/// Context returned by [`test_context`].
+pub type TestContext =
+    Context<context::block::BlockEnv, context::tx::TxEnv, context::CfgEnv, InMemoryDB>;
+
+/// Error of the [`TestContext`].
+pub type TestError = Error<InMemoryDB>;
+
+/// Caller of the transaction of [`test_context`].
+pub const TEST_CALLER: Address = address!("0000000000000000000000000000000000001000");
+
+/// Balance of the [`TEST_CALLER`].
+pub const TEST_CALLER_BALANCE: U256 = U256::from_limbs([0, 0, 1, 0]);
+
+/// Returns a context with an in-memory database and the latest spec, so all precompiles are
+/// active.
+///
+/// The transaction caller is [`TEST_CALLER`], funded with [`TEST_CALLER_BALANCE`].
+pub fn test_context() -> TestContext {
+    let mut db = InMemoryDB::default();
+    db.insert_account_info(TEST_CALLER, AccountInfo::from_balance(TEST_CALLER_BALANCE));
+    TestContext::new(db, SpecId::LATEST).modify_tx_chained(|tx| tx.caller = TEST_CALLER)
+}
+
+/// Creates the precompile provider `P` for the context and runs the precompile at `address`.
+///
+/// Returns `None` if there is no precompile at `address`.
+pub fn run_precompile<P>(
+    context: &mut TestContext,
+    address: Address,
+    input: impl Into<Bytes>,
+    gas_limit: u64,
+) -> Result<Option<InterpreterResult>, P::Error>
+where
+    P: PrecompileProvider<Context = TestContext>,
+{
+    let mut precompiles = P::new(context);
+    precompiles.run(context, &address, &input.into(), gas_limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use handler::EthPrecompileProvider;
+    use interpreter::InstructionResult;
+
+    #[test]
+    fn identity_precompile() {
+        let mut context = test_context();
+        let identity = Address::with_last_byte(4);
+        let input = Bytes::from_static(&[1; 40]);
+
+        let result = run_precompile::<EthPrecompileProvider<_, TestError>>(
+            &mut context,
+            identity,
+            input.clone(),
+            100,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(result.result, InstructionResult::Return);
+        assert_eq!(result.output, input);
+        // Base cost of 15 and 3 per word.
+        assert_eq!(result.gas.spent(), 15 + 2 * 3);
+
+        let caller = context.journaled_state.load_account(TEST_CALLER).unwrap();
+        assert_eq!(caller.data.info.balance, TEST_CALLER_BALANCE);
+        assert!(run_precompile::<EthPrecompileProvider<_, TestError>>(
+            &mut context,
+            TEST_CALLER,
+            Bytes::new(),
+            100
+        )
+        .unwrap()
+        .is_none());
+    }
+}
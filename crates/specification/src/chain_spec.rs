@@ -0,0 +1,34 @@
+use crate::hardfork::{PrecompileSpecId, SpecId};
+use core::fmt::Debug;
+
+/// Activation rules of a chain.
+///
+/// Lets chains with custom activation rules, e.g. timestamp based activations, decide which
+/// precompiles are active at a block without forking the handler.
+///
+/// Only the precompile selection is honoured. Every other feature, such as opcodes, gas costs
+/// and validation rules, still follows the spec of the configuration.
+///
+/// Mainnet rules are implemented by [`SpecId`].
+pub trait ChainSpec: Debug + Send + Sync {
+    /// Returns the precompile spec active at the given block.
+    fn active_precompiles(&self, block: u64, timestamp: u64) -> PrecompileSpecId;
+}
+
+impl ChainSpec for SpecId {
+    fn active_precompiles(&self, _block: u64, _timestamp: u64) -> PrecompileSpecId {
+        PrecompileSpecId::from_spec_id(*self)
+    }
+}
+
+/// Chain specs are compared by identity.
+impl PartialEq for dyn ChainSpec {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(
+            self as *const Self as *const u8,
+            other as *const Self as *const u8,
+        )
+    }
+}
+
+impl Eq for dyn ChainSpec {}
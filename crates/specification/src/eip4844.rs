@@ -1,7 +1,7 @@
 //! EIP-4844 constants
 
 /// Gas consumption of a single data blob (== blob byte size)
-pub const GAS_PER_BLOB: u64 = 1 << 17;
+pub const GAS_PER_BLOB: u64 = primitives::eip4844::GAS_PER_BLOB;
 
 /// Target number of the blob per block
 pub const TARGET_BLOB_NUMBER_PER_BLOCK: u64 = 3;
@@ -16,10 +16,11 @@ pub const MAX_BLOB_GAS_PER_BLOCK: u64 = MAX_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB
 pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = TARGET_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB;
 
 /// Minimum gas price for data blobs
-pub const MIN_BLOB_GASPRICE: u64 = 1;
+pub const MIN_BLOB_GASPRICE: u64 = primitives::eip4844::MIN_BLOB_BASE_FEE;
 
 /// Controls the maximum rate of change for blob gas price
-pub const BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3338477;
+pub const BLOB_GASPRICE_UPDATE_FRACTION: u64 =
+    primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN;
 
 /// First version of the blob
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
@@ -0,0 +1,4 @@
+//! EIP-7825: Transaction gas limit cap
+
+/// Maximum gas limit of a transaction, enabled in Osaka.
+pub const TX_GAS_LIMIT_CAP: u64 = 1 << 24;
@@ -46,6 +46,34 @@ impl SpecId {
     pub const fn is_enabled_in(self, other: Self) -> bool {
         self as u8 >= other as u8
     }
+
+    /// Returns the name of the hardfork, see [`name`].
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FRONTIER => name::FRONTIER,
+            Self::FRONTIER_THAWING => name::FRONTIER_THAWING,
+            Self::HOMESTEAD => name::HOMESTEAD,
+            Self::DAO_FORK => name::DAO_FORK,
+            Self::TANGERINE => name::TANGERINE,
+            Self::SPURIOUS_DRAGON => name::SPURIOUS_DRAGON,
+            Self::BYZANTIUM => name::BYZANTIUM,
+            Self::CONSTANTINOPLE => name::CONSTANTINOPLE,
+            Self::PETERSBURG => name::PETERSBURG,
+            Self::ISTANBUL => name::ISTANBUL,
+            Self::MUIR_GLACIER => name::MUIR_GLACIER,
+            Self::BERLIN => name::BERLIN,
+            Self::LONDON => name::LONDON,
+            Self::ARROW_GLACIER => name::ARROW_GLACIER,
+            Self::GRAY_GLACIER => name::GRAY_GLACIER,
+            Self::MERGE => name::MERGE,
+            Self::SHANGHAI => name::SHANGHAI,
+            Self::CANCUN => name::CANCUN,
+            Self::PRAGUE => name::PRAGUE,
+            Self::OSAKA => name::OSAKA,
+            Self::LATEST => name::LATEST,
+        }
+    }
 }
 
 /// String identifiers for hardforks.
@@ -104,34 +132,46 @@ impl From<&str> for SpecId {
 
 impl From<SpecId> for &'static str {
     fn from(spec_id: SpecId) -> Self {
-        match spec_id {
-            SpecId::FRONTIER => name::FRONTIER,
-            SpecId::FRONTIER_THAWING => name::FRONTIER_THAWING,
-            SpecId::HOMESTEAD => name::HOMESTEAD,
-            SpecId::DAO_FORK => name::DAO_FORK,
-            SpecId::TANGERINE => name::TANGERINE,
-            SpecId::SPURIOUS_DRAGON => name::SPURIOUS_DRAGON,
-            SpecId::BYZANTIUM => name::BYZANTIUM,
-            SpecId::CONSTANTINOPLE => name::CONSTANTINOPLE,
-            SpecId::PETERSBURG => name::PETERSBURG,
-            SpecId::ISTANBUL => name::ISTANBUL,
-            SpecId::MUIR_GLACIER => name::MUIR_GLACIER,
-            SpecId::BERLIN => name::BERLIN,
-            SpecId::LONDON => name::LONDON,
-            SpecId::ARROW_GLACIER => name::ARROW_GLACIER,
-            SpecId::GRAY_GLACIER => name::GRAY_GLACIER,
-            SpecId::MERGE => name::MERGE,
-            SpecId::SHANGHAI => name::SHANGHAI,
-            SpecId::CANCUN => name::CANCUN,
-            SpecId::PRAGUE => name::PRAGUE,
-            SpecId::OSAKA => name::OSAKA,
-            SpecId::LATEST => name::LATEST,
-        }
+        spec_id.as_str()
     }
 }
 
 impl core::fmt::Display for SpecId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", <&'static str>::from(*self))
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum PrecompileSpecId {
+    HOMESTEAD,
+    BYZANTIUM,
+    ISTANBUL,
+    BERLIN,
+    CANCUN,
+    PRAGUE,
+    LATEST,
+}
+
+impl From<SpecId> for PrecompileSpecId {
+    fn from(spec_id: SpecId) -> Self {
+        Self::from_spec_id(spec_id)
+    }
+}
+
+impl PrecompileSpecId {
+    /// Returns the appropriate precompile Spec for the primitive [SpecId].
+    pub const fn from_spec_id(spec_id: SpecId) -> Self {
+        match spec_id {
+            FRONTIER | FRONTIER_THAWING | HOMESTEAD | DAO_FORK | TANGERINE | SPURIOUS_DRAGON => {
+                Self::HOMESTEAD
+            }
+            BYZANTIUM | CONSTANTINOPLE | PETERSBURG => Self::BYZANTIUM,
+            ISTANBUL | MUIR_GLACIER => Self::ISTANBUL,
+            BERLIN | LONDON | ARROW_GLACIER | GRAY_GLACIER | MERGE | SHANGHAI => Self::BERLIN,
+            CANCUN => Self::CANCUN,
+            PRAGUE | OSAKA => Self::PRAGUE,
+            LATEST => Self::LATEST,
+        }
     }
 }
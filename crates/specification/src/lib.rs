@@ -5,9 +5,11 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+pub mod chain_spec;
 pub mod constants;
 pub mod eip170;
 pub mod eip2;
 pub mod eip4844;
 pub mod eip7702;
+pub mod eip7825;
 pub mod hardfork;
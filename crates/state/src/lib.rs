@@ -25,6 +25,9 @@ pub struct Account {
     pub storage: EvmStorage,
     /// Account status flags
     pub status: AccountStatus,
+    /// Balance of the account when it was loaded, before it was changed by the transaction.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub original_balance: U256,
 }
 
 impl Account {
@@ -34,13 +37,23 @@ impl Account {
             info: AccountInfo::default(),
             storage: HashMap::default(),
             status: AccountStatus::LoadedAsNotExisting,
+            original_balance: U256::ZERO,
         }
     }
 
     /// Checks if account is empty and check if empty state before spurious dragon hardfork.
     #[inline]
     pub fn state_clear_aware_is_empty(&self, spec: SpecId) -> bool {
-        if SpecId::is_enabled_in(spec, SpecId::SPURIOUS_DRAGON) {
+        self.is_empty_with_state_clear(SpecId::is_enabled_in(spec, SpecId::SPURIOUS_DRAGON))
+    }
+
+    /// Checks if account is empty, with EIP-161 state clearing enabled or not.
+    ///
+    /// Without state clearing only accounts that were loaded as not existing and were not
+    /// touched are considered empty.
+    #[inline]
+    pub fn is_empty_with_state_clear(&self, state_clear: bool) -> bool {
+        if state_clear {
             self.is_empty()
         } else {
             let loaded_not_existing = self.is_loaded_as_not_existing();
@@ -94,6 +107,11 @@ impl Account {
         self.status |= AccountStatus::Cold;
     }
 
+    /// Is account marked as cold.
+    pub fn is_cold(&self) -> bool {
+        self.status.contains(AccountStatus::Cold)
+    }
+
     /// Marks the account as warm and return true if it was previously cold.
     pub fn mark_warm(&mut self) -> bool {
         if self.status.contains(AccountStatus::Cold) {
@@ -117,6 +135,19 @@ impl Account {
         self.status.contains(AccountStatus::Created)
     }
 
+    /// Marks the storage of the account as cleared.
+    pub fn mark_storage_cleared(&mut self) {
+        self.status |= AccountStatus::StorageCleared;
+    }
+
+    /// Is account storage cleared, slots that are not loaded are zero.
+    ///
+    /// Unlike [`Account::is_created`], the account is not considered as created in this
+    /// transaction.
+    pub fn is_storage_cleared(&self) -> bool {
+        self.status.contains(AccountStatus::StorageCleared)
+    }
+
     /// Is account empty, check if nonce and balance are zero and code is empty.
     pub fn is_empty(&self) -> bool {
         self.info.is_empty()
@@ -133,6 +164,7 @@ impl Account {
 impl From<AccountInfo> for Account {
     fn from(info: AccountInfo) -> Self {
         Self {
+            original_balance: info.balance,
             info,
             storage: HashMap::default(),
             status: AccountStatus::Loaded,
@@ -161,6 +193,9 @@ bitflags! {
         const LoadedAsNotExisting = 0b0001000;
         /// used to mark account as cold
         const Cold = 0b0010000;
+        /// Storage of the account was replaced, slots that are not present in the account storage
+        /// are zero and are not fetched from database.
+        const StorageCleared = 0b0100000;
     }
 }
 
@@ -11,14 +11,11 @@ use database::{AlloyDB, CacheDB, StateBuilder};
 use indicatif::ProgressBar;
 use inspector::{
     inspector_context::InspectorContext, inspectors::TracerEip3155, InspectorEthFrame,
-    InspectorMainEvm,
+    InspectorEthPreExecution, InspectorEthPrecompileProvider, InspectorMainEvm,
 };
 use revm::{
     database_interface::WrapDatabaseAsync,
-    handler::{
-        EthExecution, EthHandler, EthPostExecution, EthPreExecution, EthPrecompileProvider,
-        EthValidation,
-    },
+    handler::{EthExecution, EthHandler, EthPostExecution, EthValidation},
     primitives::TxKind,
     Context, EvmCommit,
 };
@@ -103,8 +100,8 @@ async fn main() -> anyhow::Result<()> {
         ),
         EthHandler::new(
             EthValidation::new(),
-            EthPreExecution::new(),
-            EthExecution::<_, _, InspectorEthFrame<_, _, EthPrecompileProvider<_, _>>>::new(),
+            InspectorEthPreExecution::new(),
+            EthExecution::<_, _, InspectorEthFrame<_, _, InspectorEthPrecompileProvider<_, _>>>::new(),
             EthPostExecution::new(),
         ),
     );